@@ -5,6 +5,9 @@
 //! Features:
 //! - `Health` component to track current and maximum health.
 //! - `HealthApplyDamage` event to apply damage to entities.
+//! - `HealthDamaged` event triggered after damage changed an entity's health.
+//! - `HealthLastDamage` component remembering who damaged an entity last and how.
+//! - `HealthDamageHooks` resource to intercept or modify damage before it is applied.
 //! - `DestroyedMarker` component added when an entity's health reaches zero.
 //!
 //! Usage:
@@ -18,14 +21,24 @@
 //!     target: entity,
 //!     source: Some(player_entity),
 //!     amount: 25.0,
+//!     kind: DamageKind::Projectile,
 //! });
+//!
+//! // Intercept the damage before it reaches the health, e.g. for a shield
+//! fn halve_damage(_world: &mut DeferredWorld, damage: &mut HealthApplyDamage) {
+//!     damage.amount /= 2.0;
+//! }
+//! app.world_mut()
+//!     .resource_mut::<HealthDamageHooks>()
+//!     .add(halve_damage);
 //! ```
 
-use bevy::prelude::*;
+use bevy::{ecs::world::DeferredWorld, prelude::*};
 
 pub mod prelude {
     pub use super::{
-        DestroyedMarker, Health, HealthApplyDamage, HealthPlugin, HealthPluginSystems,
+        DamageKind, DestroyedMarker, Health, HealthApplyDamage, HealthDamageHook,
+        HealthDamageHooks, HealthDamaged, HealthLastDamage, HealthPlugin, HealthPluginSystems,
     };
 }
 
@@ -47,6 +60,15 @@ impl Health {
     pub fn new(max: f32) -> Self {
         Self { current: max, max }
     }
+
+    /// The current health as a fraction of the maximum, in `[0, 1]`.
+    pub fn fraction(&self) -> f32 {
+        if self.max <= 0.0 {
+            return 0.0;
+        }
+
+        (self.current / self.max).clamp(0.0, 1.0)
+    }
 }
 
 /// Marker component indicating that an entity has been destroyed.
@@ -57,6 +79,45 @@ impl Health {
 #[derive(Component, Clone, Debug, Reflect)]
 pub struct DestroyedMarker;
 
+/// What caused some damage.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect)]
+pub enum DamageKind {
+    /// Two bodies crashed into each other, a kinetic impact.
+    Collision,
+    /// A projectile hit the entity.
+    Projectile,
+    /// The entity was caught in an explosion.
+    Explosive,
+    /// The damage was applied by a script or the game rules.
+    #[default]
+    Scripted,
+}
+
+impl DamageKind {
+    /// The name of the damage kind, as used by scenario scripts.
+    pub fn name(&self) -> &'static str {
+        match self {
+            DamageKind::Collision => "collision",
+            DamageKind::Projectile => "projectile",
+            DamageKind::Explosive => "explosive",
+            DamageKind::Scripted => "scripted",
+        }
+    }
+}
+
+/// The last damage that changed the health of an entity.
+///
+/// This is inserted by the `on_damage` system before the `DestroyedMarker`,
+/// so it can be used to find who destroyed an entity.
+#[derive(Component, Clone, Debug, Reflect)]
+pub struct HealthLastDamage {
+    /// Optional source entity that caused the damage.
+    pub source: Option<Entity>,
+
+    /// What caused the damage.
+    pub kind: DamageKind,
+}
+
 /// Event to apply damage to a target entity.
 ///
 /// `amount` is subtracted from the target's current health. If health reaches
@@ -71,6 +132,48 @@ pub struct HealthApplyDamage {
 
     /// Amount of damage to apply.
     pub amount: f32,
+
+    /// What caused the damage.
+    pub kind: DamageKind,
+}
+
+/// Event triggered after a `HealthApplyDamage` changed the health of an entity.
+///
+/// It is triggered before the `DestroyedMarker` is added, so observers see
+/// the final hit before the destruction.
+#[derive(Event, Clone, Debug)]
+pub struct HealthDamaged {
+    /// The entity that received damage.
+    pub target: Entity,
+
+    /// Optional source entity that caused the damage.
+    pub source: Option<Entity>,
+
+    /// The amount of health that was actually removed.
+    pub amount: f32,
+
+    /// What caused the damage.
+    pub kind: DamageKind,
+
+    /// The health of the target after the damage.
+    pub health: Health,
+}
+
+/// A pre-damage hook. It runs before the damage of a `HealthApplyDamage` is
+/// applied to a living target, and can modify the damage, or absorb all of it
+/// by setting the amount to zero.
+pub type HealthDamageHook = fn(&mut DeferredWorld, &mut HealthApplyDamage);
+
+/// The pre-damage hooks, run by the `on_damage` system in the order they were
+/// added.
+#[derive(Resource, Clone, Debug, Default)]
+pub struct HealthDamageHooks(Vec<HealthDamageHook>);
+
+impl HealthDamageHooks {
+    /// Add a hook that runs after the hooks that were already added.
+    pub fn add(&mut self, hook: HealthDamageHook) {
+        self.0.push(hook);
+    }
 }
 
 /// System set for the Health plugin.
@@ -88,6 +191,8 @@ impl Plugin for HealthPlugin {
     fn build(&self, app: &mut App) {
         debug!("HealthPlugin: build");
 
+        app.init_resource::<HealthDamageHooks>();
+
         // Listen for damage events and apply them to entities
         app.add_observer(on_damage);
     }
@@ -95,34 +200,69 @@ impl Plugin for HealthPlugin {
 
 /// System to handle `HealthApplyDamage` events.
 ///
-/// Reduces the target's current health by the damage amount. If health
-/// reaches zero, adds `DestroyedMarker`.
-fn on_damage(
-    damage: On<HealthApplyDamage>,
-    mut commands: Commands,
-    mut q_health: Query<(Entity, &mut Health, Has<DestroyedMarker>)>,
-) {
+/// Runs the `HealthDamageHooks`, then reduces the target's current health by
+/// what is left of the damage, records it in `HealthLastDamage` and triggers
+/// `HealthDamaged`. If health reaches zero, adds `DestroyedMarker`.
+fn on_damage(damage: On<HealthApplyDamage>, mut world: DeferredWorld) {
     let target = damage.target;
     trace!("on_damage: target {:?}, damage {:?}", target, damage.amount);
 
-    let Ok((entity, mut health, destroyed)) = q_health.get_mut(target) else {
-        trace!("on_damage: entity {:?} not found in q_health", target);
+    let Some(health) = world.get::<Health>(target) else {
+        trace!("on_damage: entity {:?} has no Health", target);
         return;
     };
 
-    if destroyed {
-        trace!("on_damage: entity {:?} is already destroyed", entity);
+    if world.get::<DestroyedMarker>(target).is_some() {
+        trace!("on_damage: entity {:?} is already destroyed", target);
         return;
     }
 
     if health.current <= 0.0 {
-        trace!("on_damage: entity {:?} health is already zero", entity);
+        trace!("on_damage: entity {:?} health is already zero", target);
         return;
     }
 
+    let mut damage = damage.event().clone();
+    let hooks = world.resource::<HealthDamageHooks>().clone();
+    for hook in hooks.0.iter() {
+        hook(&mut world, &mut damage);
+    }
+
+    if damage.amount <= 0.0 {
+        trace!("on_damage: damage to entity {:?} was absorbed", target);
+        return;
+    }
+
+    let Some(mut health) = world.get_mut::<Health>(target) else {
+        error!("on_damage: entity {:?} lost its Health in a hook", target);
+        return;
+    };
+
+    let previous = health.current;
     health.current -= damage.amount;
     if health.current <= 0.0 {
         health.current = 0.0;
-        commands.entity(entity).insert(DestroyedMarker);
+    }
+
+    let amount = previous - health.current;
+    let health = health.clone();
+
+    let mut commands = world.commands();
+    if amount > 0.0 {
+        commands.entity(target).insert(HealthLastDamage {
+            source: damage.source,
+            kind: damage.kind,
+        });
+        commands.trigger(HealthDamaged {
+            target,
+            source: damage.source,
+            amount,
+            kind: damage.kind,
+            health: health.clone(),
+        });
+    }
+
+    if health.current <= 0.0 {
+        commands.entity(target).insert(DestroyedMarker);
     }
 }
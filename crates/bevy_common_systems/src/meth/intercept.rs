@@ -0,0 +1,162 @@
+use bevy::prelude::*;
+
+/// Compute the time until a projectile fired now can hit a moving target.
+///
+/// - `shooter_position`: Where the projectile is fired from.
+/// - `shooter_velocity`: The velocity of the shooter, which the projectile inherits.
+/// - `target_position`: The current position of the target.
+/// - `target_velocity`: The velocity of the target, assumed constant.
+/// - `projectile_speed`: The speed of the projectile relative to the shooter.
+///
+/// Returns the smallest positive time of flight, or `None` if the projectile can never catch
+/// the target.
+pub fn intercept_time(
+    shooter_position: Vec3,
+    shooter_velocity: Vec3,
+    target_position: Vec3,
+    target_velocity: Vec3,
+    projectile_speed: f32,
+) -> Option<f32> {
+    // Solve |offset + velocity * t| = speed * t in the frame of the shooter
+    let offset = target_position - shooter_position;
+    let velocity = target_velocity - shooter_velocity;
+
+    let a = velocity.length_squared() - projectile_speed * projectile_speed;
+    let b = 2.0 * offset.dot(velocity);
+    let c = offset.length_squared();
+
+    if a.abs() < f32::EPSILON {
+        // The projectile is exactly as fast as the target, the equation is linear
+        if b >= 0.0 {
+            return None;
+        }
+        return Some(-c / b);
+    }
+
+    let discriminant = b * b - 4.0 * a * c;
+    if discriminant < 0.0 {
+        return None;
+    }
+
+    let sqrt = discriminant.sqrt();
+    let t1 = (-b - sqrt) / (2.0 * a);
+    let t2 = (-b + sqrt) / (2.0 * a);
+
+    match (t1 >= 0.0, t2 >= 0.0) {
+        (true, true) => Some(t1.min(t2)),
+        (true, false) => Some(t1),
+        (false, true) => Some(t2),
+        (false, false) => None,
+    }
+}
+
+/// Compute the point to aim at so that a projectile fired now hits a moving target.
+///
+/// The arguments are the same as for `intercept_time`. Because the projectile inherits the
+/// velocity of the shooter, the returned point is the direction to fire in, not where the hit
+/// happens.
+///
+/// Returns `None` if the projectile can never catch the target.
+pub fn intercept_point(
+    shooter_position: Vec3,
+    shooter_velocity: Vec3,
+    target_position: Vec3,
+    target_velocity: Vec3,
+    projectile_speed: f32,
+) -> Option<Vec3> {
+    let time = intercept_time(
+        shooter_position,
+        shooter_velocity,
+        target_position,
+        target_velocity,
+        projectile_speed,
+    )?;
+
+    Some(target_position + (target_velocity - shooter_velocity) * time)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intercept_static_target() {
+        let point = intercept_point(
+            Vec3::ZERO,
+            Vec3::ZERO,
+            Vec3::new(0.0, 0.0, -100.0),
+            Vec3::ZERO,
+            50.0,
+        );
+        assert_eq!(point, Some(Vec3::new(0.0, 0.0, -100.0)));
+
+        let time = intercept_time(
+            Vec3::ZERO,
+            Vec3::ZERO,
+            Vec3::new(0.0, 0.0, -100.0),
+            Vec3::ZERO,
+            50.0,
+        );
+        assert!((time.unwrap() - 2.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_intercept_crossing_target() {
+        let target_position = Vec3::new(0.0, 0.0, -100.0);
+        let target_velocity = Vec3::new(10.0, 0.0, 0.0);
+        let speed = 50.0;
+
+        let point = intercept_point(
+            Vec3::ZERO,
+            Vec3::ZERO,
+            target_position,
+            target_velocity,
+            speed,
+        )
+        .unwrap();
+        let time = intercept_time(
+            Vec3::ZERO,
+            Vec3::ZERO,
+            target_position,
+            target_velocity,
+            speed,
+        )
+        .unwrap();
+
+        // The projectile and the target meet at the aim point
+        let projectile = point.normalize() * speed * time;
+        let target = target_position + target_velocity * time;
+        assert!(projectile.abs_diff_eq(target, 1e-3));
+        assert!(point.x > 0.0);
+    }
+
+    #[test]
+    fn test_intercept_moving_shooter() {
+        let shooter_velocity = Vec3::new(0.0, 0.0, -5.0);
+
+        // Both move together, so the aim point is the target itself
+        let point = intercept_point(
+            Vec3::ZERO,
+            shooter_velocity,
+            Vec3::new(0.0, 0.0, -100.0),
+            shooter_velocity,
+            50.0,
+        );
+        assert!(point
+            .unwrap()
+            .abs_diff_eq(Vec3::new(0.0, 0.0, -100.0), 1e-5));
+    }
+
+    #[test]
+    fn test_intercept_unreachable_target() {
+        // The target runs away faster than the projectile
+        let point = intercept_point(
+            Vec3::ZERO,
+            Vec3::ZERO,
+            Vec3::new(0.0, 0.0, -100.0),
+            Vec3::new(0.0, 0.0, -80.0),
+            50.0,
+        );
+        assert_eq!(point, None);
+    }
+}
@@ -1,15 +1,17 @@
 //! Utilities for vector math and interpolation in 3D space.
 //!
 //! This module provides helper functions and traits for smooth value interpolation
-//! (`LerpSnap`) and spherical coordinate conversions and operations (`sphere`).
+//! (`LerpSnap`), spherical coordinate conversions and operations (`sphere`) and the
+//! intercept solver used to lead moving targets (`intercept`).
 
+pub mod intercept;
 pub mod lerp;
 pub mod sphere;
 
 /// The prelude re-exports the most commonly used math utilities.
 ///
 /// Use `bevy_common_systems::meth::prelude::*` to easily access `LerpSnap` for smooth
-/// interpolation and all spherical math functions from `sphere`.
+/// interpolation, all spherical math functions from `sphere` and the intercept solver.
 pub mod prelude {
-    pub use super::{lerp::LerpSnap, sphere::*};
+    pub use super::{intercept::*, lerp::LerpSnap, sphere::*};
 }
@@ -18,6 +18,8 @@ pub struct CollisionImpactMarker;
 pub struct CollisionImpactEvent {
     /// The entity that took the impact.
     pub entity: Entity,
+    /// The collider of `entity` that was hit.
+    pub collider: Entity,
     /// The other entity involved in the collision.
     pub other: Entity,
     // /// The point of impact in world space.
@@ -59,9 +61,15 @@ fn on_collision_event(
     collision: On<CollisionStart>,
     mut commands: Commands,
     q_velocity: Query<&LinearVelocity, With<RigidBody>>,
+    q_sensor: Query<(), With<Sensor>>,
 ) {
     trace!("on_collision_event: collision {:?}", collision);
 
+    // Sensors only detect overlaps, nothing actually hits them.
+    if q_sensor.contains(collision.collider1) || q_sensor.contains(collision.collider2) {
+        return;
+    }
+
     let Some(body) = collision.body1 else {
         return;
     };
@@ -80,6 +88,7 @@ fn on_collision_event(
 
     commands.trigger(CollisionImpactEvent {
         entity: body,
+        collider: collision.collider1,
         other,
         // hit_point: collision.contact_point,
         // hit_normal: collision.contact_normal,
@@ -3,13 +3,21 @@
 use bevy::prelude::*;
 use bevy_asset_loader::prelude::*;
 
-use crate::{scenario::register_scenario, sections::register_sections};
+use crate::{
+    scenario::{
+        register_scenario, ScenarioAsset, ScenarioAssetLoader, ScenarioManifestAsset,
+        ScenarioManifestAssetLoader,
+    },
+    sections::{register_sections, GameSectionsAsset, GameSectionsAssetLoader},
+};
 
 mod scenario;
 mod sections;
 
 pub mod prelude {
-    pub use super::{GameAssets, GameAssetsPlugin, GameAssetsStates};
+    pub use super::{
+        scenario::prelude::*, sections::prelude::*, GameAssets, GameAssetsPlugin, GameAssetsStates,
+    };
 }
 
 /// Game states for the asset loader.
@@ -28,6 +36,16 @@ impl Plugin for GameAssetsPlugin {
     fn build(&self, app: &mut App) {
         debug!("GameAssetsPlugin: build");
 
+        // Scenarios are loaded from `*.scenario.ron` and `*.scenario.json` files.
+        app.init_asset::<ScenarioAsset>();
+        app.init_asset_loader::<ScenarioAssetLoader>();
+        // The scenarios of the game are listed in `*.scenarios.ron` and `*.scenarios.json` files.
+        app.init_asset::<ScenarioManifestAsset>();
+        app.init_asset_loader::<ScenarioManifestAssetLoader>();
+        // Sections are loaded from `*.sections.ron` and `*.sections.json` files.
+        app.init_asset::<GameSectionsAsset>();
+        app.init_asset_loader::<GameSectionsAssetLoader>();
+
         // Setup the asset loader to load assets during the loading state.
         app.init_state::<GameAssetsStates>();
         app.add_loading_state(
@@ -56,14 +74,11 @@ pub struct GameAssets {
     pub cubemap: Handle<Image>,
     #[asset(path = "textures/asteroid.png")]
     pub asteroid_texture: Handle<Image>,
-    #[asset(path = "gltf/hull-01.glb#Scene0")]
-    pub hull_01: Handle<Scene>,
-    #[asset(path = "gltf/turret-yaw-01.glb#Scene0")]
-    pub turret_yaw_01: Handle<Scene>,
-    #[asset(path = "gltf/turret-pitch-01.glb#Scene0")]
-    pub turret_pitch_01: Handle<Scene>,
-    #[asset(path = "gltf/turret-barrel-01.glb#Scene0")]
-    pub turret_barrel_01: Handle<Scene>,
+    #[asset(path = "sections/default.sections.ron")]
+    pub sections: Handle<GameSectionsAsset>,
     #[asset(path = "icons/fps.png")]
     pub fps_icon: Handle<Image>,
+    /// The list of scenario files, a new scenario is added to the game by listing it here.
+    #[asset(path = "scenarios/index.scenarios.ron")]
+    pub scenarios: Handle<ScenarioManifestAsset>,
 }
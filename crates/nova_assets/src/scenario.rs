@@ -1,340 +1,1175 @@
-use bevy::{platform::collections::HashMap, prelude::*};
+//! Scenario files and the asset loader that reads them.
+//!
+//! Scenarios are described in `*.scenario.ron` or `*.scenario.json` files. The files mirror the
+//! `ScenarioConfig` types from `nova_scenario`, but they reference assets by path and spaceship
+//! sections by their id in `GameSections`. The paths are turned into handles by the loader, and
+//! the sections are resolved once the `GameSections` resource is available.
+//!
+//! The scenario files of the game are listed in a `*.scenarios.ron` or `*.scenarios.json`
+//! manifest, so that a new scenario only needs a new file and a line in the manifest.
+
+use bevy::{
+    asset::{io::Reader, AssetLoader, LoadContext},
+    platform::collections::HashMap,
+    prelude::*,
+    reflect::{DynamicEnum, DynamicVariant},
+};
+use bevy_enhanced_input::prelude::Binding;
 use nova_gameplay::prelude::*;
 use nova_scenario::prelude::*;
-use rand::prelude::*;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+pub mod prelude {
+    pub use super::{
+        AsteroidAsset, AsteroidFieldAsset, DelayActionAsset, EventActionAsset, InputBindingAsset,
+        PlayerControllerAsset, ScenarioAsset, ScenarioAssetError, ScenarioAssetLoader,
+        ScenarioAssetLoaderError, ScenarioEventAsset, ScenarioManifestAsset,
+        ScenarioManifestAssetLoader, ScenarioObjectAsset, ScenarioObjectKindAsset,
+        ScenarioValidationError, SpaceshipAsset, SpaceshipControllerAsset, SpaceshipSectionAsset,
+    };
+}
+
+/// A scenario as it is written in a scenario file.
+#[derive(Asset, TypePath, Clone, Debug, serde::Deserialize)]
+pub struct ScenarioAsset {
+    /// Unique identifier for the scenario
+    pub id: ScenarioId,
+    /// The display name of the scenario
+    pub name: String,
+    /// A brief description of the scenario
+    pub description: String,
+    /// The path to the cubemap image used for the scenario's skybox
+    pub cubemap: String,
+    /// Events associated with the scenario
+    pub events: Vec<ScenarioEventAsset>,
+    /// The images referenced by the scenario, loaded together with it.
+    #[serde(skip)]
+    #[dependency]
+    images: Vec<Handle<Image>>,
+}
+
+/// A scenario event as it is written in a scenario file.
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct ScenarioEventAsset {
+    /// The name of the event to listen for
+    pub name: EventConfig,
+    /// Filters to apply to the event
+    #[serde(default)]
+    pub filters: Vec<EventFilterConfig>,
+    /// Actions to perform when the event is triggered
+    pub actions: Vec<EventActionAsset>,
+}
+
+/// An event action as it is written in a scenario file.
+#[derive(Clone, Debug, serde::Deserialize)]
+pub enum EventActionAsset {
+    DebugMessage(DebugMessageActionConfig),
+    VariableSet(VariableSetActionConfig),
+    Objective(ObjectiveActionConfig),
+    ObjectiveComplete(ObjectiveCompleteActionConfig),
+    SpawnScenarioObject(ScenarioObjectAsset),
+    SpawnAsteroidField(AsteroidFieldAsset),
+    NextScenario(NextScenarioActionConfig),
+    Delay(DelayActionAsset),
+    Timer(TimerActionConfig),
+    CancelTimer(CancelTimerActionConfig),
+}
+
+/// A delay action as it is written in a scenario file.
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct DelayActionAsset {
+    pub seconds: f32,
+    pub actions: Vec<EventActionAsset>,
+}
+
+/// A scenario object as it is written in a scenario file.
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct ScenarioObjectAsset {
+    pub base: BaseScenarioObjectConfig,
+    pub kind: ScenarioObjectKindAsset,
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+pub enum ScenarioObjectKindAsset {
+    Asteroid(AsteroidAsset),
+    Spaceship(SpaceshipAsset),
+    TriggerZone(TriggerZoneConfig),
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct AsteroidAsset {
+    pub radius: f32,
+    /// The path to the texture of the asteroid
+    pub texture: String,
+}
+
+/// A field of asteroids scattered at random, spawned by a single action. The field is scattered
+/// when the scenarios are registered, from a seed derived from the ids of the scenario and the
+/// field, so the asteroids keep their position and size across games and saves.
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct AsteroidFieldAsset {
+    /// The id of the asteroids, followed by their index (e.g `asteroid_3`)
+    pub id: String,
+    /// The name of the asteroids, followed by their index (e.g `Asteroid 3`)
+    pub name: String,
+    pub count: usize,
+    #[serde(default)]
+    pub center: Vec3,
+    /// The half size of the box around the center in which the asteroids are scattered
+    pub extent: Vec3,
+    pub min_radius: f32,
+    pub max_radius: f32,
+    pub health: f32,
+    /// The path to the texture of the asteroids
+    pub texture: String,
+}
+
+impl AsteroidFieldAsset {
+    /// The random number generator of the field in the given scenario, always seeded the same.
+    pub fn rng(&self, scenario: &ScenarioId) -> StdRng {
+        // FNV-1a, which unlike the std hasher is stable across Rust versions
+        let seed = scenario
+            .bytes()
+            .chain([0])
+            .chain(self.id.bytes())
+            .fold(0xcbf29ce484222325_u64, |hash, byte| {
+                (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+            });
+
+        StdRng::seed_from_u64(seed)
+    }
+
+    /// Scatter the asteroids of the field.
+    pub fn asteroids(&self, rng: &mut impl Rng) -> Vec<ScenarioObjectAsset> {
+        (0..self.count)
+            .map(|index| {
+                let offset = Vec3::new(
+                    rng.random_range(-self.extent.x..=self.extent.x),
+                    rng.random_range(-self.extent.y..=self.extent.y),
+                    rng.random_range(-self.extent.z..=self.extent.z),
+                );
+
+                ScenarioObjectAsset {
+                    base: BaseScenarioObjectConfig {
+                        id: format!("{}_{}", self.id, index),
+                        name: format!("{} {}", self.name, index),
+                        position: self.center + offset,
+                        rotation: Quat::IDENTITY,
+                        health: self.health,
+                        faction: None,
+                        explosion: None,
+                    },
+                    kind: ScenarioObjectKindAsset::Asteroid(AsteroidAsset {
+                        radius: rng.random_range(self.min_radius..=self.max_radius),
+                        texture: self.texture.clone(),
+                    }),
+                }
+            })
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct SpaceshipAsset {
+    pub controller: SpaceshipControllerAsset,
+    pub sections: Vec<SpaceshipSectionAsset>,
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+pub enum SpaceshipControllerAsset {
+    None,
+    Player(PlayerControllerAsset),
+    AI(AIControllerConfig),
+}
+
+#[derive(Clone, Debug, Default, serde::Deserialize)]
+pub struct PlayerControllerAsset {
+    /// The input bindings of each section, by section id
+    #[serde(default)]
+    pub input_mapping: HashMap<SectionId, Vec<InputBindingAsset>>,
+}
+
+/// An input binding, given by the name of the key or button (e.g `Keyboard("Space")`).
+#[derive(Clone, Debug, serde::Deserialize)]
+pub enum InputBindingAsset {
+    Keyboard(String),
+    Mouse(String),
+    Gamepad(String),
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct SpaceshipSectionAsset {
+    pub id: SectionId,
+    pub position: Vec3,
+    #[serde(default)]
+    pub rotation: Quat,
+    /// The id of the section in `GameSections`
+    pub section: String,
+}
+
+/// Errors that can happen while reading a scenario file.
+#[derive(Debug, thiserror::Error)]
+pub enum ScenarioAssetLoaderError {
+    #[error("could not read scenario file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("could not parse RON scenario file: {0}")]
+    Ron(#[from] ron::error::SpannedError),
+    #[error("could not parse JSON scenario file: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("invalid scenario file: {0}")]
+    Invalid(#[from] ScenarioValidationError),
+}
+
+/// Errors found while validating the values of a scenario file.
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum ScenarioValidationError {
+    #[error("scenario '{scenario}': {field} must be positive, got {value}")]
+    NotPositive {
+        scenario: ScenarioId,
+        field: &'static str,
+        value: f32,
+    },
+    #[error("scenario '{scenario}': {field} must not be negative, got {value}")]
+    Negative {
+        scenario: ScenarioId,
+        field: &'static str,
+        value: f32,
+    },
+    #[error("scenario '{scenario}': {field} must not be empty")]
+    Empty {
+        scenario: ScenarioId,
+        field: &'static str,
+    },
+    #[error("scenario '{scenario}': min_{field} {min} is greater than max_{field} {max}")]
+    InvalidRange {
+        scenario: ScenarioId,
+        field: &'static str,
+        min: f32,
+        max: f32,
+    },
+}
+
+/// Errors that can happen while turning a `ScenarioAsset` into a `ScenarioConfig`.
+#[derive(Debug, thiserror::Error)]
+pub enum ScenarioAssetError {
+    #[error("scenario '{scenario}': section '{section}' not found in GameSections")]
+    UnknownSection {
+        scenario: ScenarioId,
+        section: String,
+    },
+    #[error("scenario '{scenario}': unknown input binding {binding:?}")]
+    UnknownBinding {
+        scenario: ScenarioId,
+        binding: InputBindingAsset,
+    },
+    #[error("scenario '{scenario}': spaceship '{object}' has an invalid design: {error}")]
+    InvalidSpaceship {
+        scenario: ScenarioId,
+        object: String,
+        error: SpaceshipDesignError,
+    },
+}
+
+/// Asset loader for `*.scenario.ron` and `*.scenario.json` files.
+#[derive(Default, TypePath)]
+pub struct ScenarioAssetLoader;
+
+impl AssetLoader for ScenarioAssetLoader {
+    type Asset = ScenarioAsset;
+    type Settings = ();
+    type Error = ScenarioAssetLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &Self::Settings,
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+
+        let is_json = load_context
+            .path()
+            .extension()
+            .is_some_and(|extension| extension == "json");
+        let mut scenario: ScenarioAsset = if is_json {
+            serde_json::from_slice(&bytes)?
+        } else {
+            ron::de::from_bytes(&bytes)?
+        };
+        validate_scenario(&scenario)?;
+
+        scenario.images = scenario
+            .image_paths()
+            .into_iter()
+            .map(|path| load_context.load::<Image>(path))
+            .collect();
+
+        Ok(scenario)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["scenario.ron", "scenario.json"]
+    }
+}
+
+/// The scenarios listed in a scenario manifest, loaded together with it.
+#[derive(Asset, TypePath, Clone, Debug)]
+pub struct ScenarioManifestAsset {
+    #[dependency]
+    pub scenarios: Vec<Handle<ScenarioAsset>>,
+}
+
+/// Asset loader for `*.scenarios.ron` and `*.scenarios.json` files, a list of paths to scenario
+/// files from the assets folder.
+#[derive(Default, TypePath)]
+pub struct ScenarioManifestAssetLoader;
+
+impl AssetLoader for ScenarioManifestAssetLoader {
+    type Asset = ScenarioManifestAsset;
+    type Settings = ();
+    type Error = ScenarioAssetLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &Self::Settings,
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+
+        let is_json = load_context
+            .path()
+            .extension()
+            .is_some_and(|extension| extension == "json");
+        let paths: Vec<String> = if is_json {
+            serde_json::from_slice(&bytes)?
+        } else {
+            ron::de::from_bytes(&bytes)?
+        };
+
+        let scenarios = paths
+            .into_iter()
+            .map(|path| load_context.load::<ScenarioAsset>(path))
+            .collect();
+
+        Ok(ScenarioManifestAsset { scenarios })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["scenarios.ron", "scenarios.json"]
+    }
+}
+
+/// Check that the values of the scenario make sense, so that a bad file is rejected when it is
+/// loaded instead of misbehaving in the middle of a game.
+fn validate_scenario(scenario: &ScenarioAsset) -> Result<(), ScenarioValidationError> {
+    for event in scenario.events.iter() {
+        validate_actions(&scenario.id, &event.actions)?;
+    }
+
+    Ok(())
+}
+
+fn validate_actions(
+    scenario: &ScenarioId,
+    actions: &[EventActionAsset],
+) -> Result<(), ScenarioValidationError> {
+    let positive = |field: &'static str, value: f32| {
+        if value > 0.0 && value.is_finite() {
+            Ok(())
+        } else {
+            Err(ScenarioValidationError::NotPositive {
+                scenario: scenario.clone(),
+                field,
+                value,
+            })
+        }
+    };
+    let not_negative = |field: &'static str, value: f32| {
+        if value >= 0.0 && value.is_finite() {
+            Ok(())
+        } else {
+            Err(ScenarioValidationError::Negative {
+                scenario: scenario.clone(),
+                field,
+                value,
+            })
+        }
+    };
+
+    for action in actions.iter() {
+        match action {
+            EventActionAsset::SpawnScenarioObject(config) => {
+                if let Some(explosion) = &config.base.explosion {
+                    positive("explosion.radius", explosion.radius)?;
+                    not_negative("explosion.damage", explosion.damage)?;
+                }
+                if let ScenarioObjectKindAsset::Spaceship(SpaceshipAsset {
+                    controller: SpaceshipControllerAsset::AI(ai),
+                    ..
+                }) = &config.kind
+                {
+                    match &ai.behaviour {
+                        AIBehaviour::Pursue {
+                            max_speed,
+                            fire_range,
+                        } => {
+                            positive("max_speed", *max_speed)?;
+                            positive("fire_range", *fire_range)?;
+                        }
+                        AIBehaviour::Orbit {
+                            radius,
+                            speed,
+                            fire_range,
+                        } => {
+                            positive("radius", *radius)?;
+                            positive("speed", *speed)?;
+                            positive("fire_range", *fire_range)?;
+                        }
+                        AIBehaviour::Snipe {
+                            distance,
+                            max_speed,
+                            fire_range,
+                        } => {
+                            positive("distance", *distance)?;
+                            positive("max_speed", *max_speed)?;
+                            positive("fire_range", *fire_range)?;
+                        }
+                        AIBehaviour::Flee {
+                            safe_distance,
+                            max_speed,
+                        } => {
+                            positive("safe_distance", *safe_distance)?;
+                            positive("max_speed", *max_speed)?;
+                        }
+                        AIBehaviour::Patrol {
+                            max_speed,
+                            arrive_distance,
+                            fire_range,
+                            ..
+                        } => {
+                            positive("max_speed", *max_speed)?;
+                            not_negative("arrive_distance", *arrive_distance)?;
+                            positive("fire_range", *fire_range)?;
+                        }
+                        AIBehaviour::Guard {
+                            target_id,
+                            radius,
+                            engage_range,
+                            max_speed,
+                        } => {
+                            if target_id.is_empty() {
+                                return Err(ScenarioValidationError::Empty {
+                                    scenario: scenario.clone(),
+                                    field: "target_id",
+                                });
+                            }
+                            positive("radius", *radius)?;
+                            positive("engage_range", *engage_range)?;
+                            positive("max_speed", *max_speed)?;
+                        }
+                    }
+                }
+            }
+            EventActionAsset::SpawnAsteroidField(config) => {
+                not_negative("extent", config.extent.min_element())?;
+                positive("min_radius", config.min_radius)?;
+                positive("max_radius", config.max_radius)?;
+                positive("health", config.health)?;
+                if config.min_radius > config.max_radius {
+                    return Err(ScenarioValidationError::InvalidRange {
+                        scenario: scenario.clone(),
+                        field: "radius",
+                        min: config.min_radius,
+                        max: config.max_radius,
+                    });
+                }
+            }
+            EventActionAsset::Delay(config) => {
+                positive("seconds", config.seconds)?;
+                validate_actions(scenario, &config.actions)?;
+            }
+            EventActionAsset::Timer(config) => {
+                positive("interval", config.interval)?;
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+impl ScenarioAsset {
+    /// All the image paths that are referenced by the scenario.
+    fn image_paths(&self) -> Vec<String> {
+        let mut paths = vec![self.cubemap.clone()];
+        for event in self.events.iter() {
+            collect_image_paths(&event.actions, &mut paths);
+        }
+
+        paths
+    }
+
+    /// Build the scenario configuration, resolving the asset paths to handles and the section ids
+    /// to their configuration from `GameSections`.
+    pub fn to_config(
+        &self,
+        asset_server: &AssetServer,
+        sections: &GameSections,
+    ) -> Result<ScenarioConfig, ScenarioAssetError> {
+        let mut events = Vec::with_capacity(self.events.len());
+        for event in self.events.iter() {
+            let actions = self.action_configs(&event.actions, asset_server, sections)?;
+
+            events.push(ScenarioEventConfig {
+                name: event.name,
+                filters: event.filters.clone(),
+                actions,
+            });
+        }
+
+        Ok(ScenarioConfig {
+            id: self.id.clone(),
+            name: self.name.clone(),
+            description: self.description.clone(),
+            cubemap: asset_server.load(self.cubemap.clone()),
+            events,
+        })
+    }
+
+    /// Build the configuration of the actions. An asteroid field is turned into one spawn action
+    /// per asteroid.
+    fn action_configs(
+        &self,
+        actions: &[EventActionAsset],
+        asset_server: &AssetServer,
+        sections: &GameSections,
+    ) -> Result<Vec<EventActionConfig>, ScenarioAssetError> {
+        let mut configs = Vec::with_capacity(actions.len());
+        for action in actions.iter() {
+            let action = match action {
+                EventActionAsset::DebugMessage(config) => {
+                    EventActionConfig::DebugMessage(config.clone())
+                }
+                EventActionAsset::VariableSet(config) => {
+                    EventActionConfig::VariableSet(config.clone())
+                }
+                EventActionAsset::Objective(config) => EventActionConfig::Objective(config.clone()),
+                EventActionAsset::ObjectiveComplete(config) => {
+                    EventActionConfig::ObjectiveComplete(config.clone())
+                }
+                EventActionAsset::SpawnScenarioObject(config) => {
+                    EventActionConfig::SpawnScenarioObject(self.object_config(
+                        config,
+                        asset_server,
+                        sections,
+                    )?)
+                }
+                EventActionAsset::SpawnAsteroidField(config) => {
+                    for asteroid in config.asteroids(&mut config.rng(&self.id)) {
+                        configs.push(EventActionConfig::SpawnScenarioObject(self.object_config(
+                            &asteroid,
+                            asset_server,
+                            sections,
+                        )?));
+                    }
+                    continue;
+                }
+                EventActionAsset::NextScenario(config) => {
+                    EventActionConfig::NextScenario(config.clone())
+                }
+                EventActionAsset::Delay(config) => EventActionConfig::Delay(DelayActionConfig {
+                    seconds: config.seconds,
+                    actions: self.action_configs(&config.actions, asset_server, sections)?,
+                    path: Vec::new(),
+                }),
+                EventActionAsset::Timer(config) => EventActionConfig::Timer(config.clone()),
+                EventActionAsset::CancelTimer(config) => {
+                    EventActionConfig::CancelTimer(config.clone())
+                }
+            };
+
+            configs.push(action);
+        }
+
+        Ok(configs)
+    }
+
+    fn object_config(
+        &self,
+        config: &ScenarioObjectAsset,
+        asset_server: &AssetServer,
+        sections: &GameSections,
+    ) -> Result<ScenarioObjectConfig, ScenarioAssetError> {
+        Ok(ScenarioObjectConfig {
+            base: config.base.clone(),
+            kind: self.object_kind(&config.base.id, &config.kind, asset_server, sections)?,
+        })
+    }
+
+    fn object_kind(
+        &self,
+        id: &str,
+        kind: &ScenarioObjectKindAsset,
+        asset_server: &AssetServer,
+        sections: &GameSections,
+    ) -> Result<ScenarioObjectKind, ScenarioAssetError> {
+        let kind = match kind {
+            ScenarioObjectKindAsset::Asteroid(config) => {
+                ScenarioObjectKind::Asteroid(AsteroidConfig {
+                    radius: config.radius,
+                    texture: asset_server.load(config.texture.clone()),
+                })
+            }
+            ScenarioObjectKindAsset::Spaceship(config) => {
+                ScenarioObjectKind::Spaceship(self.spaceship_config(id, config, sections)?)
+            }
+            ScenarioObjectKindAsset::TriggerZone(config) => {
+                ScenarioObjectKind::TriggerZone(config.clone())
+            }
+        };
+
+        Ok(kind)
+    }
+
+    fn spaceship_config(
+        &self,
+        id: &str,
+        config: &SpaceshipAsset,
+        sections: &GameSections,
+    ) -> Result<SpaceshipConfig, ScenarioAssetError> {
+        let controller = match &config.controller {
+            SpaceshipControllerAsset::None => SpaceshipController::None,
+            SpaceshipControllerAsset::Player(config) => {
+                let mut input_mapping = HashMap::default();
+                for (id, bindings) in config.input_mapping.iter() {
+                    let mut section_bindings = Vec::with_capacity(bindings.len());
+                    for binding in bindings.iter() {
+                        section_bindings.push(self.binding(binding)?);
+                    }
+                    input_mapping.insert(id.clone(), section_bindings);
+                }
+
+                SpaceshipController::Player(PlayerControllerConfig { input_mapping })
+            }
+            SpaceshipControllerAsset::AI(config) => SpaceshipController::AI(config.clone()),
+        };
+
+        let mut spaceship_sections = Vec::with_capacity(config.sections.len());
+        for section in config.sections.iter() {
+            let Some(section_config) = sections.get_section(&section.section) else {
+                return Err(ScenarioAssetError::UnknownSection {
+                    scenario: self.id.clone(),
+                    section: section.section.clone(),
+                });
+            };
+
+            spaceship_sections.push(SpaceshipSectionConfig {
+                id: section.id.clone(),
+                position: section.position,
+                rotation: section.rotation,
+                config: section_config.clone(),
+            });
+        }
+
+        // The same checks as in the editor, a spaceship that could not be built there is rejected
+        validate_spaceship_sections(&spaceship_sections).map_err(|error| {
+            ScenarioAssetError::InvalidSpaceship {
+                scenario: self.id.clone(),
+                object: id.to_string(),
+                error,
+            }
+        })?;
+
+        Ok(SpaceshipConfig {
+            controller,
+            sections: spaceship_sections,
+        })
+    }
+
+    fn binding(&self, binding: &InputBindingAsset) -> Result<Binding, ScenarioAssetError> {
+        let result = match binding {
+            InputBindingAsset::Keyboard(name) => {
+                from_variant_name::<KeyCode>(name).map(Binding::from)
+            }
+            InputBindingAsset::Mouse(name) => {
+                from_variant_name::<MouseButton>(name).map(Binding::from)
+            }
+            InputBindingAsset::Gamepad(name) => {
+                from_variant_name::<GamepadButton>(name).map(Binding::from)
+            }
+        };
+
+        result.ok_or_else(|| ScenarioAssetError::UnknownBinding {
+            scenario: self.id.clone(),
+            binding: binding.clone(),
+        })
+    }
+}
+
+/// Collect the image paths referenced by the actions, including the delayed ones.
+fn collect_image_paths(actions: &[EventActionAsset], paths: &mut Vec<String>) {
+    for action in actions.iter() {
+        match action {
+            EventActionAsset::SpawnScenarioObject(ScenarioObjectAsset {
+                kind: ScenarioObjectKindAsset::Asteroid(asteroid),
+                ..
+            }) => {
+                paths.push(asteroid.texture.clone());
+            }
+            EventActionAsset::SpawnAsteroidField(field) => {
+                paths.push(field.texture.clone());
+            }
+            EventActionAsset::Delay(config) => {
+                collect_image_paths(&config.actions, paths);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Build a unit variant of a reflected enum from the name of the variant.
+fn from_variant_name<T: FromReflect>(name: &str) -> Option<T> {
+    T::from_reflect(&DynamicEnum::new(name.to_string(), DynamicVariant::Unit))
+}
 
 pub(crate) fn register_scenario(
     mut commands: Commands,
     game_assets: Res<super::GameAssets>,
+    manifests: Res<Assets<ScenarioManifestAsset>>,
+    scenarios: Res<Assets<ScenarioAsset>>,
+    asset_server: Res<AssetServer>,
     sections: Res<GameSections>,
 ) {
-    commands.insert_resource(GameScenarios(HashMap::from([
-        (
-            "asteroid_field".to_string(),
-            asteroid_field(&game_assets, &sections),
-        ),
-        (
-            "asteroid_next".to_string(),
-            asteroid_next(&game_assets, &sections),
-        ),
-    ])));
-}
+    let mut game_scenarios = GameScenarios::default();
 
-pub fn asteroid_field(game_assets: &super::GameAssets, sections: &GameSections) -> ScenarioConfig {
-    let mut rng = rand::rng();
-
-    let mut objects = Vec::new();
-    for i in 0..20 {
-        let pos = Vec3::new(
-            rng.random_range(-100.0..100.0),
-            rng.random_range(-20.0..20.0),
-            rng.random_range(-100.0..100.0),
+    let Some(manifest) = manifests.get(&game_assets.scenarios) else {
+        error!(
+            "register_scenario: manifest {:?} not loaded",
+            game_assets.scenarios
         );
-        let radius = rng.random_range(1.0..3.0);
-        let texture = game_assets.asteroid_texture.clone();
+        commands.insert_resource(game_scenarios);
+        return;
+    };
 
-        objects.push(ScenarioObjectConfig {
-            base: BaseScenarioObjectConfig {
-                id: format!("asteroid_{}", i),
-                name: format!("Asteroid {}", i),
-                position: pos,
-                rotation: Quat::IDENTITY,
-                health: 100.0,
-            },
-            kind: ScenarioObjectKind::Asteroid(AsteroidConfig { radius, texture }),
-        });
+    for handle in manifest.scenarios.iter() {
+        let Some(scenario) = scenarios.get(handle) else {
+            error!("register_scenario: scenario {:?} not loaded", handle);
+            continue;
+        };
+
+        match scenario.to_config(&asset_server, &sections) {
+            Ok(config) => {
+                debug!("register_scenario: registered scenario {:?}", config.id);
+                game_scenarios.insert(config.id.clone(), config);
+            }
+            Err(e) => {
+                error!("register_scenario: {}", e);
+            }
+        }
     }
 
-    let spaceship = SpaceshipConfig {
-        controller: SpaceshipController::Player(PlayerControllerConfig {
-            input_mapping: HashMap::from([
-                (
-                    "thruster".to_string(),
-                    vec![KeyCode::Space.into(), GamepadButton::RightTrigger.into()],
-                ),
+    commands.insert_resource(game_scenarios);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn asteroid_field() -> AsteroidFieldAsset {
+        AsteroidFieldAsset {
+            id: "asteroid".to_string(),
+            name: "Asteroid".to_string(),
+            count: 10,
+            center: Vec3::new(0.0, 50.0, 0.0),
+            extent: Vec3::new(100.0, 20.0, 100.0),
+            min_radius: 1.0,
+            max_radius: 3.0,
+            health: 100.0,
+            texture: "textures/asteroid.png".to_string(),
+        }
+    }
+
+    #[test]
+    fn parses_scenario_from_ron() {
+        // Arrange
+        let source = r#"(
+            id: "test",
+            name: "Test",
+            description: "A test scenario.",
+            cubemap: "textures/cubemap.png",
+            events: [
                 (
-                    "turret".to_string(),
-                    vec![
-                        MouseButton::Left.into(),
-                        GamepadButton::RightTrigger2.into(),
+                    name: OnStart,
+                    actions: [
+                        SpawnAsteroidField((
+                            id: "asteroid",
+                            name: "Asteroid",
+                            count: 5,
+                            extent: (10.0, 10.0, 10.0),
+                            min_radius: 1.0,
+                            max_radius: 2.0,
+                            health: 100.0,
+                            texture: "textures/field.png",
+                        )),
+                        Delay((
+                            seconds: 1.0,
+                            actions: [
+                                SpawnScenarioObject((
+                                    base: (
+                                        id: "rock",
+                                        name: "Rock",
+                                        position: (0.0, 0.0, 0.0),
+                                        rotation: (0.0, 0.0, 0.0, 1.0),
+                                        health: 10.0,
+                                    ),
+                                    kind: Asteroid((radius: 1.0, texture: "textures/rock.png")),
+                                )),
+                            ],
+                        )),
                     ],
                 ),
-            ]),
-        }),
-        sections: vec![
-            SpaceshipSectionConfig {
-                id: "controller".to_string(),
-                position: Vec3::ZERO,
-                rotation: Quat::IDENTITY,
-                config: sections
-                    .get_section("basic_controller_section")
-                    .unwrap()
-                    .clone(),
-            },
-            SpaceshipSectionConfig {
-                id: "hull_front".to_string(),
-                position: Vec3::new(0.0, 0.0, 1.0),
-                rotation: Quat::IDENTITY,
-                config: sections
-                    .get_section("reinforced_hull_section")
-                    .unwrap()
-                    .clone(),
-            },
-            SpaceshipSectionConfig {
-                id: "hull_back".to_string(),
-                position: Vec3::new(0.0, 0.0, -1.0),
-                rotation: Quat::IDENTITY,
-                config: sections
-                    .get_section("reinforced_hull_section")
-                    .unwrap()
-                    .clone(),
-            },
-            SpaceshipSectionConfig {
-                id: "thruster".to_string(),
-                position: Vec3::new(0.0, 0.0, 2.0),
-                rotation: Quat::IDENTITY,
-                config: sections
-                    .get_section("basic_thruster_section")
-                    .unwrap()
-                    .clone(),
-            },
-            SpaceshipSectionConfig {
-                id: "turret".to_string(),
-                position: Vec3::new(0.0, 0.0, -2.0),
-                rotation: Quat::from_rotation_x(-std::f32::consts::FRAC_PI_2),
-                config: sections
-                    .get_section("better_turret_section")
-                    .unwrap()
-                    .clone(),
+            ],
+        )"#;
+
+        // Act
+        let scenario: ScenarioAsset = ron::de::from_str(source).unwrap();
+
+        // Assert
+        assert!(validate_scenario(&scenario).is_ok());
+        assert_eq!(
+            scenario.image_paths(),
+            vec![
+                "textures/cubemap.png".to_string(),
+                "textures/field.png".to_string(),
+                "textures/rock.png".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn scatters_asteroid_field_in_its_extent() {
+        // Arrange
+        let field = asteroid_field();
+        let mut rng = StdRng::seed_from_u64(42);
+
+        // Act
+        let asteroids = field.asteroids(&mut rng);
+
+        // Assert
+        assert_eq!(asteroids.len(), 10);
+        for (index, asteroid) in asteroids.iter().enumerate() {
+            assert_eq!(asteroid.base.id, format!("asteroid_{}", index));
+            let offset = asteroid.base.position - field.center;
+            assert!(offset.abs().cmple(field.extent).all());
+            let ScenarioObjectKindAsset::Asteroid(config) = &asteroid.kind else {
+                panic!("expected an asteroid, got {:?}", asteroid.kind);
+            };
+            assert!((1.0..=3.0).contains(&config.radius));
+        }
+    }
+
+    #[test]
+    fn scatters_asteroid_field_the_same_in_a_scenario() {
+        // Arrange
+        let field = asteroid_field();
+        let layout = |scenario: &str| {
+            field
+                .asteroids(&mut field.rng(&scenario.to_string()))
+                .into_iter()
+                .map(|asteroid| {
+                    let ScenarioObjectKindAsset::Asteroid(config) = asteroid.kind else {
+                        panic!("expected an asteroid, got {:?}", asteroid.kind);
+                    };
+                    (asteroid.base.position, config.radius)
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // Act
+        let first = layout("test");
+        let second = layout("test");
+        let other = layout("other");
+
+        // Assert
+        assert_eq!(first, second);
+        assert_ne!(first, other);
+    }
+
+    #[test]
+    fn rejects_asteroid_field_with_invalid_radius() {
+        // Arrange
+        let mut field = asteroid_field();
+        field.min_radius = 4.0;
+        let scenario = ScenarioAsset {
+            id: "test".to_string(),
+            name: "Test".to_string(),
+            description: String::new(),
+            cubemap: "textures/cubemap.png".to_string(),
+            events: vec![ScenarioEventAsset {
+                name: EventConfig::OnStart,
+                filters: Vec::new(),
+                actions: vec![EventActionAsset::SpawnAsteroidField(field)],
+            }],
+            images: Vec::new(),
+        };
+
+        // Act
+        let result = validate_scenario(&scenario);
+
+        // Assert
+        assert_eq!(
+            result,
+            Err(ScenarioValidationError::InvalidRange {
+                scenario: "test".to_string(),
+                field: "radius",
+                min: 4.0,
+                max: 3.0,
+            })
+        );
+    }
+
+    fn scenario_with(actions: Vec<EventActionAsset>) -> ScenarioAsset {
+        ScenarioAsset {
+            id: "test".to_string(),
+            name: "Test".to_string(),
+            description: String::new(),
+            cubemap: "textures/cubemap.png".to_string(),
+            events: vec![ScenarioEventAsset {
+                name: EventConfig::OnStart,
+                filters: Vec::new(),
+                actions,
+            }],
+            images: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn rejects_timer_with_zero_interval() {
+        // Arrange
+        let scenario = scenario_with(vec![EventActionAsset::Timer(TimerActionConfig {
+            id: "tick".to_string(),
+            interval: 0.0,
+            repeat: true,
+        })]);
+
+        // Act
+        let result = validate_scenario(&scenario);
+
+        // Assert
+        assert_eq!(
+            result,
+            Err(ScenarioValidationError::NotPositive {
+                scenario: "test".to_string(),
+                field: "interval",
+                value: 0.0,
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_nested_delay_with_negative_seconds() {
+        // Arrange
+        let scenario = scenario_with(vec![EventActionAsset::Delay(DelayActionAsset {
+            seconds: 1.0,
+            actions: vec![EventActionAsset::Delay(DelayActionAsset {
+                seconds: -2.0,
+                actions: Vec::new(),
+            })],
+        })]);
+
+        // Act
+        let result = validate_scenario(&scenario);
+
+        // Assert
+        assert_eq!(
+            result,
+            Err(ScenarioValidationError::NotPositive {
+                scenario: "test".to_string(),
+                field: "seconds",
+                value: -2.0,
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_explosion_without_radius() {
+        // Arrange
+        let scenario = scenario_with(vec![EventActionAsset::SpawnScenarioObject(
+            ScenarioObjectAsset {
+                base: BaseScenarioObjectConfig {
+                    id: "rock".to_string(),
+                    name: "Rock".to_string(),
+                    position: Vec3::ZERO,
+                    rotation: Quat::IDENTITY,
+                    health: 10.0,
+                    faction: None,
+                    explosion: Some(ExplosionConfig {
+                        radius: 0.0,
+                        damage: 50.0,
+                        falloff: DamageFalloff::Linear,
+                        impulse: 0.0,
+                    }),
+                },
+                kind: ScenarioObjectKindAsset::Asteroid(AsteroidAsset {
+                    radius: 1.0,
+                    texture: "textures/rock.png".to_string(),
+                }),
             },
-        ],
-    };
-    objects.push(ScenarioObjectConfig {
-        base: BaseScenarioObjectConfig {
-            id: "player_spaceship".to_string(),
-            name: "Player Spaceship".to_string(),
-            position: Vec3::ZERO,
-            rotation: Quat::IDENTITY,
-            health: 500.0,
-        },
-        kind: ScenarioObjectKind::Spaceship(spaceship),
-    });
-
-    let spaceship = SpaceshipConfig {
-        controller: SpaceshipController::None,
-        sections: vec![
-            SpaceshipSectionConfig {
-                id: "controller".to_string(),
+        )]);
+
+        // Act
+        let result = validate_scenario(&scenario);
+
+        // Assert
+        assert_eq!(
+            result,
+            Err(ScenarioValidationError::NotPositive {
+                scenario: "test".to_string(),
+                field: "explosion.radius",
+                value: 0.0,
+            })
+        );
+    }
+
+    fn ai_spaceship(behaviour: AIBehaviour) -> EventActionAsset {
+        EventActionAsset::SpawnScenarioObject(ScenarioObjectAsset {
+            base: BaseScenarioObjectConfig {
+                id: "enemy".to_string(),
+                name: "Enemy".to_string(),
                 position: Vec3::ZERO,
                 rotation: Quat::IDENTITY,
-                config: sections
-                    .get_section("basic_controller_section")
-                    .unwrap()
-                    .clone(),
-            },
-            SpaceshipSectionConfig {
-                id: "hull_front".to_string(),
-                position: Vec3::new(0.0, 0.0, 1.0),
-                rotation: Quat::IDENTITY,
-                config: sections
-                    .get_section("reinforced_hull_section")
-                    .unwrap()
-                    .clone(),
-            },
-            SpaceshipSectionConfig {
-                id: "hull_back".to_string(),
-                position: Vec3::new(0.0, 0.0, -1.0),
-                rotation: Quat::IDENTITY,
-                config: sections
-                    .get_section("reinforced_hull_section")
-                    .unwrap()
-                    .clone(),
-            },
-            SpaceshipSectionConfig {
-                id: "thruster".to_string(),
-                position: Vec3::new(0.0, 0.0, 2.0),
-                rotation: Quat::IDENTITY,
-                config: sections
-                    .get_section("basic_thruster_section")
-                    .unwrap()
-                    .clone(),
+                health: 100.0,
+                faction: None,
+                explosion: None,
             },
-            SpaceshipSectionConfig {
-                id: "turret".to_string(),
-                position: Vec3::new(0.0, 0.0, -2.0),
-                rotation: Quat::from_rotation_x(-std::f32::consts::FRAC_PI_2),
-                config: sections
-                    .get_section("better_turret_section")
-                    .unwrap()
-                    .clone(),
+            kind: ScenarioObjectKindAsset::Spaceship(SpaceshipAsset {
+                controller: SpaceshipControllerAsset::AI(AIControllerConfig { behaviour }),
+                sections: Vec::new(),
+            }),
+        })
+    }
+
+    #[test]
+    fn rejects_ai_with_negative_speed() {
+        // Arrange
+        let scenario = scenario_with(vec![ai_spaceship(AIBehaviour::Orbit {
+            radius: 50.0,
+            speed: -10.0,
+            fire_range: 100.0,
+        })]);
+
+        // Act
+        let result = validate_scenario(&scenario);
+
+        // Assert
+        assert_eq!(
+            result,
+            Err(ScenarioValidationError::NotPositive {
+                scenario: "test".to_string(),
+                field: "speed",
+                value: -10.0,
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_guard_without_target() {
+        // Arrange
+        let scenario = scenario_with(vec![ai_spaceship(AIBehaviour::Guard {
+            target_id: String::new(),
+            radius: 50.0,
+            engage_range: 100.0,
+            max_speed: 10.0,
+        })]);
+
+        // Act
+        let result = validate_scenario(&scenario);
+
+        // Assert
+        assert_eq!(
+            result,
+            Err(ScenarioValidationError::Empty {
+                scenario: "test".to_string(),
+                field: "target_id",
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_disconnected_spaceships() {
+        // Arrange
+        let section = |id: &str, kind: SectionKind| SectionConfig {
+            base: BaseSectionConfig {
+                id: id.to_string(),
+                ..default()
             },
-        ],
-    };
-    objects.push(ScenarioObjectConfig {
-        base: BaseScenarioObjectConfig {
-            id: "other_spaceship".to_string(),
-            name: "Other Spaceship".to_string(),
-            position: Vec3::new(10.0, 0.0, 0.0),
-            rotation: Quat::IDENTITY,
-            health: 100.0,
-        },
-        kind: ScenarioObjectKind::Spaceship(spaceship),
-    });
-
-    let events = vec![
-        ScenarioEventConfig {
-            name: EventConfig::OnStart,
-            filters: vec![],
-            actions: objects
-                .into_iter()
-                .map(EventActionConfig::SpawnScenarioObject)
-                .collect::<_>(),
-        },
-        ScenarioEventConfig {
-            name: EventConfig::OnStart,
-            filters: vec![],
-            actions: vec![EventActionConfig::Objective(ObjectiveActionConfig::new(
-                "destroy_asteroids",
-                "Objective: Destroy 5 asteroids!",
-            ))],
-        },
-        ScenarioEventConfig {
-            name: EventConfig::OnStart,
-            filters: vec![],
-            actions: vec![EventActionConfig::VariableSet(VariableSetActionConfig {
-                key: "asteroids_destroyed".to_string(),
-                expression: VariableExpressionNode::new_term(VariableTermNode::new_factor(
-                    VariableFactorNode::new_literal(VariableLiteral::Number(0.0)),
-                )),
-            })],
-        },
-        ScenarioEventConfig {
-            name: EventConfig::OnStart,
-            filters: vec![],
-            actions: vec![EventActionConfig::VariableSet(VariableSetActionConfig {
-                key: "objective_destroy_asteroids".to_string(),
-                expression: VariableExpressionNode::new_term(VariableTermNode::new_factor(
-                    VariableFactorNode::new_literal(VariableLiteral::Boolean(false)),
-                )),
-            })],
-        },
-        ScenarioEventConfig {
-            name: EventConfig::OnDestroyed,
-            filters: vec![EventFilterConfig::Entity(EntityFilterConfig {
-                id: Some("player_spaceship".to_string()),
-                type_name: None,
-            })],
-            actions: vec![
-                EventActionConfig::DebugMessage(DebugMessageActionConfig {
-                    message: "The player's spaceship was destroyed!".to_string(),
-                }),
-                EventActionConfig::NextScenario(NextScenarioActionConfig {
-                    scenario_id: "asteroid_field".to_string(),
-                    linger: true,
-                }),
-            ],
-        },
-        ScenarioEventConfig {
-            name: EventConfig::OnDestroyed,
-            filters: vec![EventFilterConfig::Entity(EntityFilterConfig {
-                id: None,
-                type_name: Some("asteroid".to_string()),
-            })],
-            actions: vec![EventActionConfig::VariableSet(VariableSetActionConfig {
-                key: "asteroids_destroyed".to_string(),
-                expression: VariableExpressionNode::new_add(
-                    VariableTermNode::new_factor(VariableFactorNode::new_name(
-                        "asteroids_destroyed".to_string(),
-                    )),
-                    VariableExpressionNode::new_term(VariableTermNode::new_factor(
-                        VariableFactorNode::new_literal(VariableLiteral::Number(1.0)),
-                    )),
-                ),
-            })],
-        },
-        ScenarioEventConfig {
-            name: EventConfig::OnDestroyed,
-            filters: vec![
-                EventFilterConfig::Entity(EntityFilterConfig {
-                    id: None,
-                    type_name: Some("asteroid".to_string()),
-                }),
-                EventFilterConfig::Expression(ExpressionFilterConfig(
-                    VariableConditionNode::new_greater_than(
-                        VariableExpressionNode::new_term(VariableTermNode::new_factor(
-                            VariableFactorNode::new_name("asteroids_destroyed".to_string()),
-                        )),
-                        VariableExpressionNode::new_term(VariableTermNode::new_factor(
-                            VariableFactorNode::new_literal(VariableLiteral::Number(4.0)),
-                        )),
-                    ),
-                )),
-                EventFilterConfig::Expression(ExpressionFilterConfig(
-                    VariableConditionNode::new_equals(
-                        VariableExpressionNode::new_term(VariableTermNode::new_factor(
-                            VariableFactorNode::new_name("objective_destroy_asteroids".to_string()),
-                        )),
-                        VariableExpressionNode::new_term(VariableTermNode::new_factor(
-                            VariableFactorNode::new_literal(VariableLiteral::Boolean(false)),
-                        )),
-                    ),
-                )),
-            ],
-            actions: vec![
-                EventActionConfig::DebugMessage(DebugMessageActionConfig {
-                    message: "Objective Complete: Destroyed 5 asteroids!".to_string(),
-                }),
-                EventActionConfig::VariableSet(VariableSetActionConfig {
-                    key: "objective_destroy_asteroids".to_string(),
-                    expression: VariableExpressionNode::new_term(VariableTermNode::new_factor(
-                        VariableFactorNode::new_literal(VariableLiteral::Boolean(true)),
-                    )),
-                }),
-                EventActionConfig::ObjectiveComplete(ObjectiveCompleteActionConfig {
-                    id: "destroy_asteroids".to_string(),
-                }),
-                EventActionConfig::NextScenario(NextScenarioActionConfig {
-                    scenario_id: "asteroid_next".to_string(),
-                    linger: true,
-                }),
+            kind,
+        };
+        let sections = GameSections(vec![
+            section(
+                "controller",
+                SectionKind::Controller(ControllerSectionConfig::default()),
+            ),
+            section(
+                "hull",
+                SectionKind::Hull(HullSectionConfig { render_mesh: None }),
+            ),
+        ]);
+        let spaceship = |hull: Vec3| SpaceshipAsset {
+            controller: SpaceshipControllerAsset::None,
+            sections: vec![
+                SpaceshipSectionAsset {
+                    id: "controller".to_string(),
+                    position: Vec3::ZERO,
+                    rotation: Quat::IDENTITY,
+                    section: "controller".to_string(),
+                },
+                SpaceshipSectionAsset {
+                    id: "hull".to_string(),
+                    position: hull,
+                    rotation: Quat::IDENTITY,
+                    section: "hull".to_string(),
+                },
             ],
-        },
-        ScenarioEventConfig {
-            name: EventConfig::OnDestroyed,
-            filters: vec![EventFilterConfig::Entity(EntityFilterConfig {
-                id: None,
-                type_name: Some("asteroid".to_string()),
-            })],
-            actions: vec![EventActionConfig::DebugMessage(DebugMessageActionConfig {
-                message: "An asteroid was destroyed!".to_string(),
-            })],
-        },
-    ];
-
-    ScenarioConfig {
-        id: "asteroid_field".to_string(),
-        name: "Asteroid Field".to_string(),
-        description: "A dense asteroid field.".to_string(),
-        cubemap: game_assets.cubemap.clone(),
-        events,
+        };
+        let scenario = scenario_with(Vec::new());
+
+        // Act
+        let connected = scenario.spaceship_config("ship", &spaceship(Vec3::Z), &sections);
+        let disconnected =
+            scenario.spaceship_config("ship", &spaceship(Vec3::new(0.0, 0.0, 3.0)), &sections);
+
+        // Assert
+        assert!(connected.is_ok());
+        assert!(matches!(
+            disconnected,
+            Err(ScenarioAssetError::InvalidSpaceship {
+                error: SpaceshipDesignError::Disconnected { .. },
+                ..
+            })
+        ));
     }
-}
 
-pub fn asteroid_next(game_assets: &super::GameAssets, _sections: &GameSections) -> ScenarioConfig {
-    let events = vec![ScenarioEventConfig {
-        name: EventConfig::OnStart,
-        filters: vec![],
-        actions: vec![EventActionConfig::NextScenario(NextScenarioActionConfig {
-            scenario_id: "asteroid_field".to_string(),
-            linger: true,
-        })],
-    }];
-
-    ScenarioConfig {
-        id: "asteroid_next".to_string(),
-        name: "Asteroid Field - Next".to_string(),
-        description: "The next scenario after the asteroid field.".to_string(),
-        cubemap: game_assets.cubemap.clone(),
-        events,
+    #[test]
+    fn manifest_lists_valid_scenario_files() {
+        // Arrange
+        let assets = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../../assets");
+        let manifest =
+            std::fs::read_to_string(assets.join("scenarios/index.scenarios.ron")).unwrap();
+
+        // Act
+        let paths: Vec<String> = ron::de::from_str(&manifest).unwrap();
+
+        // Assert
+        assert!(!paths.is_empty());
+        for path in paths {
+            let source = std::fs::read_to_string(assets.join(&path)).unwrap();
+            let scenario: ScenarioAsset = ron::de::from_str(&source)
+                .unwrap_or_else(|e| panic!("could not parse {}: {}", path, e));
+            assert_eq!(validate_scenario(&scenario), Ok(()), "invalid {}", path);
+        }
     }
 }
@@ -1,113 +1,720 @@
-use bevy::prelude::*;
+//! Section files and the asset loader that reads them.
+//!
+//! The sections that can be used to build spaceships are described in `*.sections.ron` or
+//! `*.sections.json` files, as a list of sections. The files mirror the `SectionConfig` types from
+//! `nova_gameplay`, but they reference the glTF scenes by path. The loader validates the sections
+//! and loads the scenes as dependencies of the asset.
+
+use bevy::{
+    asset::{io::Reader, AssetLoader, LoadContext},
+    platform::collections::HashSet,
+    prelude::*,
+};
 use nova_gameplay::prelude::*;
 
-pub(crate) fn register_sections(mut commands: Commands, game_assets: Res<super::GameAssets>) {
-    // This should be loaded from a JSON file, but for now it is fine.
+pub mod prelude {
+    pub use super::{
+        BatterySectionAsset, BeamSectionAsset, ControllerSectionAsset, GameSectionsAsset,
+        GameSectionsAssetLoader, GameSectionsAssetLoaderError, HullSectionAsset,
+        MissileLauncherSectionAsset, ReactorSectionAsset, SectionAsset, SectionKindAsset,
+        SectionValidationError, ShieldSectionAsset, ThrusterSectionAsset, TurretSectionAsset,
+    };
+}
 
-    commands.insert_resource(GameSections(vec![
-        SectionConfig {
-            base: BaseSectionConfig {
-                id: "basic_hull_section".to_string(),
-                name: "Basic Hull Section".to_string(),
-                description: "A basic hull section for spaceships.".to_string(),
-                mass: 1.0,
-            },
-            kind: SectionKind::Hull(HullSectionConfig { render_mesh: None }),
-        },
-        SectionConfig {
-            base: BaseSectionConfig {
-                id: "reinforced_hull_section".to_string(),
-                name: "Reinforced Hull Section".to_string(),
-                description: "A reinforced hull section for spaceships.".to_string(),
-                mass: 1.0,
-            },
-            kind: SectionKind::Hull(HullSectionConfig {
-                render_mesh: Some(game_assets.hull_01.clone()),
-            }),
-        },
-        SectionConfig {
-            base: BaseSectionConfig {
-                id: "basic_thruster_section".to_string(),
-                name: "Basic Thruster Section".to_string(),
-                description: "A basic thruster section for spaceships.".to_string(),
-                mass: 1.0,
-            },
-            kind: SectionKind::Thruster(ThrusterSectionConfig {
-                magnitude: 1.0,
-                render_mesh: None,
-            }),
-        },
-        SectionConfig {
-            base: BaseSectionConfig {
-                id: "basic_controller_section".to_string(),
-                name: "Basic Controller Section".to_string(),
-                description: "A basic controller section for spaceships.".to_string(),
-                mass: 1.0,
-            },
-            kind: SectionKind::Controller(ControllerSectionConfig {
-                frequency: 4.0,
-                damping_ratio: 4.0,
-                max_torque: 100.0,
-                render_mesh: None,
-            }),
-        },
-        SectionConfig {
-            base: BaseSectionConfig {
-                id: "basic_turret_section".to_string(),
-                name: "Basic Turret Section".to_string(),
-                description: "A basic turret section for spaceships.".to_string(),
-                mass: 1.0,
-            },
-            kind: SectionKind::Turret(TurretSectionConfig {
-                yaw_speed: std::f32::consts::PI,   // 180 degrees per second
-                pitch_speed: std::f32::consts::PI, // 180 degrees per second
-                min_pitch: Some(-std::f32::consts::FRAC_PI_6),
-                max_pitch: Some(std::f32::consts::FRAC_PI_2),
-                render_mesh_base: None,
-                base_offset: Vec3::new(0.0, -0.5, 0.0),
-                render_mesh_yaw: None,
-                yaw_offset: Vec3::new(0.0, 0.1, 0.0),
-                render_mesh_pitch: None,
-                pitch_offset: Vec3::new(0.0, 0.2, 0.0),
-                render_mesh_barrel: None,
-                barrel_offset: Vec3::new(0.1, 0.2, 0.0),
-                muzzle_offset: Vec3::new(0.0, 0.0, -0.5),
-                fire_rate: 100.0,
-                muzzle_speed: 100.0,
-                projectile_lifetime: 5.0,
-                projectile_mass: 0.1,
-                projectile_render_mesh: None,
-                muzzle_effect: None,
-            }),
-        },
-        SectionConfig {
+/// The sections read from a sections file, ready to be used as `GameSections`.
+#[derive(Asset, TypePath, Clone, Debug)]
+pub struct GameSectionsAsset {
+    pub sections: Vec<SectionConfig>,
+    /// The scenes referenced by the sections, loaded together with them.
+    #[dependency]
+    scenes: Vec<Handle<Scene>>,
+}
+
+/// A section as it is written in a sections file.
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct SectionAsset {
+    pub base: BaseSectionConfig,
+    pub kind: SectionKindAsset,
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+pub enum SectionKindAsset {
+    Hull(HullSectionAsset),
+    Thruster(ThrusterSectionAsset),
+    Controller(ControllerSectionAsset),
+    Turret(TurretSectionAsset),
+    Reactor(ReactorSectionAsset),
+    Battery(BatterySectionAsset),
+    MissileLauncher(MissileLauncherSectionAsset),
+    Beam(BeamSectionAsset),
+    Shield(ShieldSectionAsset),
+}
+
+#[derive(Clone, Debug, Default, serde::Deserialize)]
+pub struct HullSectionAsset {
+    /// The path to the scene of the hull (e.g `gltf/hull-01.glb#Scene0`)
+    pub render_mesh: Option<String>,
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct ThrusterSectionAsset {
+    pub magnitude: f32,
+    /// The path to the scene of the thruster
+    pub render_mesh: Option<String>,
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct ControllerSectionAsset {
+    pub frequency: f32,
+    pub damping_ratio: f32,
+    pub max_torque: f32,
+    /// The path to the scene of the controller
+    pub render_mesh: Option<String>,
+}
+
+/// A turret section as it is written in a sections file. The angles are in radians, the same as
+/// in `TurretSectionConfig`. Particle effects can't be referenced from a file yet.
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct TurretSectionAsset {
+    pub yaw_speed: f32,
+    pub pitch_speed: f32,
+    pub min_pitch: Option<f32>,
+    pub max_pitch: Option<f32>,
+    pub render_mesh_base: Option<String>,
+    pub base_offset: Vec3,
+    pub render_mesh_yaw: Option<String>,
+    pub yaw_offset: Vec3,
+    pub render_mesh_pitch: Option<String>,
+    pub pitch_offset: Vec3,
+    pub render_mesh_barrel: Option<String>,
+    pub barrel_offset: Vec3,
+    pub muzzle_offset: Vec3,
+    pub fire_rate: f32,
+    /// If missing, the turret has unlimited ammunition.
+    #[serde(default)]
+    pub magazine_size: Option<u32>,
+    #[serde(default)]
+    pub reload_time: f32,
+    /// If missing or zero, the turret never overheats.
+    #[serde(default)]
+    pub heat_per_shot: f32,
+    #[serde(default)]
+    pub cooling_rate: f32,
+    pub muzzle_speed: f32,
+    pub projectile_lifetime: f32,
+    pub projectile_mass: f32,
+    pub projectile_render_mesh: Option<String>,
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct ReactorSectionAsset {
+    pub output: f32,
+    /// The path to the scene of the reactor
+    pub render_mesh: Option<String>,
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct BatterySectionAsset {
+    pub capacity: f32,
+    /// The path to the scene of the battery
+    pub render_mesh: Option<String>,
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct ShieldSectionAsset {
+    pub capacity: f32,
+    pub regen_rate: f32,
+    /// The time in seconds after the last hit before the shield regenerates
+    pub regen_delay: f32,
+    /// The path to the scene of the shield generator
+    pub render_mesh: Option<String>,
+}
+
+/// A missile launcher section as it is written in a sections file. The angles are in radians.
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct MissileLauncherSectionAsset {
+    pub lock_on_time: f32,
+    pub lock_range: f32,
+    pub lock_angle: f32,
+    pub reload_time: f32,
+    pub missile_speed: f32,
+    pub missile_turn_rate: f32,
+    pub missile_lifetime: f32,
+    pub missile_mass: f32,
+    pub proximity_radius: f32,
+    pub blast_radius: f32,
+    pub blast_damage: f32,
+    /// The path to the scene of the launcher
+    pub render_mesh: Option<String>,
+    /// The path to the scene of the missiles
+    pub missile_render_mesh: Option<String>,
+}
+
+/// A beam section as it is written in a sections file. The angles are in radians, the same as in
+/// `BeamSectionConfig`.
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct BeamSectionAsset {
+    pub yaw_speed: f32,
+    pub pitch_speed: f32,
+    pub min_pitch: Option<f32>,
+    pub max_pitch: Option<f32>,
+    pub render_mesh_base: Option<String>,
+    pub base_offset: Vec3,
+    pub render_mesh_yaw: Option<String>,
+    pub yaw_offset: Vec3,
+    pub render_mesh_pitch: Option<String>,
+    pub pitch_offset: Vec3,
+    pub render_mesh_barrel: Option<String>,
+    pub barrel_offset: Vec3,
+    pub muzzle_offset: Vec3,
+    pub damage_per_second: f32,
+    pub range: f32,
+}
+
+/// Errors found while validating the sections of a sections file.
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum SectionValidationError {
+    #[error("section at index {index} has an empty id")]
+    EmptyId { index: usize },
+    #[error("section '{id}' is defined more than once")]
+    DuplicateId { id: String },
+    #[error("section '{id}': {field} must be positive, got {value}")]
+    NotPositive {
+        id: String,
+        field: &'static str,
+        value: f32,
+    },
+    #[error("section '{id}': {field} must not be negative, got {value}")]
+    Negative {
+        id: String,
+        field: &'static str,
+        value: f32,
+    },
+    #[error("section '{id}': min_pitch {min} is greater than max_pitch {max}")]
+    InvalidPitchRange { id: String, min: f32, max: f32 },
+}
+
+/// Errors that can happen while reading a sections file.
+#[derive(Debug, thiserror::Error)]
+pub enum GameSectionsAssetLoaderError {
+    #[error("could not read sections file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("could not parse RON sections file: {0}")]
+    Ron(#[from] ron::error::SpannedError),
+    #[error("could not parse JSON sections file: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("invalid sections file: {0}")]
+    Invalid(#[from] SectionValidationError),
+}
+
+/// Asset loader for `*.sections.ron` and `*.sections.json` files.
+#[derive(Default, TypePath)]
+pub struct GameSectionsAssetLoader;
+
+impl AssetLoader for GameSectionsAssetLoader {
+    type Asset = GameSectionsAsset;
+    type Settings = ();
+    type Error = GameSectionsAssetLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &Self::Settings,
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+
+        let is_json = load_context
+            .path()
+            .extension()
+            .is_some_and(|extension| extension == "json");
+        let sections: Vec<SectionAsset> = if is_json {
+            serde_json::from_slice(&bytes)?
+        } else {
+            ron::de::from_bytes(&bytes)?
+        };
+
+        validate_sections(&sections)?;
+
+        let mut scenes = Vec::new();
+        let mut load_scene = |path: &Option<String>| {
+            path.as_ref().map(|path| {
+                let handle = load_context.load::<Scene>(path.clone());
+                scenes.push(handle.clone());
+                handle
+            })
+        };
+
+        let sections = sections
+            .iter()
+            .map(|section| SectionConfig {
+                base: section.base.clone(),
+                kind: match &section.kind {
+                    SectionKindAsset::Hull(config) => SectionKind::Hull(HullSectionConfig {
+                        render_mesh: load_scene(&config.render_mesh),
+                    }),
+                    SectionKindAsset::Thruster(config) => {
+                        SectionKind::Thruster(ThrusterSectionConfig {
+                            magnitude: config.magnitude,
+                            render_mesh: load_scene(&config.render_mesh),
+                        })
+                    }
+                    SectionKindAsset::Controller(config) => {
+                        SectionKind::Controller(ControllerSectionConfig {
+                            frequency: config.frequency,
+                            damping_ratio: config.damping_ratio,
+                            max_torque: config.max_torque,
+                            render_mesh: load_scene(&config.render_mesh),
+                        })
+                    }
+                    SectionKindAsset::Turret(config) => SectionKind::Turret(TurretSectionConfig {
+                        yaw_speed: config.yaw_speed,
+                        pitch_speed: config.pitch_speed,
+                        min_pitch: config.min_pitch,
+                        max_pitch: config.max_pitch,
+                        render_mesh_base: load_scene(&config.render_mesh_base),
+                        base_offset: config.base_offset,
+                        render_mesh_yaw: load_scene(&config.render_mesh_yaw),
+                        yaw_offset: config.yaw_offset,
+                        render_mesh_pitch: load_scene(&config.render_mesh_pitch),
+                        pitch_offset: config.pitch_offset,
+                        render_mesh_barrel: load_scene(&config.render_mesh_barrel),
+                        barrel_offset: config.barrel_offset,
+                        muzzle_offset: config.muzzle_offset,
+                        fire_rate: config.fire_rate,
+                        magazine_size: config.magazine_size,
+                        reload_time: config.reload_time,
+                        heat_per_shot: config.heat_per_shot,
+                        cooling_rate: config.cooling_rate,
+                        muzzle_speed: config.muzzle_speed,
+                        projectile_lifetime: config.projectile_lifetime,
+                        projectile_mass: config.projectile_mass,
+                        projectile_render_mesh: load_scene(&config.projectile_render_mesh),
+                        muzzle_effect: None,
+                    }),
+                    SectionKindAsset::Reactor(config) => {
+                        SectionKind::Reactor(ReactorSectionConfig {
+                            output: config.output,
+                            render_mesh: load_scene(&config.render_mesh),
+                        })
+                    }
+                    SectionKindAsset::Battery(config) => {
+                        SectionKind::Battery(BatterySectionConfig {
+                            capacity: config.capacity,
+                            render_mesh: load_scene(&config.render_mesh),
+                        })
+                    }
+                    SectionKindAsset::Shield(config) => SectionKind::Shield(ShieldSectionConfig {
+                        capacity: config.capacity,
+                        regen_rate: config.regen_rate,
+                        regen_delay: config.regen_delay,
+                        render_mesh: load_scene(&config.render_mesh),
+                    }),
+                    SectionKindAsset::Beam(config) => SectionKind::Beam(BeamSectionConfig {
+                        yaw_speed: config.yaw_speed,
+                        pitch_speed: config.pitch_speed,
+                        min_pitch: config.min_pitch,
+                        max_pitch: config.max_pitch,
+                        render_mesh_base: load_scene(&config.render_mesh_base),
+                        base_offset: config.base_offset,
+                        render_mesh_yaw: load_scene(&config.render_mesh_yaw),
+                        yaw_offset: config.yaw_offset,
+                        render_mesh_pitch: load_scene(&config.render_mesh_pitch),
+                        pitch_offset: config.pitch_offset,
+                        render_mesh_barrel: load_scene(&config.render_mesh_barrel),
+                        barrel_offset: config.barrel_offset,
+                        muzzle_offset: config.muzzle_offset,
+                        damage_per_second: config.damage_per_second,
+                        range: config.range,
+                    }),
+                    SectionKindAsset::MissileLauncher(config) => {
+                        SectionKind::MissileLauncher(MissileLauncherSectionConfig {
+                            lock_on_time: config.lock_on_time,
+                            lock_range: config.lock_range,
+                            lock_angle: config.lock_angle,
+                            reload_time: config.reload_time,
+                            missile_speed: config.missile_speed,
+                            missile_turn_rate: config.missile_turn_rate,
+                            missile_lifetime: config.missile_lifetime,
+                            missile_mass: config.missile_mass,
+                            proximity_radius: config.proximity_radius,
+                            blast_radius: config.blast_radius,
+                            blast_damage: config.blast_damage,
+                            render_mesh: load_scene(&config.render_mesh),
+                            missile_render_mesh: load_scene(&config.missile_render_mesh),
+                        })
+                    }
+                },
+            })
+            .collect();
+
+        Ok(GameSectionsAsset { sections, scenes })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["sections.ron", "sections.json"]
+    }
+}
+
+/// Check that the section ids are unique and that the values of each section make sense.
+fn validate_sections(sections: &[SectionAsset]) -> Result<(), SectionValidationError> {
+    let mut ids = HashSet::new();
+
+    for (index, section) in sections.iter().enumerate() {
+        let id = &section.base.id;
+        if id.is_empty() {
+            return Err(SectionValidationError::EmptyId { index });
+        }
+        if !ids.insert(id.clone()) {
+            return Err(SectionValidationError::DuplicateId { id: id.clone() });
+        }
+
+        let positive = |field: &'static str, value: f32| {
+            if value > 0.0 && value.is_finite() {
+                Ok(())
+            } else {
+                Err(SectionValidationError::NotPositive {
+                    id: id.clone(),
+                    field,
+                    value,
+                })
+            }
+        };
+        let not_negative = |field: &'static str, value: f32| {
+            if value >= 0.0 && value.is_finite() {
+                Ok(())
+            } else {
+                Err(SectionValidationError::Negative {
+                    id: id.clone(),
+                    field,
+                    value,
+                })
+            }
+        };
+
+        positive("mass", section.base.mass)?;
+        // A section without health could never be destroyed
+        positive("health", section.base.health)?;
+
+        match &section.kind {
+            SectionKindAsset::Hull(_) => {}
+            SectionKindAsset::Thruster(config) => {
+                positive("magnitude", config.magnitude)?;
+            }
+            SectionKindAsset::Controller(config) => {
+                positive("frequency", config.frequency)?;
+                positive("damping_ratio", config.damping_ratio)?;
+                positive("max_torque", config.max_torque)?;
+            }
+            SectionKindAsset::Turret(config) => {
+                positive("yaw_speed", config.yaw_speed)?;
+                positive("pitch_speed", config.pitch_speed)?;
+                positive("fire_rate", config.fire_rate)?;
+                positive("muzzle_speed", config.muzzle_speed)?;
+                positive("projectile_lifetime", config.projectile_lifetime)?;
+                positive("projectile_mass", config.projectile_mass)?;
+
+                if let Some(magazine_size) = config.magazine_size {
+                    positive("magazine_size", magazine_size as f32)?;
+                    positive("reload_time", config.reload_time)?;
+                }
+                // A turret that heats up without cooling down would be locked out forever
+                if config.heat_per_shot != 0.0 {
+                    positive("heat_per_shot", config.heat_per_shot)?;
+                    positive("cooling_rate", config.cooling_rate)?;
+                }
+
+                if let (Some(min), Some(max)) = (config.min_pitch, config.max_pitch) {
+                    if min > max {
+                        return Err(SectionValidationError::InvalidPitchRange {
+                            id: id.clone(),
+                            min,
+                            max,
+                        });
+                    }
+                }
+            }
+            SectionKindAsset::Reactor(config) => {
+                positive("output", config.output)?;
+            }
+            SectionKindAsset::Battery(config) => {
+                positive("capacity", config.capacity)?;
+            }
+            SectionKindAsset::Shield(config) => {
+                positive("capacity", config.capacity)?;
+                positive("regen_rate", config.regen_rate)?;
+                not_negative("regen_delay", config.regen_delay)?;
+            }
+            SectionKindAsset::Beam(config) => {
+                positive("yaw_speed", config.yaw_speed)?;
+                positive("pitch_speed", config.pitch_speed)?;
+                positive("damage_per_second", config.damage_per_second)?;
+                positive("range", config.range)?;
+
+                if let (Some(min), Some(max)) = (config.min_pitch, config.max_pitch) {
+                    if min > max {
+                        return Err(SectionValidationError::InvalidPitchRange {
+                            id: id.clone(),
+                            min,
+                            max,
+                        });
+                    }
+                }
+            }
+            SectionKindAsset::MissileLauncher(config) => {
+                not_negative("lock_on_time", config.lock_on_time)?;
+                positive("lock_range", config.lock_range)?;
+                positive("lock_angle", config.lock_angle)?;
+                positive("reload_time", config.reload_time)?;
+                positive("missile_speed", config.missile_speed)?;
+                positive("missile_turn_rate", config.missile_turn_rate)?;
+                positive("missile_lifetime", config.missile_lifetime)?;
+                positive("missile_mass", config.missile_mass)?;
+                positive("proximity_radius", config.proximity_radius)?;
+                positive("blast_radius", config.blast_radius)?;
+                positive("blast_damage", config.blast_damage)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+pub(crate) fn register_sections(
+    mut commands: Commands,
+    game_assets: Res<super::GameAssets>,
+    sections: Res<Assets<GameSectionsAsset>>,
+) {
+    let Some(asset) = sections.get(&game_assets.sections) else {
+        error!(
+            "register_sections: sections {:?} not loaded",
+            game_assets.sections
+        );
+        commands.insert_resource(GameSections::default());
+        return;
+    };
+
+    debug!(
+        "register_sections: registered {} sections",
+        asset.sections.len()
+    );
+    commands.insert_resource(GameSections(asset.sections.clone()));
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn hull(id: &str) -> SectionAsset {
+        SectionAsset {
             base: BaseSectionConfig {
-                id: "better_turret_section".to_string(),
-                name: "Better Turret Section".to_string(),
-                description: "A better turret section for spaceships.".to_string(),
+                id: id.to_string(),
+                name: id.to_string(),
+                description: String::new(),
                 mass: 1.0,
+                health: 100.0,
+                resistances: DamageResistances::default(),
             },
-            kind: SectionKind::Turret(TurretSectionConfig {
-                yaw_speed: std::f32::consts::PI,   // 180 degrees per second
-                pitch_speed: std::f32::consts::PI, // 180 degrees per second
-                min_pitch: Some(-std::f32::consts::FRAC_PI_6),
-                max_pitch: Some(std::f32::consts::FRAC_PI_2),
-                render_mesh_base: None,
-                base_offset: Vec3::new(0.0, -0.5, 0.0),
-                render_mesh_yaw: Some(game_assets.turret_yaw_01.clone()),
-                yaw_offset: Vec3::new(0.0, 0.1, 0.0),
-                render_mesh_pitch: Some(game_assets.turret_pitch_01.clone()),
-                pitch_offset: Vec3::new(0.0, 0.332706, 0.303954),
-                render_mesh_barrel: Some(game_assets.turret_barrel_01.clone()),
-                barrel_offset: Vec3::new(0.0, 0.128437, -0.110729),
-                muzzle_offset: Vec3::new(0.0, 0.0, -1.2),
-                fire_rate: 100.0,
+            kind: SectionKindAsset::Hull(HullSectionAsset::default()),
+        }
+    }
+
+    #[test]
+    fn parses_sections_from_ron() {
+        // Arrange
+        let source = r#"[
+            (
+                base: (id: "hull", name: "Hull", description: "A hull.", mass: 1.0),
+                kind: Hull((render_mesh: Some("gltf/hull-01.glb#Scene0"))),
+            ),
+            (
+                base: (id: "thruster", name: "Thruster", description: "A thruster.", mass: 1.0),
+                kind: Thruster((magnitude: 1.0)),
+            ),
+        ]"#;
+
+        // Act
+        let sections: Vec<SectionAsset> = ron::de::from_str(source).unwrap();
+
+        // Assert
+        assert_eq!(sections.len(), 2);
+        assert!(validate_sections(&sections).is_ok());
+    }
+
+    #[test]
+    fn rejects_duplicate_ids() {
+        // Arrange
+        let sections = vec![hull("hull"), hull("hull")];
+
+        // Act
+        let result = validate_sections(&sections);
+
+        // Assert
+        assert_eq!(
+            result,
+            Err(SectionValidationError::DuplicateId {
+                id: "hull".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_values_naming_the_section() {
+        // Arrange
+        let mut section = hull("heavy_hull");
+        section.base.mass = 0.0;
+
+        // Act
+        let result = validate_sections(&[section]);
+
+        // Assert
+        let error = result.unwrap_err();
+        assert!(error.to_string().contains("heavy_hull"));
+        assert!(error.to_string().contains("mass"));
+    }
+
+    #[test]
+    fn rejects_sections_without_health() {
+        // Arrange
+        let mut section = hull("hull");
+        section.base.health = 0.0;
+
+        // Act
+        let result = validate_sections(&[section]);
+
+        // Assert
+        assert_eq!(
+            result,
+            Err(SectionValidationError::NotPositive {
+                id: "hull".to_string(),
+                field: "health",
+                value: 0.0,
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_turrets_that_never_cool_down() {
+        // Arrange
+        let source = r#"(
+            base: (id: "hot_turret", name: "Hot Turret", description: "A turret.", mass: 1.0),
+            kind: Turret((
+                yaw_speed: 1.0,
+                pitch_speed: 1.0,
+                base_offset: (0.0, 0.0, 0.0),
+                yaw_offset: (0.0, 0.0, 0.0),
+                pitch_offset: (0.0, 0.0, 0.0),
+                barrel_offset: (0.0, 0.0, 0.0),
+                muzzle_offset: (0.0, 0.0, 0.0),
+                fire_rate: 10.0,
+                heat_per_shot: 0.1,
                 muzzle_speed: 100.0,
-                projectile_lifetime: 5.0,
+                projectile_lifetime: 1.0,
                 projectile_mass: 0.1,
-                projectile_render_mesh: None,
-                muzzle_effect: None,
-            }),
-        },
-    ]));
+            )),
+        )"#;
+        let section: SectionAsset = ron::de::from_str(source).unwrap();
+
+        // Act
+        let result = validate_sections(&[section]);
+
+        // Assert
+        assert_eq!(
+            result,
+            Err(SectionValidationError::NotPositive {
+                id: "hot_turret".to_string(),
+                field: "cooling_rate",
+                value: 0.0,
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_missile_launchers_with_negative_lock_on_time() {
+        // Arrange
+        let mut section = hull("launcher");
+        section.kind = SectionKindAsset::MissileLauncher(MissileLauncherSectionAsset {
+            lock_on_time: -1.0,
+            lock_range: 300.0,
+            lock_angle: 0.5,
+            reload_time: 3.0,
+            missile_speed: 60.0,
+            missile_turn_rate: 2.0,
+            missile_lifetime: 8.0,
+            missile_mass: 0.5,
+            proximity_radius: 3.0,
+            blast_radius: 8.0,
+            blast_damage: 60.0,
+            render_mesh: None,
+            missile_render_mesh: None,
+        });
+
+        // Act
+        let result = validate_sections(&[section]);
+
+        // Assert
+        assert_eq!(
+            result,
+            Err(SectionValidationError::Negative {
+                id: "launcher".to_string(),
+                field: "lock_on_time",
+                value: -1.0,
+            })
+        );
+    }
+
+    #[test]
+    fn default_sections_file_is_valid() {
+        // Arrange
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../../assets/sections/default.sections.ron");
+        let source = std::fs::read_to_string(path).unwrap();
+
+        // Act
+        let sections: Vec<SectionAsset> = ron::de::from_str(&source).unwrap();
+
+        // Assert
+        assert_eq!(validate_sections(&sections), Ok(()));
+        let has = |kind: fn(&SectionKindAsset) -> bool| {
+            sections.iter().any(|section| kind(&section.kind))
+        };
+        assert!(has(|kind| matches!(kind, SectionKindAsset::Hull(_))));
+        assert!(has(|kind| matches!(kind, SectionKindAsset::Thruster(_))));
+        assert!(has(|kind| matches!(kind, SectionKindAsset::Controller(_))));
+        assert!(has(|kind| matches!(kind, SectionKindAsset::Turret(_))));
+        assert!(has(|kind| matches!(kind, SectionKindAsset::Reactor(_))));
+        assert!(has(|kind| matches!(kind, SectionKindAsset::Battery(_))));
+        assert!(has(|kind| matches!(
+            kind,
+            SectionKindAsset::MissileLauncher(_)
+        )));
+        assert!(has(|kind| matches!(kind, SectionKindAsset::Beam(_))));
+        assert!(has(|kind| matches!(kind, SectionKindAsset::Shield(_))));
+    }
+
+    #[test]
+    fn rejects_shields_with_negative_regen_delay() {
+        // Arrange
+        let mut section = hull("shield");
+        section.kind = SectionKindAsset::Shield(ShieldSectionAsset {
+            capacity: 100.0,
+            regen_rate: 10.0,
+            regen_delay: -1.0,
+            render_mesh: None,
+        });
+
+        // Act
+        let result = validate_sections(&[section]);
+
+        // Assert
+        assert_eq!(
+            result,
+            Err(SectionValidationError::Negative {
+                id: "shield".to_string(),
+                field: "regen_delay",
+                value: -1.0,
+            })
+        );
+    }
 }
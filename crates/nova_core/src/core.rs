@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use bevy::{
     picking::{hover::Hovered, pointer::PointerInteraction},
     platform::collections::HashMap,
@@ -78,9 +80,20 @@ pub(crate) fn core_plugin(app: &mut App) {
         Update,
         lock_on_left_click.run_if(in_state(ExampleStates::Editor)),
     );
+    // The error of the last play is outdated once the design changes
     app.add_systems(
         Update,
-        switch_scene_editor.run_if(in_state(ExampleStates::Scenario)),
+        clear_design_error
+            .run_if(in_state(ExampleStates::Editor).and(resource_changed::<PlayerSpaceshipConfig>)),
+    );
+    app.add_systems(
+        Update,
+        (
+            switch_scene_editor,
+            quick_save_scenario,
+            quick_load_scenario,
+        )
+            .run_if(in_state(ExampleStates::Scenario)),
     );
 
     app.configure_sets(
@@ -122,6 +135,67 @@ fn switch_scene_editor(
     }
 }
 
+/// The file written by the quick save key and read by the quick load key, in the save directory.
+const QUICK_SAVE_FILE: &str = "quicksave.ron";
+
+/// The directory of the saves, in the data directory of the user: `%APPDATA%` on Windows,
+/// `~/Library/Application Support` on macOS and `$XDG_DATA_HOME` or `~/.local/share` elsewhere.
+/// Falls back to the working directory when none of them is set.
+fn save_directory() -> PathBuf {
+    let home = || std::env::var_os("HOME").map(PathBuf::from);
+    let data = if cfg!(target_os = "windows") {
+        std::env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        home().map(|home| home.join("Library/Application Support"))
+    } else {
+        std::env::var_os("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .or_else(|| home().map(|home| home.join(".local/share")))
+    };
+
+    data.map(|data| data.join("nova-protocol").join("saves"))
+        .unwrap_or_default()
+}
+
+fn quick_save_path() -> PathBuf {
+    save_directory().join(QUICK_SAVE_FILE)
+}
+
+fn quick_save_scenario(world: &mut World) {
+    if !world
+        .resource::<ButtonInput<KeyCode>>()
+        .just_pressed(KeyCode::F5)
+    {
+        return;
+    }
+
+    let Some(save) = ScenarioSave::capture(world) else {
+        warn!("quick_save_scenario: no scenario is loaded");
+        return;
+    };
+
+    let path = quick_save_path();
+    match save.write(&path) {
+        Ok(()) => info!("quick_save_scenario: saved to {:?}", path),
+        Err(err) => error!("quick_save_scenario: {}", err),
+    }
+}
+
+fn quick_load_scenario(keys: Res<ButtonInput<KeyCode>>, mut commands: Commands) {
+    if !keys.just_pressed(KeyCode::F9) {
+        return;
+    }
+
+    let path = quick_save_path();
+    match ScenarioSave::read(&path) {
+        Ok(save) => {
+            debug!("quick_load_scenario: loading {:?}", path);
+            commands.trigger(LoadScenarioSave(save));
+        }
+        Err(err) => error!("quick_load_scenario: {}", err),
+    }
+}
+
 #[derive(Resource, Debug, Clone, Default, Reflect)]
 struct PlayerSpaceshipConfig {
     sections: HashMap<Entity, SpaceshipSectionConfig>,
@@ -152,13 +226,15 @@ fn test_scenario(
                 position: pos,
                 rotation: Quat::IDENTITY,
                 health: 100.0,
+                faction: None,
+                explosion: None,
             },
             kind: ScenarioObjectKind::Asteroid(AsteroidConfig { radius, texture }),
         });
     }
 
     let spaceship = SpaceshipConfig {
-        controller: SpaceshipController::AI(AIControllerConfig {}),
+        controller: SpaceshipController::AI(AIControllerConfig::default()),
         sections: vec![
             SpaceshipSectionConfig {
                 id: "controller".to_string(),
@@ -196,6 +272,24 @@ fn test_scenario(
                     .unwrap()
                     .clone(),
             },
+            SpaceshipSectionConfig {
+                id: "reactor".to_string(),
+                position: Vec3::new(1.0, 0.0, 0.0),
+                rotation: Quat::IDENTITY,
+                config: sections
+                    .get_section("basic_reactor_section")
+                    .unwrap()
+                    .clone(),
+            },
+            SpaceshipSectionConfig {
+                id: "battery".to_string(),
+                position: Vec3::new(-1.0, 0.0, 0.0),
+                rotation: Quat::IDENTITY,
+                config: sections
+                    .get_section("basic_battery_section")
+                    .unwrap()
+                    .clone(),
+            },
             SpaceshipSectionConfig {
                 id: "turret".to_string(),
                 position: Vec3::new(0.0, 0.0, -2.0),
@@ -218,6 +312,8 @@ fn test_scenario(
             ),
             rotation: Quat::IDENTITY,
             health: 100.0,
+            faction: None,
+            explosion: None,
         },
         kind: ScenarioObjectKind::Spaceship(spaceship),
     });
@@ -239,6 +335,8 @@ fn test_scenario(
             position: Vec3::new(0.0, 0.0, 50.0),
             rotation: Quat::IDENTITY,
             health: 100.0,
+            faction: None,
+            explosion: None,
         },
         kind: ScenarioObjectKind::Spaceship(player_spaceship),
     });
@@ -265,6 +363,7 @@ fn test_scenario(
             filters: vec![EventFilterConfig::Entity(EntityFilterConfig {
                 id: Some("player_spaceship".to_string()),
                 type_name: None,
+                zone_id: None,
             })],
             actions: vec![EventActionConfig::DebugMessage(DebugMessageActionConfig {
                 message: "The player's spaceship was destroyed!".to_string(),
@@ -275,6 +374,7 @@ fn test_scenario(
             filters: vec![EventFilterConfig::Entity(EntityFilterConfig {
                 id: Some("other_spaceship".to_string()),
                 type_name: None,
+                zone_id: None,
             })],
             actions: vec![
                 EventActionConfig::DebugMessage(DebugMessageActionConfig {
@@ -307,6 +407,7 @@ const BORDER_COLOR_ACTIVE: Color = Color::srgb(0.75, 0.52, 0.99);
 const BACKGROUND_COLOR: Color = Color::srgb(0.1, 0.1, 0.1);
 
 const TEXT_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
+const ERROR_TEXT_COLOR: Color = Color::srgb(0.9, 0.3, 0.3);
 
 fn setup_editor_scene(
     mut commands: Commands,
@@ -451,10 +552,29 @@ fn setup_editor_scene(
                         button("Play"),
                         observe(continue_to_simulation),
                     ));
+                    parent.spawn((
+                        Name::new("Design Error"),
+                        DesignErrorTextMarker,
+                        Text::new(""),
+                        TextFont {
+                            font_size: 14.0,
+                            ..default()
+                        },
+                        TextColor(ERROR_TEXT_COLOR),
+                        Node {
+                            width: percent(80),
+                            margin: UiRect::all(px(10)),
+                            ..default()
+                        },
+                    ));
                 });
         });
 }
 
+/// The text that shows why the spaceship design can't be played.
+#[derive(Component)]
+struct DesignErrorTextMarker;
+
 #[derive(Resource, Default, Debug, Component, PartialEq, Eq, Clone, Reflect)]
 enum SectionChoice {
     #[default]
@@ -584,10 +704,29 @@ fn create_new_spaceship_with_controller(
 fn continue_to_simulation(
     _activate: On<Activate>,
     mut game_state: ResMut<NextState<ExampleStates>>,
+    player_config: Res<PlayerSpaceshipConfig>,
+    mut q_error: Query<&mut Text, With<DesignErrorTextMarker>>,
 ) {
+    if let Err(error) = validate_spaceship_sections(player_config.sections.values()) {
+        warn!(
+            "continue_to_simulation: invalid spaceship design: {}",
+            error
+        );
+        for mut text in &mut q_error {
+            **text = format!("Invalid design: {}", error);
+        }
+        return;
+    }
+
     game_state.set(ExampleStates::Scenario);
 }
 
+fn clear_design_error(mut q_error: Query<&mut Text, With<DesignErrorTextMarker>>) {
+    for mut text in &mut q_error {
+        text.clear();
+    }
+}
+
 #[derive(Component)]
 struct SectionPreviewMarker;
 
@@ -778,6 +917,181 @@ fn on_click_spaceship_section(
                     );
                     player_config.inputs.insert(turret_entity, binds);
                 }
+                SectionKind::Reactor(reactor) => {
+                    let rotation = Quat::IDENTITY;
+
+                    let mut reactor_entity = Entity::PLACEHOLDER;
+                    commands.entity(spaceship).with_children(|parent| {
+                        reactor_entity = parent
+                            .spawn((
+                                base_section(section.base.clone()),
+                                reactor_section(reactor.clone()),
+                                Transform {
+                                    translation: position,
+                                    rotation,
+                                    ..default()
+                                },
+                            ))
+                            .id();
+                    });
+
+                    player_config.sections.insert(
+                        reactor_entity,
+                        SpaceshipSectionConfig {
+                            id: reactor_entity.to_string(),
+                            position,
+                            rotation,
+                            config: section.clone(),
+                        },
+                    );
+                }
+                SectionKind::Battery(battery) => {
+                    let rotation = Quat::IDENTITY;
+
+                    let mut battery_entity = Entity::PLACEHOLDER;
+                    commands.entity(spaceship).with_children(|parent| {
+                        battery_entity = parent
+                            .spawn((
+                                base_section(section.base.clone()),
+                                battery_section(battery.clone()),
+                                Transform {
+                                    translation: position,
+                                    rotation,
+                                    ..default()
+                                },
+                            ))
+                            .id();
+                    });
+
+                    player_config.sections.insert(
+                        battery_entity,
+                        SpaceshipSectionConfig {
+                            id: battery_entity.to_string(),
+                            position,
+                            rotation,
+                            config: section.clone(),
+                        },
+                    );
+                }
+                SectionKind::Shield(shield) => {
+                    let rotation = Quat::IDENTITY;
+
+                    let mut shield_entity = Entity::PLACEHOLDER;
+                    commands.entity(spaceship).with_children(|parent| {
+                        shield_entity = parent
+                            .spawn((
+                                base_section(section.base.clone()),
+                                shield_section(shield.clone()),
+                                Transform {
+                                    translation: position,
+                                    rotation,
+                                    ..default()
+                                },
+                            ))
+                            .id();
+                    });
+
+                    player_config.sections.insert(
+                        shield_entity,
+                        SpaceshipSectionConfig {
+                            id: shield_entity.to_string(),
+                            position,
+                            rotation,
+                            config: section.clone(),
+                        },
+                    );
+                }
+                SectionKind::Beam(beam) => {
+                    let rotation = Quat::from_rotation_arc(Vec3::Y, normal.normalize());
+
+                    let key_bind = keyboard.map(|k| {
+                        k.get_pressed()
+                            .next()
+                            .map_or(MouseButton::Left.into(), |k| Binding::from(*k))
+                    });
+                    let pad_bind = gamepad.map(|b| {
+                        b.get_pressed()
+                            .next()
+                            .map_or(GamepadButton::RightTrigger2.into(), |b| Binding::from(*b))
+                    });
+                    let binds = vec![key_bind, pad_bind]
+                        .into_iter()
+                        .flatten()
+                        .collect::<Vec<Binding>>();
+
+                    let mut beam_entity = Entity::PLACEHOLDER;
+                    commands.entity(spaceship).with_children(|parent| {
+                        beam_entity = parent
+                            .spawn((
+                                base_section(section.base.clone()),
+                                beam_section(beam.clone()),
+                                SpaceshipTurretInputBinding(binds.clone()),
+                                Transform {
+                                    translation: position,
+                                    rotation,
+                                    ..default()
+                                },
+                            ))
+                            .id();
+                    });
+
+                    player_config.sections.insert(
+                        beam_entity,
+                        SpaceshipSectionConfig {
+                            id: beam_entity.to_string(),
+                            position,
+                            rotation,
+                            config: section.clone(),
+                        },
+                    );
+                    player_config.inputs.insert(beam_entity, binds);
+                }
+                SectionKind::MissileLauncher(launcher) => {
+                    // Launchers always face the front of the spaceship
+                    let rotation = Quat::IDENTITY;
+
+                    let key_bind = keyboard.map(|k| {
+                        k.get_pressed()
+                            .next()
+                            .map_or(KeyCode::KeyF.into(), |k| Binding::from(*k))
+                    });
+                    let pad_bind = gamepad.map(|b| {
+                        b.get_pressed()
+                            .next()
+                            .map_or(GamepadButton::LeftTrigger2.into(), |b| Binding::from(*b))
+                    });
+                    let binds = vec![key_bind, pad_bind]
+                        .into_iter()
+                        .flatten()
+                        .collect::<Vec<Binding>>();
+
+                    let mut launcher_entity = Entity::PLACEHOLDER;
+                    commands.entity(spaceship).with_children(|parent| {
+                        launcher_entity = parent
+                            .spawn((
+                                base_section(section.base.clone()),
+                                missile_launcher_section(launcher.clone()),
+                                SpaceshipMissileInputBinding(binds.clone()),
+                                Transform {
+                                    translation: position,
+                                    rotation,
+                                    ..default()
+                                },
+                            ))
+                            .id();
+                    });
+
+                    player_config.sections.insert(
+                        launcher_entity,
+                        SpaceshipSectionConfig {
+                            id: launcher_entity.to_string(),
+                            position,
+                            rotation,
+                            config: section.clone(),
+                        },
+                    );
+                    player_config.inputs.insert(launcher_entity, binds);
+                }
             }
         }
         SectionChoice::Delete => {
@@ -0,0 +1,191 @@
+//! A headless version of the game, without a window or the render app, used to simulate scenarios
+//! in automated tests on machines without a GPU.
+//!
+//! Time is advanced manually: every `App::update` moves the clock forward by exactly one fixed
+//! timestep, so `FixedUpdate` (and the physics) runs once per update, and the same scenario with
+//! the same seed always plays out the same way.
+
+use std::time::Duration;
+
+use bevy::{
+    app::Plugins, diagnostic::DiagnosticsPlugin, input::InputPlugin, prelude::*,
+    scene::ScenePlugin, state::app::StatesPlugin, time::TimeUpdateStrategy,
+};
+use bevy_rand::prelude::*;
+use nova_gameplay::prelude::*;
+use nova_scenario::prelude::*;
+
+/// The default timestep of the headless app, the same as the default of `Time<Fixed>`.
+const DEFAULT_TIMESTEP: Duration = Duration::from_micros(15625);
+
+pub struct HeadlessAppBuilder {
+    app: App,
+    timestep: Duration,
+    seed: u64,
+    scenario: Option<ScenarioConfig>,
+}
+
+impl Default for HeadlessAppBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HeadlessAppBuilder {
+    pub fn new() -> Self {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            TransformPlugin,
+            AssetPlugin {
+                meta_check: bevy::asset::AssetMetaCheck::Never,
+                ..default()
+            },
+            ScenePlugin,
+            InputPlugin,
+            StatesPlugin,
+            DiagnosticsPlugin,
+        ));
+
+        // The assets that the gameplay systems use, normally added by the render plugins
+        app.init_asset::<Mesh>();
+        app.init_asset::<Image>();
+        app.init_asset::<StandardMaterial>();
+
+        Self {
+            app,
+            timestep: DEFAULT_TIMESTEP,
+            seed: 0,
+            scenario: None,
+        }
+    }
+
+    /// The time that passes on every update.
+    pub fn with_timestep(mut self, timestep: Duration) -> Self {
+        self.timestep = timestep;
+        self
+    }
+
+    /// The seed of the global random number generator.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// The scenario to load when the app is built.
+    pub fn with_scenario(mut self, scenario: ScenarioConfig) -> Self {
+        self.scenario = Some(scenario);
+        self
+    }
+
+    pub fn with_game_plugins<M>(mut self, plugins: impl Plugins<M>) -> Self {
+        self.app.add_plugins(plugins);
+        self
+    }
+
+    pub fn build(mut self) -> App {
+        self.app
+            .insert_resource(Time::<Fixed>::from_duration(self.timestep));
+        self.app
+            .insert_resource(TimeUpdateStrategy::ManualDuration(self.timestep));
+
+        self.app
+            .add_plugins(EntropyPlugin::<WyRand>::with_seed(self.seed.to_le_bytes()));
+        self.app
+            .add_plugins(bevy_enhanced_input::EnhancedInputPlugin);
+        self.app.add_plugins(NovaGameplayPlugin { render: false });
+        self.app.add_plugins(NovaScenarioPlugin { render: false });
+        self.app.init_resource::<GameScenarios>();
+
+        if let Some(scenario) = self.scenario {
+            self.app
+                .world_mut()
+                .resource_mut::<GameScenarios>()
+                .insert(scenario.id.clone(), scenario.clone());
+            self.app.world_mut().trigger(LoadScenario(scenario));
+            self.app.world_mut().flush();
+        }
+
+        self.app
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use avian3d::prelude::*;
+    use nova_events::prelude::*;
+
+    use super::*;
+
+    fn scenario() -> ScenarioConfig {
+        ScenarioConfig {
+            id: "headless".to_string(),
+            name: "Headless".to_string(),
+            description: String::new(),
+            cubemap: Handle::default(),
+            events: vec![ScenarioEventConfig {
+                name: EventConfig::OnStart,
+                filters: vec![],
+                actions: vec![EventActionConfig::SpawnScenarioObject(
+                    ScenarioObjectConfig {
+                        base: BaseScenarioObjectConfig {
+                            id: "asteroid".to_string(),
+                            name: "Asteroid".to_string(),
+                            position: Vec3::ZERO,
+                            rotation: Quat::IDENTITY,
+                            health: 100.0,
+                            faction: None,
+                            explosion: None,
+                        },
+                        kind: ScenarioObjectKind::Asteroid(AsteroidConfig {
+                            radius: 1.0,
+                            texture: Handle::default(),
+                        }),
+                    },
+                )],
+            }],
+        }
+    }
+
+    fn asteroid_position(app: &mut App) -> Option<Vec3> {
+        app.world_mut()
+            .query::<(&EntityId, &Transform)>()
+            .iter(app.world())
+            .find(|(id, _)| id.0 == "asteroid")
+            .map(|(_, transform)| transform.translation)
+    }
+
+    #[test]
+    fn simulates_a_scenario_deterministically() {
+        // Arrange
+        let mut apps = [0, 1].map(|_| {
+            HeadlessAppBuilder::new()
+                .with_seed(42)
+                .with_scenario(scenario())
+                .build()
+        });
+
+        // Act
+        for app in apps.iter_mut() {
+            // Spawn the asteroid, then push it
+            app.update();
+            app.update();
+            let mut q_velocity = app.world_mut().query::<(&EntityId, &mut LinearVelocity)>();
+            for (id, mut velocity) in q_velocity.iter_mut(app.world_mut()) {
+                if id.0 == "asteroid" {
+                    velocity.0 = Vec3::X;
+                }
+            }
+
+            for _ in 0..64 {
+                app.update();
+            }
+        }
+
+        // Assert
+        let positions = apps.each_mut().map(asteroid_position);
+        let position = positions[0].unwrap();
+        assert!(position.x > 0.5);
+        assert_eq!(positions[0], positions[1]);
+    }
+}
@@ -15,6 +15,7 @@ pub use nova_scenario;
 use nova_scenario::prelude::*;
 
 mod core;
+pub mod headless;
 
 pub mod prelude {
     pub use nova_assets::prelude::*;
@@ -25,7 +26,7 @@ pub mod prelude {
     pub use nova_info::prelude::*;
     pub use nova_scenario::prelude::*;
 
-    pub use super::{AppBuilder, GameStates};
+    pub use super::{headless::HeadlessAppBuilder, AppBuilder, GameStates};
 }
 
 /// Game states for the application.
@@ -117,23 +118,6 @@ impl AppBuilder {
     }
 }
 
-// pub fn new_headless_app() -> App {
-//     let mut app = App::new();
-//     app.add_plugins((
-//         DefaultPlugins
-//             .build()
-//             .set(AssetPlugin {
-//                 meta_check: bevy::asset::AssetMetaCheck::Never,
-//                 ..default()
-//             })
-//             .set(log_plugin())
-//             .disable::<WinitPlugin>(),
-//         ScheduleRunnerPlugin::run_loop(Duration::from_secs_f64(1.0 / 64.0)),
-//     ));
-//
-//     app
-// }
-
 fn window_plugin() -> WindowPlugin {
     WindowPlugin {
         primary_window: Some(Window {
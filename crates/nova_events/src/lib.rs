@@ -3,8 +3,10 @@ use bevy_common_systems::prelude::*;
 
 pub mod prelude {
     pub use super::{
-        EntityId, EntityTypeName, OnDestroyedEvent, OnDestroyedEventInfo, OnStartEvent,
-        OnStartEventInfo, OnUpdateEvent, OnUpdateEventInfo, ENTITY_ID_COMPONENT_NAME,
+        EntityId, EntityTypeName, OnDamagedEvent, OnDamagedEventInfo, OnDestroyedEvent,
+        OnDestroyedEventInfo, OnEnterZoneEvent, OnEnterZoneEventInfo, OnExitZoneEvent,
+        OnExitZoneEventInfo, OnStartEvent, OnStartEventInfo, OnTimerEvent, OnTimerEventInfo,
+        OnUpdateEvent, OnUpdateEventInfo, ENTITY_ID_COMPONENT_NAME,
         ENTITY_TYPE_NAME_COMPONENT_NAME,
     };
 }
@@ -50,6 +52,40 @@ pub struct OnDestroyedEventInfo {
     pub id: String,
     #[serde(rename = "type_name")]
     pub type_name: String,
+    /// The id of the entity that did the last damage, if it is a scenario object
+    #[serde(rename = "source_id")]
+    pub source_id: Option<String>,
+    #[serde(rename = "source_type_name")]
+    pub source_type_name: Option<String>,
+    /// The kind of the last damage: "collision", "projectile", "explosive" or "scripted"
+    #[serde(rename = "damage_kind")]
+    pub damage_kind: Option<String>,
+}
+
+#[derive(Debug, Clone, EventKind, Reflect)]
+#[event_name("ondamaged")]
+#[event_info(OnDamagedEventInfo)]
+pub struct OnDamagedEvent;
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Default, Reflect)]
+pub struct OnDamagedEventInfo {
+    #[serde(rename = "id")]
+    pub id: String,
+    #[serde(rename = "type_name")]
+    pub type_name: String,
+    /// The id of the entity that caused the damage, if it is a scenario object
+    #[serde(rename = "source_id")]
+    pub source_id: Option<String>,
+    #[serde(rename = "source_type_name")]
+    pub source_type_name: Option<String>,
+    #[serde(rename = "amount")]
+    pub amount: f32,
+    /// The kind of the damage: "collision", "projectile", "explosive" or "scripted"
+    #[serde(rename = "damage_kind")]
+    pub damage_kind: String,
+    /// The remaining health of the target, between 0 and 1
+    #[serde(rename = "health")]
+    pub health: f32,
 }
 
 #[derive(Debug, Clone, EventKind, Reflect)]
@@ -59,3 +95,46 @@ pub struct OnUpdateEvent;
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Default, Reflect)]
 pub struct OnUpdateEventInfo;
+
+#[derive(Debug, Clone, EventKind, Reflect)]
+#[event_name("ontimer")]
+#[event_info(OnTimerEventInfo)]
+pub struct OnTimerEvent;
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Default, Reflect)]
+pub struct OnTimerEventInfo {
+    #[serde(rename = "id")]
+    pub id: String,
+    #[serde(rename = "type_name")]
+    pub type_name: String,
+}
+
+#[derive(Debug, Clone, EventKind, Reflect)]
+#[event_name("onenterzone")]
+#[event_info(OnEnterZoneEventInfo)]
+pub struct OnEnterZoneEvent;
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Default, Reflect)]
+pub struct OnEnterZoneEventInfo {
+    #[serde(rename = "id")]
+    pub id: String,
+    #[serde(rename = "type_name")]
+    pub type_name: String,
+    #[serde(rename = "zone_id")]
+    pub zone_id: String,
+}
+
+#[derive(Debug, Clone, EventKind, Reflect)]
+#[event_name("onexitzone")]
+#[event_info(OnExitZoneEventInfo)]
+pub struct OnExitZoneEvent;
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Default, Reflect)]
+pub struct OnExitZoneEventInfo {
+    #[serde(rename = "id")]
+    pub id: String,
+    #[serde(rename = "type_name")]
+    pub type_name: String,
+    #[serde(rename = "zone_id")]
+    pub zone_id: String,
+}
@@ -1,7 +1,10 @@
 //! A Bevy plugin that handles damage.
 
 pub mod prelude {
-    pub use super::{DamagePlugin, MeshFragmentMarker};
+    pub use super::{
+        AreaDamage, DamageFalloff, DamagePlugin, DamageResistances, ExplosionConfig,
+        FriendlyFirePolicy, MeshFragmentMarker, ProjectileOwner, SpaceshipWreckMarker,
+    };
 }
 
 use avian3d::prelude::*;
@@ -11,11 +14,144 @@ use bevy_rand::prelude::*;
 use nova_events::prelude::*;
 use rand::Rng;
 
+use crate::prelude::*;
+
 const DAMAGE_MODIFIER: f32 = 1.00;
 
 #[derive(Component, Debug, Clone, Reflect)]
 pub struct MeshFragmentMarker;
 
+/// A part of a spaceship that broke off: a destroyed section, or the sections that lost their
+/// connection to the controller of the spaceship.
+#[derive(Component, Debug, Clone, Reflect)]
+pub struct SpaceshipWreckMarker;
+
+/// The spaceship that fired a projectile. The damage done by the projectile is credited to it.
+#[derive(Component, Clone, Copy, Debug, Deref, DerefMut, Reflect)]
+pub struct ProjectileOwner(pub Entity);
+
+/// How much damage entities of the same faction do to each other.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Reflect)]
+pub enum FriendlyFirePolicy {
+    /// Friendly damage is applied in full.
+    #[default]
+    Allow,
+    /// Friendly damage is multiplied by the given factor.
+    Scaled(f32),
+    /// Friendly damage is ignored.
+    Deny,
+}
+
+impl FriendlyFirePolicy {
+    /// The damage that the source does to the target. Entities without a faction are neutral, so
+    /// the policy only applies when both have the same faction.
+    pub fn damage(&self, amount: f32, target: Option<&Faction>, source: Option<&Faction>) -> f32 {
+        let friendly = match (target, source) {
+            (Some(target), Some(source)) => !target.is_hostile(source),
+            _ => false,
+        };
+        if !friendly {
+            return amount;
+        }
+
+        match self {
+            FriendlyFirePolicy::Allow => amount,
+            FriendlyFirePolicy::Scaled(factor) => amount * factor,
+            FriendlyFirePolicy::Deny => 0.0,
+        }
+    }
+}
+
+/// How well an entity withstands each kind of damage, usually a section of a spaceship.
+///
+/// The resistances are the fraction of the damage that is absorbed, between 0 (none) and 1 (all).
+/// The armor is then subtracted from what is left of every hit. Scripted damage ignores both.
+#[derive(
+    Component, Clone, Debug, Default, PartialEq, Reflect, serde::Serialize, serde::Deserialize,
+)]
+#[serde(default)]
+pub struct DamageResistances {
+    pub collision: f32,
+    pub projectile: f32,
+    pub explosive: f32,
+    pub armor: f32,
+}
+
+impl DamageResistances {
+    /// The damage that is left after the resistances and the armor.
+    pub fn apply(&self, kind: DamageKind, amount: f32) -> f32 {
+        let resistance = match kind {
+            DamageKind::Collision => self.collision,
+            DamageKind::Projectile => self.projectile,
+            DamageKind::Explosive => self.explosive,
+            DamageKind::Scripted => return amount,
+        };
+
+        (amount * (1.0 - resistance.clamp(0.0, 1.0)) - self.armor.max(0.0)).max(0.0)
+    }
+}
+
+/// How the damage and the impulse of an area decrease from its center to its radius.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Reflect, serde::Serialize, serde::Deserialize)]
+pub enum DamageFalloff {
+    /// The same damage everywhere in the area.
+    Constant,
+    /// The damage decreases linearly to zero at the radius.
+    #[default]
+    Linear,
+    /// The damage decreases quadratically to zero at the radius, it is concentrated at the center.
+    Quadratic,
+}
+
+impl DamageFalloff {
+    /// The fraction of the damage of an area dealt at the given distance from its center.
+    pub fn factor(&self, distance: f32, radius: f32) -> f32 {
+        if radius <= 0.0 || distance > radius {
+            return 0.0;
+        }
+
+        let remaining = (1.0 - distance / radius).clamp(0.0, 1.0);
+        match self {
+            DamageFalloff::Constant => 1.0,
+            DamageFalloff::Linear => remaining,
+            DamageFalloff::Quadratic => remaining * remaining,
+        }
+    }
+}
+
+/// Event to damage and push away everything around a point, like the blast of a missile.
+#[derive(Event, Clone, Debug)]
+pub struct AreaDamage {
+    /// The center of the area in world space.
+    pub center: Vec3,
+    /// The radius of the area.
+    pub radius: f32,
+    /// The damage at the center of the area.
+    pub damage: f32,
+    /// How the damage and the impulse decrease from the center to the radius.
+    pub falloff: DamageFalloff,
+    /// The impulse applied at the center of the area to the bodies, away from the center.
+    pub impulse: f32,
+    /// The entity credited with the damage.
+    pub source: Option<Entity>,
+}
+
+/// An explosion when the entity is destroyed. It damages and pushes away everything in range,
+/// which can destroy other explosive entities in a chain reaction.
+#[derive(Component, Clone, Debug, PartialEq, Reflect, serde::Serialize, serde::Deserialize)]
+pub struct ExplosionConfig {
+    /// The radius of the explosion.
+    pub radius: f32,
+    /// The damage at the center of the explosion.
+    pub damage: f32,
+    /// How the damage and the impulse decrease from the center to the radius.
+    #[serde(default)]
+    pub falloff: DamageFalloff,
+    /// The impulse at the center of the explosion.
+    #[serde(default)]
+    pub impulse: f32,
+}
+
 /// A plugin that handles damage.
 pub struct DamagePlugin;
 
@@ -23,10 +159,16 @@ impl Plugin for DamagePlugin {
     fn build(&self, app: &mut App) {
         debug!("DamagePlugin: build");
 
+        app.init_resource::<FriendlyFirePolicy>();
+
         app.add_observer(on_collider_of_spawn);
         app.add_observer(on_collision_hit_to_damage);
+        app.add_observer(on_area_damage);
+        app.add_observer(on_damaged_entity);
         app.add_observer(on_destroyed_entity);
+        app.add_observer(on_destroyed_section);
         app.add_observer(on_explode_entity);
+        app.add_observer(on_explosion_area_damage);
         app.add_observer(handle_entity_explosion);
     }
 }
@@ -63,22 +205,246 @@ fn on_collision_hit_to_damage(
     hit: On<CollisionImpactEvent>,
     mut commands: Commands,
     q_mass: Query<&ComputedMass>,
+    q_owner: Query<&ProjectileOwner>,
+    q_faction: Query<&Faction>,
+    q_resistances: Query<&DamageResistances>,
+    q_section_health: Query<(), (With<SectionMarker>, With<Health>)>,
+    policy: Res<FriendlyFirePolicy>,
 ) {
     let amount = hit.relative_velocity.length() * DAMAGE_MODIFIER;
     let mass = q_mass.get(hit.other).map(|m| m.value()).unwrap_or(1.0);
     let amount = amount * mass;
 
-    commands.trigger(HealthApplyDamage {
-        target: hit.entity,
-        source: Some(hit.other),
+    // Projectiles deal damage on behalf of the ship that fired them
+    let (source, kind) = match q_owner.get(hit.other) {
+        Ok(owner) => (**owner, DamageKind::Projectile),
+        Err(_) => (hit.other, DamageKind::Collision),
+    };
+    let amount = policy.damage(
         amount,
+        q_faction.get(hit.entity).ok(),
+        q_faction.get(source).ok(),
+    );
+
+    // The section that was hit absorbs some of the damage
+    let amount = match q_resistances.get(hit.collider) {
+        Ok(resistances) => resistances.apply(kind, amount),
+        Err(_) => amount,
+    };
+    if amount <= 0.0 {
+        trace!(
+            "on_collision_hit_to_damage: damage from {:?} to {:?} ignored",
+            source,
+            hit.entity
+        );
+        return;
+    }
+
+    // The section that was hit takes the damage as well
+    let sections = if hit.collider != hit.entity && q_section_health.contains(hit.collider) {
+        vec![(hit.collider, amount)]
+    } else {
+        Vec::new()
+    };
+    damage_body_and_sections(
+        &mut commands,
+        HealthApplyDamage {
+            target: hit.entity,
+            source: Some(source),
+            amount,
+            kind,
+        },
+        sections,
+    );
+}
+
+fn on_area_damage(
+    area: On<AreaDamage>,
+    mut commands: Commands,
+    spatial_query: SpatialQuery,
+    q_collider: Query<(&ColliderOf, &GlobalTransform), Without<DestroyedMarker>>,
+    q_health: Query<(), (With<Health>, Without<DestroyedMarker>)>,
+    q_faction: Query<&Faction>,
+    q_resistances: Query<&DamageResistances>,
+    mut q_forces: Query<Forces>,
+    policy: Res<FriendlyFirePolicy>,
+) {
+    trace!("on_area_damage: area {:?}", *area);
+
+    let kind = DamageKind::Explosive;
+    let source_faction = area.source.and_then(|source| q_faction.get(source).ok());
+    let colliders = spatial_query.shape_intersections(
+        &Collider::sphere(area.radius),
+        area.center,
+        Quat::IDENTITY,
+        &SpatialQueryFilter::default(),
+    );
+
+    // A body takes the damage and the impulse of its closest collider, and every section in range
+    // takes its own damage
+    let mut bodies = Vec::<(Entity, f32, f32, Vec3)>::new();
+    let mut sections = Vec::<(Entity, Entity, f32)>::new();
+    for collider in colliders {
+        let Ok((&ColliderOf { body }, transform)) = q_collider.get(collider) else {
+            continue;
+        };
+
+        let position = transform.translation();
+        let factor = area
+            .falloff
+            .factor(position.distance(area.center), area.radius);
+        if factor <= 0.0 {
+            continue;
+        }
+
+        let amount = policy.damage(
+            area.damage * factor,
+            q_faction.get(body).ok(),
+            source_faction,
+        );
+        let amount = match q_resistances.get(collider) {
+            Ok(resistances) => resistances.apply(kind, amount),
+            Err(_) => amount,
+        };
+
+        if amount > 0.0 && collider != body && q_health.contains(collider) {
+            sections.push((collider, body, amount));
+        }
+
+        match bodies.iter_mut().find(|(entity, ..)| *entity == body) {
+            Some((_, damage, closest, closest_position)) => {
+                *damage = damage.max(amount);
+                if factor > *closest {
+                    *closest = factor;
+                    *closest_position = position;
+                }
+            }
+            None => bodies.push((body, amount, factor, position)),
+        }
+    }
+
+    for (body, amount, factor, position) in bodies {
+        if area.impulse > 0.0 {
+            if let Ok(mut forces) = q_forces.get_mut(body) {
+                let direction = (position - area.center).normalize_or(Vec3::Y);
+                forces.apply_linear_impulse(direction * area.impulse * factor);
+            }
+        }
+
+        if amount <= 0.0 {
+            continue;
+        }
+
+        let body_sections = sections
+            .iter()
+            .filter(|(_, of, _)| *of == body)
+            .map(|&(section, _, damage)| (section, damage))
+            .collect();
+        damage_body_and_sections(
+            &mut commands,
+            HealthApplyDamage {
+                target: body,
+                source: area.source,
+                amount,
+                kind,
+            },
+            body_sections,
+        );
+    }
+}
+
+/// Damage a body and the sections of it that were hit, each section with its own amount. The
+/// shield of the body takes the hit once, through the `HealthDamageHooks` when the body is
+/// damaged, and the sections only take the damage that went through it.
+pub(crate) fn damage_body_and_sections(
+    commands: &mut Commands,
+    damage: HealthApplyDamage,
+    sections: Vec<(Entity, f32)>,
+) {
+    commands.queue(move |world: &mut World| {
+        let (body, source, kind) = (damage.target, damage.source, damage.kind);
+        let shield = |world: &World| {
+            world
+                .get::<SpaceshipShield>(body)
+                .map_or(0.0, |shield| shield.current)
+        };
+
+        let before = shield(world);
+        world.trigger(damage);
+        let absorbed = before - shield(world);
+
+        for (section, amount) in sections {
+            let amount = amount - absorbed;
+            if amount > 0.0 {
+                world.trigger(HealthApplyDamage {
+                    target: section,
+                    source,
+                    amount,
+                    kind,
+                });
+            }
+        }
+    });
+}
+
+/// The scenario info of the entities that fire scenario events. Sections are part of their
+/// spaceship, so they don't fire events of their own.
+type ScenarioInfoQuery<'w, 's> =
+    Query<'w, 's, (&'static EntityId, &'static EntityTypeName), Without<SectionMarker>>;
+
+fn on_damaged_entity(
+    damaged: On<HealthDamaged>,
+    mut commands: Commands,
+    q_info: ScenarioInfoQuery,
+    q_collider: Query<&ColliderOf>,
+) {
+    let entity = damaged.target;
+    trace!("on_damaged_entity: entity {:?}", entity);
+
+    let Ok((id, type_name)) = q_info.get(entity) else {
+        return;
+    };
+
+    let source = damaged
+        .source
+        .and_then(|source| source_info(source, &q_info, &q_collider));
+
+    debug!(
+        "on_damaged_entity: entity {:?} damaged by {:?} (id: {:?}, type: {:?}, amount: {:?})",
+        entity, damaged.source, id, type_name, damaged.amount
+    );
+    commands.fire::<OnDamagedEvent>(OnDamagedEventInfo {
+        id: id.to_string(),
+        type_name: type_name.to_string(),
+        source_id: source.map(|(id, _)| id.to_string()),
+        source_type_name: source.map(|(_, type_name)| type_name.to_string()),
+        amount: damaged.amount,
+        damage_kind: damaged.kind.name().to_string(),
+        health: damaged.health.fraction(),
     });
 }
 
+/// The scenario info of the entity that caused some damage. The source of a collision is usually a
+/// collider, the scenario info lives on its body.
+fn source_info<'a>(
+    source: Entity,
+    q_info: &'a ScenarioInfoQuery,
+    q_collider: &Query<&ColliderOf>,
+) -> Option<(&'a EntityId, &'a EntityTypeName)> {
+    q_info.get(source).ok().or_else(|| {
+        q_collider
+            .get(source)
+            .ok()
+            .and_then(|collider| q_info.get(collider.body).ok())
+    })
+}
+
 fn on_destroyed_entity(
     add: On<Add, DestroyedMarker>,
     mut commands: Commands,
-    q_info: Query<(&EntityId, &EntityTypeName), With<DestroyedMarker>>,
+    q_info: ScenarioInfoQuery,
+    q_collider: Query<&ColliderOf>,
+    q_last_damage: Query<&HealthLastDamage, With<DestroyedMarker>>,
 ) {
     let entity = add.entity;
     trace!("on_destroyed_entity: entity {:?}", entity);
@@ -87,6 +453,11 @@ fn on_destroyed_entity(
         return;
     };
 
+    let last_damage = q_last_damage.get(entity).ok();
+    let source = last_damage
+        .and_then(|last_damage| last_damage.source)
+        .and_then(|source| source_info(source, &q_info, &q_collider));
+
     debug!(
         "on_destroyed_entity: entity {:?} destroyed (id: {:?}, type: {:?})",
         entity, id, type_name
@@ -94,9 +465,111 @@ fn on_destroyed_entity(
     commands.fire::<OnDestroyedEvent>(OnDestroyedEventInfo {
         id: id.to_string(),
         type_name: type_name.to_string(),
+        source_id: source.map(|(id, _)| id.to_string()),
+        source_type_name: source.map(|(_, type_name)| type_name.to_string()),
+        damage_kind: last_damage.map(|last_damage| last_damage.kind.name().to_string()),
     });
 }
 
+/// Detach a destroyed section from its spaceship, so that it drifts away as debris. The spaceship
+/// loses the mass and the capabilities of the section. If the spaceship is no longer connected,
+/// the sections that are cut from the controller become a separate wreck.
+fn on_destroyed_section(
+    add: On<Add, DestroyedMarker>,
+    mut commands: Commands,
+    q_section: Query<(&GlobalTransform, &ChildOf), (With<SectionMarker>, With<DestroyedMarker>)>,
+    q_spaceship: Query<(&Transform, &LinearVelocity, &AngularVelocity, &Children)>,
+    q_remaining: Query<
+        (&Transform, Has<ControllerSectionMarker>),
+        (With<SectionMarker>, Without<DestroyedMarker>),
+    >,
+) {
+    let entity = add.entity;
+    trace!("on_destroyed_section: entity {:?}", entity);
+
+    let Ok((transform, &ChildOf(spaceship))) = q_section.get(entity) else {
+        return;
+    };
+
+    let Ok((spaceship_transform, linear_velocity, angular_velocity, children)) =
+        q_spaceship.get(spaceship)
+    else {
+        error!(
+            "on_destroyed_section: spaceship {:?} not found in q_spaceship",
+            spaceship
+        );
+        return;
+    };
+
+    debug!(
+        "on_destroyed_section: entity {:?} detached from spaceship {:?}",
+        entity, spaceship
+    );
+    commands.entity(entity).remove::<ChildOf>().insert((
+        SpaceshipWreckMarker,
+        transform.compute_transform(),
+        RigidBody::Dynamic,
+        *linear_velocity,
+        *angular_velocity,
+    ));
+
+    let remaining = children
+        .iter()
+        .filter(|child| *child != entity)
+        .filter_map(|child| {
+            q_remaining
+                .get(child)
+                .ok()
+                .map(|(transform, controller)| (child, transform.translation, controller))
+        })
+        .collect::<Vec<_>>();
+
+    for wreck in disconnected_sections(&remaining) {
+        debug!(
+            "on_destroyed_section: sections {:?} split from spaceship {:?}",
+            wreck, spaceship
+        );
+        commands
+            .spawn((
+                Name::new("Spaceship Wreck"),
+                SpaceshipWreckMarker,
+                *spaceship_transform,
+                RigidBody::Dynamic,
+                *linear_velocity,
+                *angular_velocity,
+                Visibility::Visible,
+            ))
+            .add_children(&wreck);
+    }
+}
+
+/// The groups of sections that are no longer connected to the spaceship. The spaceship keeps the
+/// largest group with a controller, or the largest group if no controller is left.
+fn disconnected_sections(sections: &[(Entity, Vec3, bool)]) -> Vec<Vec<Entity>> {
+    let graph = SectionGraph::new(
+        sections
+            .iter()
+            .map(|&(entity, position, _)| (entity, position)),
+    );
+    let mut components = graph.components();
+    if components.len() <= 1 {
+        return Vec::new();
+    }
+
+    let is_controller = |entity: &Entity| {
+        sections
+            .iter()
+            .any(|(section, _, controller)| section == entity && *controller)
+    };
+    let kept = components
+        .iter()
+        .position(|component| component.iter().any(is_controller))
+        .unwrap_or(0);
+    components.remove(kept);
+
+    components
+}
+
 fn on_explode_entity(
     add: On<Add, DestroyedMarker>,
     mut commands: Commands,
@@ -115,6 +588,45 @@ fn on_explode_entity(
         .insert(ExplodeMesh { fragment_count: 4 });
 }
 
+/// Damage everything around an explosive entity when it is destroyed. The damage is credited to
+/// whatever destroyed the entity, so that chain reactions are credited to whoever started them.
+fn on_explosion_area_damage(
+    add: On<Add, DestroyedMarker>,
+    mut commands: Commands,
+    q_explosion: Query<
+        (
+            &GlobalTransform,
+            &ExplosionConfig,
+            Option<&HealthLastDamage>,
+        ),
+        With<DestroyedMarker>,
+    >,
+) {
+    let entity = add.entity;
+    trace!("on_explosion_area_damage: entity {:?}", entity);
+
+    let Ok((transform, explosion, last_damage)) = q_explosion.get(entity) else {
+        return;
+    };
+
+    let source = last_damage
+        .and_then(|last_damage| last_damage.source)
+        .unwrap_or(entity);
+
+    debug!(
+        "on_explosion_area_damage: entity {:?} explodes with {:?}",
+        entity, explosion
+    );
+    commands.trigger(AreaDamage {
+        center: transform.translation(),
+        radius: explosion.radius,
+        damage: explosion.damage,
+        falloff: explosion.falloff,
+        impulse: explosion.impulse,
+        source: Some(source),
+    });
+}
+
 fn handle_entity_explosion(
     add: On<Add, ExplodeFragments>,
     mut commands: Commands,
@@ -169,3 +681,162 @@ fn handle_entity_explosion(
 
     commands.entity(entity).despawn();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_sections_cut_from_the_controller() {
+        // Arrange
+        let (controller, hull, front, back) = (
+            Entity::from_raw_u32(1).unwrap(),
+            Entity::from_raw_u32(2).unwrap(),
+            Entity::from_raw_u32(3).unwrap(),
+            Entity::from_raw_u32(4).unwrap(),
+        );
+        // The section at Z = 1 was destroyed, cutting the back from the rest
+        let sections = [
+            (front, Vec3::NEG_Z, false),
+            (controller, Vec3::ZERO, true),
+            (back, Vec3::new(0.0, 0.0, 2.0), false),
+            (hull, Vec3::new(0.0, 0.0, 3.0), false),
+        ];
+
+        // Act
+        let mut wrecks = disconnected_sections(&sections);
+        let connected = disconnected_sections(&sections[..2]);
+
+        // Assert
+        assert_eq!(wrecks.len(), 1);
+        wrecks[0].sort();
+        assert_eq!(wrecks[0], vec![hull, back]);
+        assert!(connected.is_empty());
+    }
+
+    #[test]
+    fn resistances_absorb_damage_by_kind() {
+        // Arrange
+        let resistances = DamageResistances {
+            collision: 0.5,
+            projectile: 1.0,
+            explosive: 0.0,
+            armor: 2.0,
+        };
+
+        // Act
+        let collision = resistances.apply(DamageKind::Collision, 10.0);
+        let projectile = resistances.apply(DamageKind::Projectile, 10.0);
+        let explosive = resistances.apply(DamageKind::Explosive, 1.0);
+        let scripted = resistances.apply(DamageKind::Scripted, 10.0);
+
+        // Assert
+        assert_eq!(collision, 3.0);
+        assert_eq!(projectile, 0.0);
+        assert_eq!(explosive, 0.0);
+        assert_eq!(scripted, 10.0);
+    }
+
+    #[test]
+    fn area_damage_falls_off_with_distance() {
+        // Arrange
+        let radius = 10.0;
+
+        // Act
+        let center = DamageFalloff::Linear.factor(0.0, radius);
+        let linear = DamageFalloff::Linear.factor(5.0, radius);
+        let quadratic = DamageFalloff::Quadratic.factor(5.0, radius);
+        let constant = DamageFalloff::Constant.factor(5.0, radius);
+        let outside = DamageFalloff::Constant.factor(15.0, radius);
+
+        // Assert
+        assert_eq!(center, 1.0);
+        assert_eq!(linear, 0.5);
+        assert_eq!(quadratic, 0.25);
+        assert_eq!(constant, 1.0);
+        assert_eq!(outside, 0.0);
+    }
+
+    #[test]
+    fn friendly_fire_policy_only_affects_same_faction() {
+        // Arrange
+        let player = Faction::new(PLAYER_FACTION);
+        let enemy = Faction::new(ENEMY_FACTION);
+
+        // Act
+        let hostile = FriendlyFirePolicy::Deny.damage(10.0, Some(&player), Some(&enemy));
+        let neutral = FriendlyFirePolicy::Deny.damage(10.0, None, Some(&enemy));
+        let denied = FriendlyFirePolicy::Deny.damage(10.0, Some(&enemy), Some(&enemy));
+        let scaled = FriendlyFirePolicy::Scaled(0.5).damage(10.0, Some(&enemy), Some(&enemy));
+        let allowed = FriendlyFirePolicy::Allow.damage(10.0, Some(&enemy), Some(&enemy));
+
+        // Assert
+        assert_eq!(hostile, 10.0);
+        assert_eq!(neutral, 10.0);
+        assert_eq!(denied, 0.0);
+        assert_eq!(scaled, 5.0);
+        assert_eq!(allowed, 10.0);
+    }
+
+    #[test]
+    fn detaches_sections_damaged_until_destroyed() {
+        // Arrange
+        let mut app = crate::plugin::test_app();
+        let section = |position: Vec3| {
+            (
+                base_section(BaseSectionConfig::default()),
+                Health::new(100.0),
+                Transform::from_translation(position),
+            )
+        };
+        let spaceship = app
+            .world_mut()
+            .spawn((
+                SpaceshipRootMarker,
+                RigidBody::Dynamic,
+                Transform::default(),
+            ))
+            .id();
+        let controller = app
+            .world_mut()
+            .spawn((
+                section(Vec3::ZERO),
+                ControllerSectionMarker,
+                ChildOf(spaceship),
+            ))
+            .id();
+        let middle = app
+            .world_mut()
+            .spawn((section(Vec3::NEG_Z), ChildOf(spaceship)))
+            .id();
+        let front = app
+            .world_mut()
+            .spawn((section(Vec3::new(0.0, 0.0, -2.0)), ChildOf(spaceship)))
+            .id();
+        app.update();
+
+        // Act
+        for _ in 0..2 {
+            app.world_mut().trigger(HealthApplyDamage {
+                target: middle,
+                source: None,
+                amount: 50.0,
+                kind: DamageKind::Scripted,
+            });
+        }
+        app.update();
+
+        // Assert
+        let world = app.world();
+        assert!(world.get::<DestroyedMarker>(middle).is_some());
+        assert!(world.get::<ChildOf>(middle).is_none());
+        assert!(world.get::<SpaceshipWreckMarker>(middle).is_some());
+        assert_eq!(
+            world.get::<ChildOf>(controller).unwrap().parent(),
+            spaceship
+        );
+        let wreck = world.get::<ChildOf>(front).unwrap().parent();
+        assert_ne!(wreck, spaceship);
+        assert!(world.get::<SpaceshipWreckMarker>(wreck).is_some());
+    }
+}
@@ -0,0 +1,27 @@
+//! Factions decide which entities are hostile to each other. Entities without a faction are neutral
+//! and never targeted.
+
+use bevy::prelude::*;
+
+pub mod prelude {
+    pub use super::{Faction, ENEMY_FACTION, PLAYER_FACTION};
+}
+
+/// The default faction of the player's spaceship.
+pub const PLAYER_FACTION: &str = "player";
+/// The default faction of the ai spaceships.
+pub const ENEMY_FACTION: &str = "enemy";
+
+/// The faction of an entity. Entities of different factions are hostile.
+#[derive(Component, Clone, Debug, PartialEq, Eq, Hash, Deref, DerefMut, Reflect)]
+pub struct Faction(pub String);
+
+impl Faction {
+    pub fn new<S: Into<String>>(s: S) -> Self {
+        Faction(s.into())
+    }
+
+    pub fn is_hostile(&self, other: &Faction) -> bool {
+        self != other
+    }
+}
@@ -48,7 +48,7 @@ impl Plugin for HealthHudPlugin {
 
 fn update_text_hud(
     mut q_hud: Query<(&mut Text, &HealthHudTargetEntity), With<HealthHudMarker>>,
-    q_target: Query<&Health>,
+    q_target: Query<(&Health, Option<&SpaceshipShield>)>,
 ) {
     for (mut hud_input, target) in &mut q_hud {
         let Some(target) = **target else {
@@ -56,12 +56,19 @@ fn update_text_hud(
             continue;
         };
 
-        let Ok(health) = q_target.get(target) else {
+        let Ok((health, shield)) = q_target.get(target) else {
             **hud_input = "Health: 0%".to_string();
             continue;
         };
 
         let health_percent = (health.current / health.max * 100.0).round();
-        **hud_input = format!("Health: {}%", health_percent);
+        **hud_input = match shield.filter(|shield| shield.capacity > 0.0) {
+            Some(shield) => format!(
+                "Health: {}% | Shield: {}%",
+                health_percent,
+                (shield.fraction() * 100.0).round()
+            ),
+            None => format!("Health: {}%", health_percent),
+        };
     }
 }
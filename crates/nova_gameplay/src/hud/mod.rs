@@ -4,12 +4,13 @@ use crate::prelude::*;
 
 pub mod health;
 pub mod objectives;
+pub mod power;
 pub mod velocity;
 
 pub mod prelude {
     pub use super::{
-        health::prelude::*, objectives::prelude::*, velocity::prelude::*, NovaHudPlugin,
-        NovaHudSystems,
+        health::prelude::*, objectives::prelude::*, power::prelude::*, velocity::prelude::*,
+        NovaHudPlugin, NovaHudSystems,
     };
 }
 
@@ -26,12 +27,15 @@ impl Plugin for NovaHudPlugin {
         app.add_plugins(velocity::VelocityHudPlugin);
         app.add_plugins(health::HealthHudPlugin);
         app.add_plugins(objectives::ObjectivesHudPlugin);
+        app.add_plugins(power::PowerHudPlugin);
 
         // Setup and remove HUDs when player spaceship is added/removed
         app.add_observer(setup_hud_velocity);
         app.add_observer(remove_hud_velocity);
         app.add_observer(setup_hud_health);
         app.add_observer(remove_hud_health);
+        app.add_observer(setup_hud_power);
+        app.add_observer(remove_hud_power);
         app.add_observer(setup_hud_objectives);
         app.add_observer(remove_hud_objectives);
     }
@@ -112,6 +116,44 @@ fn remove_hud_health(
     }
 }
 
+fn setup_hud_power(
+    add: On<Add, PlayerSpaceshipMarker>,
+    mut commands: Commands,
+    q_spaceship: Query<Entity, (With<SpaceshipRootMarker>, With<PlayerSpaceshipMarker>)>,
+) {
+    let entity = add.entity;
+    debug!("setup_hud_power: entity {:?}", entity);
+
+    let Ok(spaceship) = q_spaceship.get(entity) else {
+        error!(
+            "setup_hud_power: entity {:?} not found in q_spaceship",
+            entity
+        );
+        return;
+    };
+
+    commands.spawn((power_hud(PowerHudConfig {
+        target: Some(spaceship),
+    }),));
+}
+
+fn remove_hud_power(
+    remove: On<Remove, PlayerSpaceshipMarker>,
+    mut commands: Commands,
+    q_hud: Query<(Entity, &PowerHudTargetEntity), With<PowerHudMarker>>,
+) {
+    let entity = remove.entity;
+    debug!("remove_hud_power: entity {:?}", entity);
+
+    for (hud_entity, target) in &q_hud {
+        if let Some(target_entity) = **target {
+            if target_entity == entity {
+                commands.entity(hud_entity).despawn();
+            }
+        }
+    }
+}
+
 fn setup_hud_objectives(
     add: On<Add, PlayerSpaceshipMarker>,
     mut commands: Commands,
@@ -7,7 +7,7 @@ pub mod prelude {
     };
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub struct ObjectiveActionConfig {
     pub id: String,
     pub message: String,
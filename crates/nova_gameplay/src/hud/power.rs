@@ -0,0 +1,74 @@
+use bevy::prelude::*;
+
+use crate::prelude::*;
+
+pub mod prelude {
+    pub use super::{
+        power_hud, PowerHudConfig, PowerHudMarker, PowerHudPlugin, PowerHudTargetEntity,
+    };
+}
+
+#[derive(Component, Debug, Clone, Reflect)]
+pub struct PowerHudMarker;
+
+#[derive(Clone, Debug, Default)]
+pub struct PowerHudConfig {
+    pub target: Option<Entity>,
+}
+
+pub fn power_hud(config: PowerHudConfig) -> impl Bundle {
+    debug!("power_hud: config {:?}", config);
+
+    (
+        Name::new("PowerHUD"),
+        PowerHudMarker,
+        PowerHudTargetEntity(config.target),
+        Text::new("Power: 100%"),
+        TextShadow::default(),
+        TextLayout::new_with_justify(Justify::Center),
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: px(30),
+            right: px(5),
+            ..default()
+        },
+    )
+}
+
+#[derive(Component, Debug, Clone, Deref, DerefMut, Reflect)]
+pub struct PowerHudTargetEntity(Option<Entity>);
+
+#[derive(Default)]
+pub struct PowerHudPlugin;
+
+impl Plugin for PowerHudPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, update_text_hud.in_set(super::NovaHudSystems));
+    }
+}
+
+fn update_text_hud(
+    mut q_hud: Query<(&mut Text, &PowerHudTargetEntity), With<PowerHudMarker>>,
+    q_target: Query<&SpaceshipPower>,
+) {
+    for (mut hud_input, target) in &mut q_hud {
+        let Some(target) = **target else {
+            **hud_input = "Power: 0%".to_string();
+            continue;
+        };
+
+        let Ok(power) = q_target.get(target) else {
+            **hud_input = "Power: -".to_string();
+            continue;
+        };
+
+        // Without batteries there is no charge worth showing, only the generation
+        if power.capacity <= 0.0 {
+            **hud_input = format!("Power: — (+{:.1}/s)", power.generation);
+            continue;
+        }
+
+        let power_percent = (power.fraction() * 100.0).round();
+        **hud_input = format!("Power: {}% (+{:.1}/s)", power_percent, power.generation);
+    }
+}
@@ -1,12 +1,27 @@
 use avian3d::prelude::*;
 use bevy::prelude::*;
+use nova_events::prelude::*;
 
 use crate::prelude::*;
 
 pub mod prelude {
-    pub use super::{AISpaceshipMarker, SpaceshipAIInputPlugin};
+    pub use super::{
+        AIAvoidance, AIBehaviour, AIObstacleMarker, AIPatrolState, AISpaceshipMarker,
+        AISpaceshipSteering, AITarget, SpaceshipAIInputPlugin,
+    };
 }
 
+/// The minimum speed of a pursuing ship, so that it never stops chasing its target.
+const PURSUE_MIN_SPEED: f32 = 2.0;
+/// How much the desired speed grows with the distance to the destination.
+const ARRIVE_SPEED_FACTOR: f32 = 0.2;
+/// The difference between the desired and actual velocity that the ship tolerates.
+const VELOCITY_TOLERANCE: f32 = 1.0;
+/// The alignment needed before thrusting or shooting.
+const ALIGNMENT_THRESHOLD: f32 = 0.95;
+/// A new target must be this much closer than the current one to switch to it.
+const RETARGET_DISTANCE_RATIO: f32 = 0.75;
+
 pub struct SpaceshipAIInputPlugin;
 
 impl Plugin for SpaceshipAIInputPlugin {
@@ -16,11 +31,16 @@ impl Plugin for SpaceshipAIInputPlugin {
         app.add_systems(
             Update,
             (
-                update_controller_target_rotation_torque,
-                on_thruster_input,
-                update_turret_target_input,
-                on_projectile_input,
+                update_ai_targets,
+                update_ai_steering,
+                (
+                    update_controller_target_rotation_torque,
+                    on_thruster_input,
+                    (update_turret_target_input, on_projectile_input).chain(),
+                    update_missile_launcher_input,
+                ),
             )
+                .chain()
                 .in_set(super::SpaceshipInputSystems),
         );
     }
@@ -30,51 +50,467 @@ impl Plugin for SpaceshipAIInputPlugin {
 ///
 /// This should be added to the root entity of the ai's spaceship.
 #[derive(Component, Debug, Clone, Reflect)]
-#[require(SpaceshipRootMarker)]
+#[require(
+    SpaceshipRootMarker,
+    Faction = Faction::new(ENEMY_FACTION),
+    AIBehaviour,
+    AITarget,
+    AISpaceshipSteering,
+    AIPatrolState,
+    AIAvoidance
+)]
 pub struct AISpaceshipMarker;
 
-fn update_controller_target_rotation_torque(
-    mut q_controller: Query<
-        (&mut ControllerSectionRotationInput, &ChildOf),
-        With<ControllerSectionMarker>,
+/// The hostile entity that an ai spaceship is fighting. The target is kept until it is destroyed or
+/// out of range, but the spaceship switches to a much closer hostile when it looks again.
+#[derive(Component, Clone, Debug, Reflect)]
+pub struct AITarget {
+    /// The current target, if any hostile is in range.
+    pub entity: Option<Entity>,
+    /// Hostiles further away than this are ignored.
+    pub detection_range: f32,
+    /// How often to look for a closer target.
+    pub retarget: Timer,
+}
+
+impl Default for AITarget {
+    fn default() -> Self {
+        Self {
+            entity: None,
+            detection_range: 1000.0,
+            retarget: Timer::from_seconds(1.0, TimerMode::Repeating),
+        }
+    }
+}
+
+/// How an ai spaceship flies and when it shoots. Every behaviour fights the entity in `AITarget`.
+#[derive(Component, Clone, Debug, Reflect, serde::Serialize, serde::Deserialize)]
+pub enum AIBehaviour {
+    /// Chase the target and shoot it when in range.
+    Pursue { max_speed: f32, fire_range: f32 },
+    /// Circle around the target at the given radius while shooting it.
+    Orbit {
+        radius: f32,
+        speed: f32,
+        fire_range: f32,
+    },
+    /// Keep the given distance to the target and shoot it from afar.
+    Snipe {
+        distance: f32,
+        max_speed: f32,
+        fire_range: f32,
+    },
+    /// Run away from the target until it is further than the safe distance. Never shoots.
+    Flee { safe_distance: f32, max_speed: f32 },
+    /// Fly through the waypoints in a loop, shooting the target when in range.
+    Patrol {
+        waypoints: Vec<Vec3>,
+        max_speed: f32,
+        arrive_distance: f32,
+        fire_range: f32,
+    },
+    /// Stay within the radius of the entity with the given `EntityId` and attack the target once
+    /// it comes closer than the engage range to the guarded entity.
+    Guard {
+        target_id: String,
+        radius: f32,
+        engage_range: f32,
+        max_speed: f32,
+    },
+}
+
+impl Default for AIBehaviour {
+    fn default() -> Self {
+        AIBehaviour::Pursue {
+            max_speed: 20.0,
+            fire_range: 1000.0,
+        }
+    }
+}
+
+/// The output of the ai behaviour, used to drive the sections of the spaceship.
+#[derive(Component, Clone, Debug, Default, Reflect)]
+pub struct AISpaceshipSteering {
+    /// The direction the spaceship should face.
+    pub direction: Vec3,
+    /// The thrust level of the thrusters, between 0 and 1.
+    pub thrust: f32,
+    /// The point the turrets should aim and shoot at.
+    pub aim: Option<Vec3>,
+}
+
+/// How far ahead an ai spaceship looks for obstacles on its way.
+#[derive(Component, Clone, Debug, Reflect)]
+pub struct AIAvoidance {
+    /// The radius of the sphere that is cast along the path, about the size of the spaceship.
+    pub radius: f32,
+    /// How many seconds ahead, at the current speed, to look for obstacles.
+    pub look_ahead_time: f32,
+    /// The minimum distance to look for obstacles, used when flying slowly.
+    pub min_look_ahead: f32,
+}
+
+impl Default for AIAvoidance {
+    fn default() -> Self {
+        Self {
+            radius: 3.0,
+            look_ahead_time: 3.0,
+            min_look_ahead: 20.0,
+        }
+    }
+}
+
+/// Marker component for the bodies that ai spaceships steer around, like asteroids. Spaceships are
+/// always avoided.
+#[derive(Component, Clone, Debug, Default, Reflect)]
+pub struct AIObstacleMarker;
+
+/// The index of the next waypoint of a patrolling spaceship.
+#[derive(Component, Clone, Debug, Default, Reflect)]
+pub struct AIPatrolState {
+    pub waypoint: usize,
+}
+
+/// A velocity toward the offset that slows down when getting close to it.
+fn arrive(offset: Vec3, min_speed: f32, max_speed: f32) -> Vec3 {
+    // A negative or NaN speed from a bad config must not panic in `clamp`
+    let max_speed = max_speed.max(0.0);
+    let speed = (offset.length() * ARRIVE_SPEED_FACTOR).clamp(min_speed.min(max_speed), max_speed);
+    offset.normalize_or_zero() * speed
+}
+
+/// The point to aim at, if it is within range.
+fn in_range(position: Vec3, target: Vec3, range: f32) -> Option<Vec3> {
+    (position.distance(target) <= range).then_some(target)
+}
+
+/// Blend the desired velocity with a direction away from an obstacle that is in the way. The
+/// closer the obstacle, the more the spaceship turns away from it.
+fn avoid_obstacle(
+    desired_velocity: Vec3,
+    travel_direction: Dir3,
+    hit_distance: f32,
+    hit_normal: Vec3,
+    look_ahead: f32,
+) -> Vec3 {
+    // Turn sideways, away from the surface that was hit
+    let away = hit_normal
+        .reject_from_normalized(*travel_direction)
+        .try_normalize()
+        .unwrap_or_else(|| travel_direction.any_orthonormal_vector());
+
+    let urgency = (1.0 - hit_distance / look_ahead).clamp(0.0, 1.0);
+    let speed = desired_velocity.length().max(PURSUE_MIN_SPEED);
+    let direction = desired_velocity
+        .normalize_or(*travel_direction)
+        .lerp(away, urgency)
+        .normalize_or(away);
+
+    direction * speed
+}
+
+/// Compute the velocity that the spaceship should have and the point to shoot at, given its
+/// behaviour, its position, the position of the target and of the guarded entity.
+fn behaviour_steering(
+    behaviour: &AIBehaviour,
+    position: Vec3,
+    target: Option<Vec3>,
+    guarded: Option<Vec3>,
+    patrol: &mut AIPatrolState,
+) -> (Vec3, Option<Vec3>) {
+    match behaviour {
+        AIBehaviour::Pursue {
+            max_speed,
+            fire_range,
+        } => match target {
+            Some(target) => (
+                arrive(target - position, PURSUE_MIN_SPEED, *max_speed),
+                in_range(position, target, *fire_range),
+            ),
+            None => (Vec3::ZERO, None),
+        },
+        AIBehaviour::Orbit {
+            radius,
+            speed,
+            fire_range,
+        } => match target {
+            Some(target) => {
+                let to_target = target - position;
+                let radial = to_target.normalize_or_zero();
+                let tangent = radial
+                    .cross(Vec3::Y)
+                    .try_normalize()
+                    .unwrap_or_else(|| radial.any_orthonormal_vector());
+                let speed = speed.max(0.0);
+                let correction =
+                    ((to_target.length() - radius) * ARRIVE_SPEED_FACTOR).clamp(-speed, speed);
+
+                (
+                    tangent * speed + radial * correction,
+                    in_range(position, target, *fire_range),
+                )
+            }
+            None => (Vec3::ZERO, None),
+        },
+        AIBehaviour::Snipe {
+            distance,
+            max_speed,
+            fire_range,
+        } => match target {
+            Some(target) => {
+                let to_target = target - position;
+                let max_speed = max_speed.max(0.0);
+                let speed = ((to_target.length() - distance) * ARRIVE_SPEED_FACTOR)
+                    .clamp(-max_speed, max_speed);
+
+                (
+                    to_target.normalize_or_zero() * speed,
+                    in_range(position, target, *fire_range),
+                )
+            }
+            None => (Vec3::ZERO, None),
+        },
+        AIBehaviour::Flee {
+            safe_distance,
+            max_speed,
+        } => match target {
+            Some(target) if position.distance(target) < *safe_distance => {
+                ((position - target).normalize_or_zero() * *max_speed, None)
+            }
+            _ => (Vec3::ZERO, None),
+        },
+        AIBehaviour::Patrol {
+            waypoints,
+            max_speed,
+            arrive_distance,
+            fire_range,
+        } => {
+            if waypoints.is_empty() {
+                return (Vec3::ZERO, None);
+            }
+
+            patrol.waypoint %= waypoints.len();
+            if position.distance(waypoints[patrol.waypoint]) <= *arrive_distance {
+                patrol.waypoint = (patrol.waypoint + 1) % waypoints.len();
+            }
+
+            (
+                arrive(waypoints[patrol.waypoint] - position, 0.0, *max_speed),
+                target.and_then(|target| in_range(position, target, *fire_range)),
+            )
+        }
+        AIBehaviour::Guard {
+            radius,
+            engage_range,
+            max_speed,
+            ..
+        } => {
+            let Some(guarded) = guarded else {
+                return (Vec3::ZERO, None);
+            };
+
+            let intruder = target.filter(|target| guarded.distance(*target) <= *engage_range);
+            match intruder {
+                Some(target) => (
+                    arrive(target - position, PURSUE_MIN_SPEED, *max_speed),
+                    Some(target),
+                ),
+                None if position.distance(guarded) > *radius => {
+                    (arrive(guarded - position, 0.0, *max_speed), None)
+                }
+                None => (Vec3::ZERO, None),
+            }
+        }
+    }
+}
+
+/// Pick the target among the hostile candidates. The current target is kept if it is still valid,
+/// unless `retarget` is set and another hostile is much closer.
+fn select_target<'a>(
+    position: Vec3,
+    faction: &Faction,
+    current: Option<Entity>,
+    detection_range: f32,
+    retarget: bool,
+    candidates: impl IntoIterator<Item = (Entity, Vec3, &'a Faction)>,
+) -> Option<Entity> {
+    let hostiles = candidates
+        .into_iter()
+        .filter(|(_, _, other)| faction.is_hostile(other))
+        .map(|(entity, other, _)| (entity, position.distance(other)))
+        .filter(|(_, distance)| *distance <= detection_range)
+        .collect::<Vec<_>>();
+
+    let current =
+        current.and_then(|current| hostiles.iter().find(|(entity, _)| *entity == current));
+    let nearest = hostiles.iter().min_by(|(_, a), (_, b)| a.total_cmp(b));
+
+    match (current, nearest) {
+        (Some(current), Some(nearest))
+            if retarget && nearest.1 < current.1 * RETARGET_DISTANCE_RATIO =>
+        {
+            Some(nearest.0)
+        }
+        (Some(current), _) => Some(current.0),
+        (None, nearest) => nearest.map(|(entity, _)| *entity),
+    }
+}
+
+fn update_ai_targets(
+    time: Res<Time>,
+    mut q_spaceship: Query<
+        (Entity, &GlobalTransform, &Faction, &mut AITarget),
+        (With<SpaceshipRootMarker>, With<AISpaceshipMarker>),
     >,
-    q_spaceship: Query<
-        (Entity, &Transform, &LinearVelocity),
+    q_candidate: Query<(Entity, &GlobalTransform, &Faction), Without<DestroyedMarker>>,
+) {
+    for (entity, transform, faction, mut target) in &mut q_spaceship {
+        let retarget = target.retarget.tick(time.delta()).just_finished();
+
+        let candidates = q_candidate
+            .iter()
+            .filter(|(other, _, _)| *other != entity)
+            .map(|(other, transform, faction)| (other, transform.translation(), faction));
+        let selected = select_target(
+            transform.translation(),
+            faction,
+            target.entity,
+            target.detection_range,
+            retarget,
+            candidates,
+        );
+
+        if selected != target.entity {
+            trace!(
+                "update_ai_targets: entity {:?} switched target from {:?} to {:?}",
+                entity,
+                target.entity,
+                selected
+            );
+            target.entity = selected;
+        }
+    }
+}
+
+fn update_ai_steering(
+    mut q_spaceship: Query<
+        (
+            Entity,
+            &Transform,
+            &LinearVelocity,
+            &AIBehaviour,
+            &AITarget,
+            &mut AISpaceshipSteering,
+            &mut AIPatrolState,
+            &AIAvoidance,
+        ),
         (With<SpaceshipRootMarker>, With<AISpaceshipMarker>),
     >,
-    player: Single<&Transform, (With<SpaceshipRootMarker>, With<PlayerSpaceshipMarker>)>,
+    q_guarded: Query<(&EntityId, &Transform)>,
+    q_target: Query<&GlobalTransform>,
+    q_collider: Query<&ColliderOf>,
+    q_obstacle: Query<(), Or<(With<AIObstacleMarker>, With<SpaceshipRootMarker>)>>,
+    spatial_query: SpatialQuery,
 ) {
-    let player_transform = player.into_inner();
-
-    for (entity, transform, velocity) in &q_spaceship {
-        let to_player = player_transform.translation - transform.translation;
-        let distance = to_player.length();
-        let speed = velocity.length();
-
-        // Determine whether to chase or brake
-        let target_speed = (distance * 0.2).clamp(2.0, 20.0);
-        let too_fast = speed > target_speed + 1.0;
-
-        // Desired direction:
-        // - toward player if slow
-        // - opposite of velocity if too fast
-        let desired_direction = if too_fast {
-            // Brake
-            -velocity.normalize_or_zero()
+    for (entity, transform, velocity, behaviour, ai_target, mut steering, mut patrol, avoidance) in
+        &mut q_spaceship
+    {
+        let target = ai_target
+            .entity
+            .and_then(|entity| q_target.get(entity).ok())
+            .map(|transform| transform.translation());
+
+        let guarded = match behaviour {
+            AIBehaviour::Guard { target_id, .. } => q_guarded
+                .iter()
+                .find(|(id, _)| id.0 == *target_id)
+                .map(|(_, transform)| transform.translation),
+            _ => None,
+        };
+
+        let (desired_velocity, aim) = behaviour_steering(
+            behaviour,
+            transform.translation,
+            target,
+            guarded,
+            &mut patrol,
+        );
+
+        // Look for obstacles along the current path, or along the desired one when slow
+        let travel_direction = if velocity.length() > VELOCITY_TOLERANCE {
+            Dir3::new(**velocity).ok()
         } else {
-            // Chase
-            to_player.normalize()
+            Dir3::new(desired_velocity).ok()
+        };
+        let desired_velocity = match travel_direction {
+            Some(travel_direction) => {
+                let look_ahead =
+                    (velocity.length() * avoidance.look_ahead_time).max(avoidance.min_look_ahead);
+                let hit = spatial_query.cast_shape_predicate(
+                    &Collider::sphere(avoidance.radius),
+                    transform.translation,
+                    Quat::IDENTITY,
+                    travel_direction,
+                    &ShapeCastConfig::from_max_distance(look_ahead),
+                    &SpatialQueryFilter::default(),
+                    &|collider| {
+                        let body = q_collider
+                            .get(collider)
+                            .map(|collider_of| collider_of.body)
+                            .unwrap_or(collider);
+                        body != entity && q_obstacle.contains(body)
+                    },
+                );
+
+                match hit {
+                    Some(hit) => avoid_obstacle(
+                        desired_velocity,
+                        travel_direction,
+                        hit.distance,
+                        hit.normal1,
+                        look_ahead,
+                    ),
+                    None => desired_velocity,
+                }
+            }
+            None => desired_velocity,
         };
 
-        // If velocity is zero (e.g., stationary), fall back to facing player
-        let desired_direction = if desired_direction.length_squared() == 0.0 {
-            to_player.normalize_or_zero()
+        // Accelerate toward the desired velocity, which also brakes when going too fast
+        let correction = desired_velocity - **velocity;
+        let forward = transform.forward();
+        let direction = if correction.length() > VELOCITY_TOLERANCE {
+            correction.normalize()
+        } else if let Some(aim) = aim {
+            // Nothing to correct, face the target to help the turrets
+            (aim - transform.translation).normalize_or(*forward)
         } else {
-            desired_direction
+            *forward
         };
 
+        // Apply thrust only if pointing in roughly the correct direction
+        let should_thrust = correction.length() > VELOCITY_TOLERANCE
+            && forward.dot(direction) > ALIGNMENT_THRESHOLD;
+
+        steering.direction = direction;
+        steering.thrust = if should_thrust { 1.0 } else { 0.0 };
+        steering.aim = aim;
+    }
+}
+
+fn update_controller_target_rotation_torque(
+    mut q_controller: Query<
+        (&mut ControllerSectionRotationInput, &ChildOf),
+        With<ControllerSectionMarker>,
+    >,
+    q_spaceship: Query<
+        (Entity, &Transform, &AISpaceshipSteering),
+        (With<SpaceshipRootMarker>, With<AISpaceshipMarker>),
+    >,
+) {
+    for (entity, transform, steering) in &q_spaceship {
         let forward = transform.forward().into();
-        let target_rotation = Quat::from_rotation_arc(forward, desired_direction);
+        let target_rotation = Quat::from_rotation_arc(forward, steering.direction);
 
         for (mut controller, _) in q_controller
             .iter_mut()
@@ -86,88 +522,111 @@ fn update_controller_target_rotation_torque(
 }
 
 fn on_thruster_input(
-    mut q_thruster: Query<
-        (&mut ThrusterSectionInput, &GlobalTransform, &ChildOf),
-        With<ThrusterSectionMarker>,
-    >,
+    mut q_thruster: Query<(&mut ThrusterSectionInput, &ChildOf), With<ThrusterSectionMarker>>,
     q_spaceship: Query<
-        (Entity, &Transform, &LinearVelocity),
+        (Entity, &AISpaceshipSteering),
         (With<SpaceshipRootMarker>, With<AISpaceshipMarker>),
     >,
-    player: Single<&Transform, (With<SpaceshipRootMarker>, With<PlayerSpaceshipMarker>)>,
 ) {
-    let player_transform = player.into_inner();
-
-    for (entity, transform, velocity) in &q_spaceship {
-        let to_player = player_transform.translation - transform.translation;
-        let distance = to_player.length();
-        let speed = velocity.length();
-
-        let target_speed = (distance * 0.2).clamp(2.0, 20.0);
-        let too_fast = speed > target_speed + 1.0;
-
-        let desired_direction = if too_fast {
-            -velocity.normalize_or_zero()
-        } else {
-            to_player.normalize()
-        };
-
-        // Determine how well we’re aligned before applying thrust
-        let forward = transform.forward();
-        let alignment = forward.dot(desired_direction);
-
-        // Apply thrust only if pointing in roughly the correct direction
-        // and not already moving too fast
-        let should_thrust = alignment > 0.95;
-
-        let thrust_level = if should_thrust { 1.0 } else { 0.0 };
-
-        for (mut thruster_input, _, _) in q_thruster
+    for (entity, steering) in &q_spaceship {
+        for (mut thruster_input, _) in q_thruster
             .iter_mut()
-            .filter(|(_, _, ChildOf(parent))| *parent == entity)
+            .filter(|(_, ChildOf(parent))| *parent == entity)
         {
-            **thruster_input = thrust_level;
+            **thruster_input = steering.thrust;
         }
     }
 }
 
+/// Aim the turrets and beams at the target. The turrets lead it so that the projectiles hit it
+/// while it moves.
 fn update_turret_target_input(
-    mut q_turret: Query<(&mut TurretSectionTargetInput, &ChildOf), With<TurretSectionMarker>>,
-    q_spaceship: Query<Entity, (With<SpaceshipRootMarker>, With<AISpaceshipMarker>)>,
-    player: Single<&Transform, (With<SpaceshipRootMarker>, With<PlayerSpaceshipMarker>)>,
+    mut q_turret: Query<
+        (
+            &mut TurretSectionTargetInput,
+            &TurretSectionMuzzleEntity,
+            Option<&TurretSectionMuzzleSpeed>,
+            &ChildOf,
+        ),
+        Or<(With<TurretSectionMarker>, With<BeamSectionMarker>)>,
+    >,
+    q_muzzle: Query<&GlobalTransform, With<TurretSectionBarrelMuzzleMarker>>,
+    q_spaceship: Query<
+        (Entity, &LinearVelocity, &AISpaceshipSteering, &AITarget),
+        (With<SpaceshipRootMarker>, With<AISpaceshipMarker>),
+    >,
+    q_velocity: Query<&LinearVelocity>,
 ) {
-    let transform = player.into_inner();
+    for (entity, velocity, steering, target) in &q_spaceship {
+        let target_velocity = target
+            .entity
+            .and_then(|target| q_velocity.get(target).ok())
+            .map(|velocity| **velocity)
+            .unwrap_or_default();
 
-    for entity in &q_spaceship {
-        for (mut turret_input, _) in q_turret
+        for (mut turret_input, muzzle, muzzle_speed, _) in q_turret
             .iter_mut()
-            .filter(|(_, ChildOf(c_parent))| *c_parent == entity)
+            .filter(|(_, _, _, ChildOf(c_parent))| *c_parent == entity)
         {
-            **turret_input = Some(transform.translation);
+            let Some(aim) = steering.aim else {
+                **turret_input = None;
+                continue;
+            };
+
+            let Ok(muzzle_transform) = q_muzzle.get(**muzzle) else {
+                error!(
+                    "update_turret_target_input: muzzle entity {:?} not found in q_muzzle",
+                    **muzzle
+                );
+                continue;
+            };
+
+            let lead = muzzle_speed.and_then(|muzzle_speed| {
+                intercept_point(
+                    muzzle_transform.translation(),
+                    **velocity,
+                    aim,
+                    target_velocity,
+                    **muzzle_speed,
+                )
+            });
+            **turret_input = Some(lead.unwrap_or(aim));
         }
     }
 }
 
+/// Shoot when the barrel points at the aim point of the turret and the turret is not about to
+/// overheat.
 fn on_projectile_input(
     mut q_turret: Query<
         (
             &TurretSectionMuzzleEntity,
+            &TurretSectionTargetInput,
             &mut TurretSectionInput,
             &ChildOf,
+            Option<&TurretSectionHeat>,
         ),
-        With<TurretSectionMarker>,
+        Or<(With<TurretSectionMarker>, With<BeamSectionMarker>)>,
     >,
     q_muzzle: Query<&GlobalTransform, With<TurretSectionBarrelMuzzleMarker>>,
     q_spaceship: Query<Entity, (With<SpaceshipRootMarker>, With<AISpaceshipMarker>)>,
-    player: Single<&Transform, (With<SpaceshipRootMarker>, With<PlayerSpaceshipMarker>)>,
 ) {
-    let player_transform = player.into_inner();
-
     for entity in &q_spaceship {
-        for (muzzle, mut input, _) in q_turret
+        for (muzzle, target_input, mut input, _, heat) in q_turret
             .iter_mut()
-            .filter(|(_, _, ChildOf(c_parent))| *c_parent == entity)
+            .filter(|(_, _, _, ChildOf(c_parent), _)| *c_parent == entity)
         {
+            let Some(aim) = **target_input else {
+                **input = false;
+                continue;
+            };
+
+            // Hold fire instead of overheating, so the turret is never locked out
+            if heat.is_some_and(|heat| heat.overheated || heat.heat + heat.per_shot >= 1.0) {
+                **input = false;
+                continue;
+            }
+
             let Ok(muzzle_transform) = q_muzzle.get(**muzzle) else {
                 error!(
                     "on_projectile_input: muzzle entity {:?} not found in q_muzzle",
@@ -176,12 +635,301 @@ fn on_projectile_input(
                 continue;
             };
 
-            let direction_to_player =
-                (player_transform.translation - muzzle_transform.translation()).normalize();
+            let direction_to_target = (aim - muzzle_transform.translation()).normalize_or_zero();
             let forward = muzzle_transform.forward();
 
-            let alignment = forward.dot(direction_to_player);
-            **input = alignment > 0.95;
+            let alignment = forward.dot(direction_to_target);
+            **input = alignment > ALIGNMENT_THRESHOLD;
+        }
+    }
+}
+
+/// Lock the missile launchers on to the target, and fire as soon as the lock is complete.
+fn update_missile_launcher_input(
+    mut q_launcher: Query<
+        (
+            &mut MissileLauncherSectionTargetInput,
+            &mut MissileLauncherSectionInput,
+            &MissileLauncherSectionLock,
+            &ChildOf,
+        ),
+        With<MissileLauncherSectionMarker>,
+    >,
+    q_spaceship: Query<(Entity, &AITarget), (With<SpaceshipRootMarker>, With<AISpaceshipMarker>)>,
+) {
+    for (entity, target) in &q_spaceship {
+        for (mut target_input, mut input, lock, _) in q_launcher
+            .iter_mut()
+            .filter(|(_, _, _, ChildOf(l_parent))| *l_parent == entity)
+        {
+            **target_input = target.entity;
+            **input = lock.is_locked();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selects_nearest_hostile_in_range() {
+        // Arrange
+        let enemy = Faction::new(ENEMY_FACTION);
+        let player = Faction::new(PLAYER_FACTION);
+        let (near_friend, far_hostile, near_hostile, out_of_range) = (
+            Entity::from_raw_u32(1).unwrap(),
+            Entity::from_raw_u32(2).unwrap(),
+            Entity::from_raw_u32(3).unwrap(),
+            Entity::from_raw_u32(4).unwrap(),
+        );
+        let candidates = [
+            (near_friend, Vec3::X, &enemy),
+            (far_hostile, Vec3::X * 50.0, &player),
+            (near_hostile, Vec3::X * 20.0, &player),
+            (out_of_range, Vec3::X * 5000.0, &player),
+        ];
+
+        // Act
+        let target = select_target(Vec3::ZERO, &enemy, None, 1000.0, false, candidates);
+
+        // Assert
+        assert_eq!(target, Some(near_hostile));
+    }
+
+    #[test]
+    fn keeps_current_target_until_a_much_closer_one_appears() {
+        // Arrange
+        let enemy = Faction::new(ENEMY_FACTION);
+        let player = Faction::new(PLAYER_FACTION);
+        let (current, slightly_closer, much_closer) = (
+            Entity::from_raw_u32(1).unwrap(),
+            Entity::from_raw_u32(2).unwrap(),
+            Entity::from_raw_u32(3).unwrap(),
+        );
+        let candidates = [
+            (current, Vec3::X * 100.0, &player),
+            (slightly_closer, Vec3::X * 90.0, &player),
+        ];
+        let more_candidates = [
+            (current, Vec3::X * 100.0, &player),
+            (much_closer, Vec3::X * 10.0, &player),
+        ];
+
+        // Act
+        let kept = select_target(Vec3::ZERO, &enemy, Some(current), 1000.0, true, candidates);
+        let not_retargeted = select_target(
+            Vec3::ZERO,
+            &enemy,
+            Some(current),
+            1000.0,
+            false,
+            more_candidates,
+        );
+        let switched = select_target(
+            Vec3::ZERO,
+            &enemy,
+            Some(current),
+            1000.0,
+            true,
+            more_candidates,
+        );
+
+        // Assert
+        assert_eq!(kept, Some(current));
+        assert_eq!(not_retargeted, Some(current));
+        assert_eq!(switched, Some(much_closer));
+    }
+
+    #[test]
+    fn avoids_obstacle_ahead() {
+        // Arrange
+        let desired_velocity = Vec3::new(0.0, 0.0, -10.0);
+        let travel_direction = Dir3::NEG_Z;
+        // The obstacle is ahead and slightly to the left, so its surface faces right
+        let hit_normal = Vec3::new(1.0, 0.0, 1.0).normalize();
+
+        // Act
+        let far = avoid_obstacle(desired_velocity, travel_direction, 90.0, hit_normal, 100.0);
+        let near = avoid_obstacle(desired_velocity, travel_direction, 10.0, hit_normal, 100.0);
+        let touching = avoid_obstacle(desired_velocity, travel_direction, 0.0, hit_normal, 100.0);
+
+        // Assert
+        assert!(far.x > 0.0 && far.x < near.x);
+        assert!(near.z < 0.0);
+        assert!(touching.abs_diff_eq(Vec3::new(10.0, 0.0, 0.0), 1e-4));
+    }
+
+    #[test]
+    fn avoids_obstacle_head_on() {
+        // Arrange
+        let desired_velocity = Vec3::new(0.0, 0.0, -10.0);
+
+        // Act
+        let velocity = avoid_obstacle(desired_velocity, Dir3::NEG_Z, 0.0, Vec3::Z, 100.0);
+
+        // Assert
+        assert!(velocity.z.abs() < 1e-4);
+        assert!((velocity.length() - 10.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn pursue_chases_target_in_range() {
+        // Arrange
+        let behaviour = AIBehaviour::Pursue {
+            max_speed: 10.0,
+            fire_range: 100.0,
+        };
+        let target = Vec3::new(0.0, 0.0, -200.0);
+
+        // Act
+        let (velocity, aim) = behaviour_steering(
+            &behaviour,
+            Vec3::ZERO,
+            Some(target),
+            None,
+            &mut AIPatrolState::default(),
+        );
+
+        // Assert
+        assert_eq!(velocity, Vec3::new(0.0, 0.0, -10.0));
+        assert_eq!(aim, None);
+    }
+
+    #[test]
+    fn snipe_backs_away_when_too_close() {
+        // Arrange
+        let behaviour = AIBehaviour::Snipe {
+            distance: 100.0,
+            max_speed: 10.0,
+            fire_range: 150.0,
+        };
+        let target = Vec3::new(0.0, 0.0, -50.0);
+
+        // Act
+        let (velocity, aim) = behaviour_steering(
+            &behaviour,
+            Vec3::ZERO,
+            Some(target),
+            None,
+            &mut AIPatrolState::default(),
+        );
+
+        // Assert
+        assert!(velocity.z > 0.0);
+        assert_eq!(aim, Some(target));
+    }
+
+    #[test]
+    fn negative_or_nan_speeds_stop_the_ship() {
+        // Arrange
+        let behaviours = [
+            AIBehaviour::Pursue {
+                max_speed: f32::NAN,
+                fire_range: 150.0,
+            },
+            AIBehaviour::Orbit {
+                radius: 50.0,
+                speed: -10.0,
+                fire_range: 150.0,
+            },
+            AIBehaviour::Snipe {
+                distance: 100.0,
+                max_speed: -10.0,
+                fire_range: 150.0,
+            },
+        ];
+        let target = Vec3::new(0.0, 0.0, -50.0);
+
+        for behaviour in behaviours.iter() {
+            // Act
+            let (velocity, _) = behaviour_steering(
+                behaviour,
+                Vec3::ZERO,
+                Some(target),
+                None,
+                &mut AIPatrolState::default(),
+            );
+
+            // Assert
+            assert_eq!(velocity, Vec3::ZERO, "{:?}", behaviour);
         }
     }
+
+    #[test]
+    fn patrol_advances_to_next_waypoint() {
+        // Arrange
+        let behaviour = AIBehaviour::Patrol {
+            waypoints: vec![Vec3::ZERO, Vec3::new(100.0, 0.0, 0.0)],
+            max_speed: 10.0,
+            arrive_distance: 5.0,
+            fire_range: 50.0,
+        };
+        let mut patrol = AIPatrolState::default();
+
+        // Act
+        let (velocity, aim) = behaviour_steering(&behaviour, Vec3::ZERO, None, None, &mut patrol);
+
+        // Assert
+        assert_eq!(patrol.waypoint, 1);
+        assert_eq!(velocity, Vec3::new(10.0, 0.0, 0.0));
+        assert_eq!(aim, None);
+    }
+
+    #[test]
+    fn guard_engages_only_intruders() {
+        // Arrange
+        let behaviour = AIBehaviour::Guard {
+            target_id: "station".to_string(),
+            radius: 20.0,
+            engage_range: 50.0,
+            max_speed: 10.0,
+        };
+        let guarded = Vec3::ZERO;
+        let far = Vec3::new(200.0, 0.0, 0.0);
+        let near = Vec3::new(30.0, 0.0, 0.0);
+
+        // Act
+        let (idle, idle_aim) = behaviour_steering(
+            &behaviour,
+            Vec3::ZERO,
+            Some(far),
+            Some(guarded),
+            &mut AIPatrolState::default(),
+        );
+        let (_, engage_aim) = behaviour_steering(
+            &behaviour,
+            Vec3::ZERO,
+            Some(near),
+            Some(guarded),
+            &mut AIPatrolState::default(),
+        );
+
+        // Assert
+        assert_eq!(idle, Vec3::ZERO);
+        assert_eq!(idle_aim, None);
+        assert_eq!(engage_aim, Some(near));
+    }
+
+    #[test]
+    fn flee_runs_from_close_target() {
+        // Arrange
+        let behaviour = AIBehaviour::Flee {
+            safe_distance: 100.0,
+            max_speed: 10.0,
+        };
+
+        // Act
+        let (velocity, aim) = behaviour_steering(
+            &behaviour,
+            Vec3::ZERO,
+            Some(Vec3::new(10.0, 0.0, 0.0)),
+            None,
+            &mut AIPatrolState::default(),
+        );
+
+        // Assert
+        assert_eq!(velocity, Vec3::new(-10.0, 0.0, 0.0));
+        assert_eq!(aim, None);
+    }
 }
@@ -1,3 +1,4 @@
+use avian3d::prelude::*;
 use bevy::prelude::*;
 use bevy_common_systems::prelude::*;
 use bevy_enhanced_input::prelude::*;
@@ -6,8 +7,8 @@ use crate::prelude::*;
 
 pub mod prelude {
     pub use super::{
-        PlayerSpaceshipMarker, SpaceshipPlayerInputPlugin, SpaceshipThrusterInputBinding,
-        SpaceshipTurretInputBinding,
+        PlayerAimAssist, PlayerSpaceshipMarker, SpaceshipMissileInputBinding,
+        SpaceshipPlayerInputPlugin, SpaceshipThrusterInputBinding, SpaceshipTurretInputBinding,
     };
 }
 
@@ -17,6 +18,8 @@ impl Plugin for SpaceshipPlayerInputPlugin {
     fn build(&self, app: &mut App) {
         debug!("SpaceshipPlayerInputPlugin: build");
 
+        app.init_resource::<PlayerAimAssist>();
+
         app.add_input_context::<ThrusterInputMarker>();
         app.add_observer(on_thruster_input_binding);
         app.add_observer(on_thruster_input);
@@ -27,11 +30,17 @@ impl Plugin for SpaceshipPlayerInputPlugin {
         app.add_observer(on_projectile_input);
         app.add_observer(on_projectile_input_completed);
 
+        app.add_input_context::<MissileInputMarker>();
+        app.add_observer(on_missile_input_binding);
+        app.add_observer(on_missile_input);
+        app.add_observer(on_missile_input_completed);
+
         app.add_systems(
             Update,
             (
                 update_controller_target_rotation_torque,
                 update_turret_target_input,
+                update_missile_target_input,
             )
                 .in_set(super::SpaceshipInputSystems),
         );
@@ -42,9 +51,30 @@ impl Plugin for SpaceshipPlayerInputPlugin {
 ///
 /// This should be added to the root entity of the player's spaceship.
 #[derive(Component, Debug, Clone, Reflect)]
-#[require(SpaceshipRootMarker)]
+#[require(SpaceshipRootMarker, Faction = Faction::new(PLAYER_FACTION))]
 pub struct PlayerSpaceshipMarker;
 
+/// Aim-assist for the turrets of the player's spaceship. When enabled, the turrets lead the hostile
+/// target closest to the crosshair instead of aiming straight ahead.
+#[derive(Resource, Clone, Debug, Reflect)]
+pub struct PlayerAimAssist {
+    pub enabled: bool,
+    /// The maximum angle in radians between the crosshair and a target.
+    pub max_angle: f32,
+    /// The maximum distance to a target.
+    pub range: f32,
+}
+
+impl Default for PlayerAimAssist {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_angle: 10f32.to_radians(),
+            range: 500.0,
+        }
+    }
+}
+
 /// System that takes the point rotation output from the chase camera and applies it to the
 /// controller of the player's spaceship.
 fn update_controller_target_rotation_torque(
@@ -73,7 +103,8 @@ fn update_controller_target_rotation_torque(
 }
 
 /// System that takes the point rotation output from the chase camera and applies it to the
-/// turret target input of the player's spaceship.
+/// turret and beam target input of the player's spaceship. With aim-assist, the turrets lead the
+/// target closest to the crosshair.
 fn update_turret_target_input(
     point_rotation: Single<
         &PointRotationOutput,
@@ -82,24 +113,127 @@ fn update_turret_target_input(
             With<SpaceshipCameraTurretInputMarker>,
         ),
     >,
-    mut q_turret: Query<(&mut TurretSectionTargetInput, &ChildOf), With<TurretSectionMarker>>,
+    mut q_turret: Query<
+        (
+            &mut TurretSectionTargetInput,
+            Option<&TurretSectionMuzzleSpeed>,
+            &ChildOf,
+        ),
+        Or<(With<TurretSectionMarker>, With<BeamSectionMarker>)>,
+    >,
     spaceship: Single<
-        (&Transform, Entity),
+        (&Transform, Entity, &Faction, Option<&LinearVelocity>),
         (With<SpaceshipRootMarker>, With<PlayerSpaceshipMarker>),
     >,
+    q_candidate: Query<
+        (&GlobalTransform, &Faction, Option<&LinearVelocity>),
+        Without<DestroyedMarker>,
+    >,
+    aim_assist: Res<PlayerAimAssist>,
 ) {
     let point_rotation = point_rotation.into_inner();
-    let (transform, spaceship) = spaceship.into_inner();
-
-    for (mut turret, _) in q_turret
+    let (transform, spaceship, faction, velocity) = spaceship.into_inner();
+    let forward = **point_rotation * Vec3::NEG_Z;
+    let position = transform.translation;
+    let velocity = velocity.map(|velocity| **velocity).unwrap_or_default();
+
+    let target = aim_assist
+        .enabled
+        .then(|| {
+            q_candidate
+                .iter()
+                .filter(|(_, other, _)| faction.is_hostile(other))
+                .filter_map(|(target_transform, _, target_velocity)| {
+                    let offset = target_transform.translation() - position;
+                    let distance = offset.length();
+                    if distance > aim_assist.range || distance <= f32::EPSILON {
+                        return None;
+                    }
+
+                    let angle = forward.angle_between(offset);
+                    if angle > aim_assist.max_angle {
+                        return None;
+                    }
+
+                    let target_velocity = target_velocity.map(|v| **v).unwrap_or_default();
+                    Some((angle, target_transform.translation(), target_velocity))
+                })
+                .min_by(|(a, _, _), (b, _, _)| a.total_cmp(b))
+        })
+        .flatten();
+
+    for (mut turret, muzzle_speed, _) in q_turret
         .iter_mut()
-        .filter(|(_, ChildOf(t_parent))| *t_parent == spaceship)
+        .filter(|(_, _, ChildOf(t_parent))| *t_parent == spaceship)
     {
-        let forward = **point_rotation * Vec3::NEG_Z;
-        let position = transform.translation;
+        let aim = target.and_then(|(_, target_position, target_velocity)| {
+            // Beams hit instantly, they aim straight at the target
+            let Some(muzzle_speed) = muzzle_speed else {
+                return Some(target_position);
+            };
+
+            intercept_point(
+                position,
+                velocity,
+                target_position,
+                target_velocity,
+                **muzzle_speed,
+            )
+        });
+
         let distance = 100.0;
+        **turret = Some(aim.unwrap_or(position + forward * distance));
+    }
+}
 
-        **turret = Some(position + forward * distance);
+/// System that locks the missile launchers of the player's spaceship on to the hostile target
+/// closest to the crosshair.
+fn update_missile_target_input(
+    point_rotation: Single<
+        &PointRotationOutput,
+        (
+            With<SpaceshipCameraInputMarker>,
+            With<SpaceshipCameraTurretInputMarker>,
+        ),
+    >,
+    mut q_launcher: Query<
+        (
+            &mut MissileLauncherSectionTargetInput,
+            &MissileLauncherSectionLock,
+            &ChildOf,
+        ),
+        With<MissileLauncherSectionMarker>,
+    >,
+    spaceship: Single<
+        (&Transform, Entity, Option<&Faction>),
+        (With<SpaceshipRootMarker>, With<PlayerSpaceshipMarker>),
+    >,
+    q_candidate: Query<(Entity, &GlobalTransform, &Faction), Without<DestroyedMarker>>,
+) {
+    let point_rotation = point_rotation.into_inner();
+    let (transform, spaceship, faction) = spaceship.into_inner();
+    let forward = **point_rotation * Vec3::NEG_Z;
+    let position = transform.translation;
+
+    for (mut target_input, lock, _) in q_launcher
+        .iter_mut()
+        .filter(|(_, _, ChildOf(l_parent))| *l_parent == spaceship)
+    {
+        let target = q_candidate
+            .iter()
+            // A spaceship without a faction is neutral and can lock on any faction
+            .filter(|(candidate, _, other)| {
+                *candidate != spaceship && faction.is_none_or(|faction| faction.is_hostile(other))
+            })
+            .filter_map(|(candidate, candidate_transform, _)| {
+                let offset = candidate_transform.translation() - position;
+                lock.can_lock(forward, offset)
+                    .then(|| (forward.angle_between(offset), candidate))
+            })
+            .min_by(|(a, _), (b, _)| a.total_cmp(b))
+            .map(|(_, candidate)| candidate);
+
+        **target_input = target;
     }
 }
 
@@ -242,3 +376,69 @@ fn on_projectile_input_completed(
 
     **q_turret = false;
 }
+
+#[derive(Component, Debug, Clone, Deref, DerefMut, Reflect)]
+pub struct SpaceshipMissileInputBinding(pub Vec<Binding>);
+
+#[derive(Component, Debug, Clone)]
+struct MissileInputMarker;
+
+#[derive(InputAction)]
+#[action_output(bool)]
+struct MissileInput;
+
+fn on_missile_input_binding(
+    add: On<Add, SpaceshipMissileInputBinding>,
+    mut commands: Commands,
+    q_binding: Query<&SpaceshipMissileInputBinding>,
+) {
+    let entity = add.entity;
+    trace!("on_missile_input_binding: entity {:?}", entity);
+
+    let Ok(binding) = q_binding.get(entity) else {
+        return;
+    };
+
+    commands.entity(entity).insert((
+        MissileInputMarker,
+        actions!(
+            MissileInputMarker[(
+                Name::new("Input: Missile"),
+                Action::<MissileInput>::new(),
+                ActionSettings {
+                    consume_input: false,
+                    ..default()
+                },
+                Bindings::spawn(binding.0.clone()),
+            )]
+        ),
+    ));
+}
+
+fn on_missile_input(
+    fire: On<Start<MissileInput>>,
+    mut q_launcher: Query<&mut MissileLauncherSectionInput, With<MissileInputMarker>>,
+) {
+    let entity = fire.event().context;
+    trace!("on_missile_input: entity {:?}", entity);
+
+    let Ok(mut q_launcher) = q_launcher.get_mut(entity) else {
+        return;
+    };
+
+    **q_launcher = true;
+}
+
+fn on_missile_input_completed(
+    fire: On<Complete<MissileInput>>,
+    mut q_launcher: Query<&mut MissileLauncherSectionInput, With<MissileInputMarker>>,
+) {
+    let entity = fire.event().context;
+    trace!("on_missile_input_completed: entity {:?}", entity);
+
+    let Ok(mut q_launcher) = q_launcher.get_mut(entity) else {
+        return;
+    };
+
+    **q_launcher = false;
+}
@@ -4,6 +4,7 @@
 
 pub mod camera_controller;
 pub mod damage;
+pub mod faction;
 pub mod hud;
 pub mod input;
 pub mod plugin;
@@ -18,6 +19,7 @@ pub mod prelude {
     pub use super::{
         camera_controller::prelude::*,
         damage::prelude::*,
+        faction::prelude::*,
         hud::prelude::*,
         input::prelude::*,
         plugin::{NovaGameplayPlugin, SpaceshipSystems},
@@ -21,24 +21,35 @@ impl Plugin for NovaGameplayPlugin {
         // We need to enable the physics plugins to have access to RigidBody and other components.
         // We will also disable gravity for this example, since we are in space, duh.
         app.add_plugins(PhysicsPlugins::default().with_collision_hooks::<TurretProjectileHooks>());
-        app.add_plugins(PhysicsPickingPlugin);
         app.insert_resource(Gravity::ZERO);
 
-        // Random number generator
-        app.add_plugins(EntropyPlugin::<WyRand>::default());
+        // Random number generator, unless a seeded one was already added
+        if !app.is_plugin_added::<EntropyPlugin<WyRand>>() {
+            app.add_plugins(EntropyPlugin::<WyRand>::default());
+        }
+
+        // Picking is also used without rendering (e.g by the editor), it is only skipped when the
+        // app has no picking at all, like the headless simulation.
+        if app.is_plugin_added::<bevy::picking::PickingPlugin>() {
+            app.add_plugins(PhysicsPickingPlugin);
+        }
 
-        // FIXME: For now we disable particle effects on wasm because it's not working
-        #[cfg(not(target_family = "wasm"))]
-        app.add_plugins(bevy_hanabi::HanabiPlugin);
+        // Plugins that need a window and the render app
+        if self.render {
+            // FIXME: For now we disable particle effects on wasm because it's not working
+            #[cfg(not(target_family = "wasm"))]
+            app.add_plugins(bevy_hanabi::HanabiPlugin);
+
+            // Bevy Common Systems - Rendering
+            app.add_plugins(bevy_common_systems::prelude::SkyboxPlugin);
+            app.add_plugins(bevy_common_systems::prelude::PostProcessingDefaultPlugin);
+        }
 
         // Bevy Common Systems - WASD Camera
         app.add_plugins(bevy_common_systems::prelude::WASDCameraPlugin);
         app.add_plugins(bevy_common_systems::prelude::WASDCameraControllerPlugin);
         // Chase Camera Plugin to have a 3rd person camera following the spaceship
         app.add_plugins(bevy_common_systems::prelude::ChaseCameraPlugin);
-        // Bevy Common Systems - Rendering
-        app.add_plugins(bevy_common_systems::prelude::SkyboxPlugin);
-        app.add_plugins(bevy_common_systems::prelude::PostProcessingDefaultPlugin);
         // Point Rotation Plugin to convert linear movement to a target rotation
         app.add_plugins(bevy_common_systems::prelude::PointRotationPlugin);
         // for debug to have a random orbiting object
@@ -101,3 +112,39 @@ impl Plugin for NovaGameplayPlugin {
         );
     }
 }
+
+/// The time that passes on every update of the test app.
+#[cfg(test)]
+pub(crate) const TEST_TIMESTEP: std::time::Duration = std::time::Duration::from_micros(15625);
+
+/// An app that runs the gameplay without a window or rendering, for tests. Every update moves the
+/// clock forward by one fixed timestep, so the physics runs once per update.
+#[cfg(test)]
+pub(crate) fn test_app() -> App {
+    use bevy::{
+        diagnostic::DiagnosticsPlugin, input::InputPlugin, scene::ScenePlugin,
+        state::app::StatesPlugin, time::TimeUpdateStrategy,
+    };
+
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        TransformPlugin,
+        AssetPlugin::default(),
+        ScenePlugin,
+        InputPlugin,
+        StatesPlugin,
+        DiagnosticsPlugin,
+    ));
+    app.init_asset::<Mesh>();
+    app.init_asset::<Image>();
+    app.init_asset::<StandardMaterial>();
+
+    app.insert_resource(Time::<Fixed>::from_duration(TEST_TIMESTEP));
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(TEST_TIMESTEP));
+
+    app.add_plugins(bevy_enhanced_input::EnhancedInputPlugin);
+    app.add_plugins(NovaGameplayPlugin { render: false });
+
+    app
+}
@@ -4,6 +4,7 @@ use avian3d::prelude::*;
 use bevy::prelude::*;
 
 use super::prelude::*;
+use crate::damage::prelude::*;
 
 pub mod prelude {
     pub use super::{
@@ -18,12 +19,38 @@ pub struct SectionMarker;
 #[derive(Component, Clone, Debug, Deref, DerefMut, Reflect, PartialEq, Eq)]
 pub struct SectionRenderOf(pub Entity);
 
-#[derive(Component, Clone, Debug, Default, Reflect)]
+/// The health of a section when it is not given in the config.
+const DEFAULT_SECTION_HEALTH: f32 = 100.0;
+
+#[derive(Component, Clone, Debug, Reflect, serde::Serialize, serde::Deserialize)]
 pub struct BaseSectionConfig {
     pub id: String,
     pub name: String,
     pub description: String,
     pub mass: f32,
+    /// The health of the section. A destroyed section breaks off the spaceship.
+    #[serde(default = "default_section_health")]
+    pub health: f32,
+    /// How well the section withstands each kind of damage.
+    #[serde(default)]
+    pub resistances: DamageResistances,
+}
+
+impl Default for BaseSectionConfig {
+    fn default() -> Self {
+        Self {
+            id: String::new(),
+            name: String::new(),
+            description: String::new(),
+            mass: 0.0,
+            health: DEFAULT_SECTION_HEALTH,
+            resistances: DamageResistances::default(),
+        }
+    }
+}
+
+fn default_section_health() -> f32 {
+    DEFAULT_SECTION_HEALTH
 }
 
 #[derive(Clone, Debug, Reflect)]
@@ -33,6 +60,11 @@ pub enum SectionKind {
     Thruster(ThrusterSectionConfig),
     Controller(ControllerSectionConfig),
     Turret(TurretSectionConfig),
+    Reactor(ReactorSectionConfig),
+    Battery(BatterySectionConfig),
+    MissileLauncher(MissileLauncherSectionConfig),
+    Beam(BeamSectionConfig),
+    Shield(ShieldSectionConfig),
 }
 
 #[derive(Clone, Debug, Reflect)]
@@ -58,6 +90,7 @@ pub fn base_section(config: BaseSectionConfig) -> impl Bundle {
         SectionMarker,
         Collider::cuboid(1.0, 1.0, 1.0),
         ColliderDensity(config.mass),
+        config.resistances.clone(),
         Visibility::Inherited,
     )
 }
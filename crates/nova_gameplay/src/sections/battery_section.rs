@@ -0,0 +1,116 @@
+//! A battery section stores the power generated by the reactors of a spaceship.
+
+use bevy::prelude::*;
+
+use crate::prelude::SectionRenderOf;
+
+pub mod prelude {
+    pub use super::{
+        battery_section, BatterySectionCapacity, BatterySectionConfig, BatterySectionMarker,
+        BatterySectionPlugin,
+    };
+}
+
+const BATTERY_SECTION_DEFAULT_CAPACITY: f32 = 50.0;
+
+/// Configuration for a battery section.
+#[derive(Clone, Debug, Reflect)]
+pub struct BatterySectionConfig {
+    /// The maximum power that the battery can store.
+    pub capacity: f32,
+    /// The render mesh of the section, defaults to prototype mesh if None.
+    pub render_mesh: Option<Handle<Scene>>,
+}
+
+impl Default for BatterySectionConfig {
+    fn default() -> Self {
+        Self {
+            capacity: BATTERY_SECTION_DEFAULT_CAPACITY,
+            render_mesh: None,
+        }
+    }
+}
+
+/// Helper function to create a battery section entity bundle.
+pub fn battery_section(config: BatterySectionConfig) -> impl Bundle {
+    debug!("battery_section: config {:?}", config);
+
+    (
+        BatterySectionMarker,
+        BatterySectionCapacity(config.capacity),
+        BatterySectionRenderMesh(config.render_mesh),
+    )
+}
+
+/// Marker component for battery sections.
+#[derive(Component, Clone, Debug, Reflect)]
+pub struct BatterySectionMarker;
+
+/// The maximum power that the battery section can store.
+#[derive(Component, Clone, Debug, Deref, DerefMut, Reflect)]
+pub struct BatterySectionCapacity(pub f32);
+
+#[derive(Component, Clone, Debug, Deref, DerefMut, Reflect)]
+struct BatterySectionRenderMesh(Option<Handle<Scene>>);
+
+/// A plugin that enables the BatterySection component and its related systems.
+#[derive(Default)]
+pub struct BatterySectionPlugin {
+    pub render: bool,
+}
+
+impl Plugin for BatterySectionPlugin {
+    fn build(&self, app: &mut App) {
+        debug!("BatterySectionPlugin: build");
+
+        if self.render {
+            app.add_observer(insert_battery_section_render);
+        }
+    }
+}
+
+fn insert_battery_section_render(
+    add: On<Add, BatterySectionMarker>,
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    q_battery: Query<&BatterySectionRenderMesh, With<BatterySectionMarker>>,
+) {
+    let entity = add.entity;
+    trace!("insert_battery_section_render: entity {:?}", entity);
+
+    let Ok(render_mesh) = q_battery.get(entity) else {
+        error!(
+            "insert_battery_section_render: entity {:?} not found in q_battery",
+            entity
+        );
+        return;
+    };
+
+    match &**render_mesh {
+        Some(scene) => {
+            commands.entity(entity).insert((children![(
+                Name::new("Battery Section Body"),
+                SectionRenderOf(entity),
+                SceneRoot(scene.clone()),
+            ),],));
+        }
+        None => {
+            commands.entity(entity).insert((children![
+                (
+                    Name::new("Battery Section Body (A)"),
+                    SectionRenderOf(entity),
+                    Mesh3d(meshes.add(Cuboid::new(1.0, 1.0, 1.0))),
+                    MeshMaterial3d(materials.add(Color::srgb(0.8, 0.7, 0.2))),
+                ),
+                (
+                    Name::new("Battery Section Terminal (B)"),
+                    SectionRenderOf(entity),
+                    Mesh3d(meshes.add(Cylinder::new(0.15, 0.2))),
+                    MeshMaterial3d(materials.add(Color::srgb(0.9, 0.9, 0.9))),
+                    Transform::from_xyz(0.0, 0.55, 0.0),
+                )
+            ],));
+        }
+    }
+}
@@ -0,0 +1,479 @@
+//! A beam section is a hitscan weapon. It aims with the same yaw and pitch rotators as a turret,
+//! and while it fires, it ray casts from its muzzle and continuously damages the first body hit.
+
+use avian3d::prelude::*;
+use bevy::prelude::*;
+use bevy_common_systems::prelude::*;
+
+use super::turret_section::{spawn_turret_rotators, TurretRotatorConfig};
+use crate::{damage::damage_body_and_sections, prelude::*};
+
+pub mod prelude {
+    pub use super::{
+        beam_section, BeamSectionConfig, BeamSectionMarker, BeamSectionPlugin, BeamSectionState,
+    };
+}
+
+/// Configuration for a beam section of a spaceship.
+#[derive(Clone, Debug, Reflect)]
+pub struct BeamSectionConfig {
+    /// The yaw speed of the beam section in radians per second.
+    pub yaw_speed: f32,
+    /// The pitch speed of the beam section in radians per second.
+    pub pitch_speed: f32,
+    /// The minimum pitch angle of the beam section in radians. If None, there is no limit.
+    pub min_pitch: Option<f32>,
+    /// The maximum pitch angle of the beam section in radians. If None, there is no limit.
+    pub max_pitch: Option<f32>,
+    /// The render mesh of the base, defaults to a cylinder base
+    pub render_mesh_base: Option<Handle<Scene>>,
+    /// The offset of the base from the section origin
+    pub base_offset: Vec3,
+    /// The render mesh of the yaw rotator, defaults to a cylinder with ridges
+    pub render_mesh_yaw: Option<Handle<Scene>>,
+    /// The offset of the yaw rotator from the base
+    pub yaw_offset: Vec3,
+    /// The render mesh of the pitch rotator, defaults to a cylinder with ridges
+    pub render_mesh_pitch: Option<Handle<Scene>>,
+    /// The offset of the pitch rotator from the yaw rotator
+    pub pitch_offset: Vec3,
+    /// The render mesh of the barrel, defaults to a simple barrel shape
+    pub render_mesh_barrel: Option<Handle<Scene>>,
+    /// The offset of the barrel from the pitch rotator
+    pub barrel_offset: Vec3,
+    /// The offset of the muzzle from the barrel
+    pub muzzle_offset: Vec3,
+    /// The damage per second dealt to the body hit by the beam.
+    pub damage_per_second: f32,
+    /// The maximum length of the beam.
+    pub range: f32,
+}
+
+impl Default for BeamSectionConfig {
+    fn default() -> Self {
+        Self {
+            yaw_speed: std::f32::consts::PI,   // 180 degrees per second
+            pitch_speed: std::f32::consts::PI, // 180 degrees per second
+            min_pitch: Some(-std::f32::consts::FRAC_PI_6),
+            max_pitch: Some(std::f32::consts::FRAC_PI_2),
+            render_mesh_base: None,
+            base_offset: Vec3::new(0.0, -0.5, 0.0),
+            render_mesh_yaw: None,
+            yaw_offset: Vec3::new(0.0, 0.1, 0.0),
+            render_mesh_pitch: None,
+            pitch_offset: Vec3::new(0.0, 0.2, 0.0),
+            render_mesh_barrel: None,
+            barrel_offset: Vec3::new(0.1, 0.2, 0.0),
+            muzzle_offset: Vec3::new(0.0, 0.0, -0.5),
+            damage_per_second: 20.0,
+            range: 200.0,
+        }
+    }
+}
+
+impl From<&BeamSectionConfig> for TurretRotatorConfig {
+    fn from(config: &BeamSectionConfig) -> Self {
+        Self {
+            yaw_speed: config.yaw_speed,
+            pitch_speed: config.pitch_speed,
+            min_pitch: config.min_pitch,
+            max_pitch: config.max_pitch,
+            render_mesh_base: config.render_mesh_base.clone(),
+            base_offset: config.base_offset,
+            render_mesh_yaw: config.render_mesh_yaw.clone(),
+            yaw_offset: config.yaw_offset,
+            render_mesh_pitch: config.render_mesh_pitch.clone(),
+            pitch_offset: config.pitch_offset,
+            render_mesh_barrel: config.render_mesh_barrel.clone(),
+            barrel_offset: config.barrel_offset,
+            muzzle_offset: config.muzzle_offset,
+        }
+    }
+}
+
+/// Helper function to create a beam section entity bundle. The beam fires while its
+/// `TurretSectionInput` is set, and aims at its `TurretSectionTargetInput`, like a turret.
+pub fn beam_section(config: BeamSectionConfig) -> impl Bundle {
+    debug!("beam_section: config {:?}", config);
+
+    (
+        BeamSectionMarker,
+        TurretSectionTargetInput(None),
+        TurretSectionInput(false),
+        BeamSectionState::default(),
+        BeamSectionConfigHelper(config),
+    )
+}
+
+/// Marker component for beam sections.
+#[derive(Component, Clone, Debug, Reflect)]
+pub struct BeamSectionMarker;
+
+/// What the beam section is doing in the current frame.
+#[derive(Component, Clone, Debug, Default, Reflect)]
+pub struct BeamSectionState {
+    /// Whether the beam is firing.
+    pub firing: bool,
+    /// The length of the beam, up to the first hit or the range of the beam.
+    pub length: f32,
+    /// The body hit by the beam.
+    pub target: Option<Entity>,
+}
+
+#[derive(Component, Clone, Debug, Deref, DerefMut, Reflect)]
+struct BeamSectionConfigHelper(BeamSectionConfig);
+
+/// The muzzle of the beam section that the beam is emitted from.
+#[derive(Component, Clone, Copy, Debug, Deref, DerefMut, Reflect)]
+struct BeamSectionEmitterOf(Entity);
+
+/// The render of the beam emitted by a beam section.
+#[derive(Component, Clone, Copy, Debug, Deref, DerefMut, Reflect)]
+struct BeamSectionRenderOf(Entity);
+
+/// A plugin that enables the BeamSection component and its related systems.
+#[derive(Default)]
+pub struct BeamSectionPlugin {
+    pub render: bool,
+}
+
+impl Plugin for BeamSectionPlugin {
+    fn build(&self, app: &mut App) {
+        debug!("BeamSectionPlugin: build");
+
+        app.add_observer(insert_beam_section);
+
+        app.add_systems(
+            Update,
+            update_beam_fire.in_set(super::SpaceshipSectionSystems),
+        );
+
+        if self.render {
+            app.add_observer(insert_beam_render);
+
+            app.add_systems(
+                Update,
+                update_beam_render
+                    .after(update_beam_fire)
+                    .in_set(super::SpaceshipSectionSystems),
+            );
+        }
+    }
+}
+
+fn insert_beam_section(
+    add: On<Add, BeamSectionMarker>,
+    mut commands: Commands,
+    q_config: Query<&BeamSectionConfigHelper, With<BeamSectionMarker>>,
+) {
+    let beam = add.entity;
+    trace!("insert_beam_section: entity {:?}", beam);
+
+    let Ok(config) = q_config.get(beam) else {
+        error!(
+            "insert_beam_section: entity {:?} not found in q_config",
+            beam
+        );
+        return;
+    };
+
+    spawn_turret_rotators(
+        &mut commands,
+        beam,
+        TurretRotatorConfig::from(&**config),
+        (Name::new("Beam Muzzle"), BeamSectionEmitterOf(beam)),
+    );
+}
+
+fn update_beam_fire(
+    mut commands: Commands,
+    spatial_query: SpatialQuery,
+    mut q_spaceship: Query<Option<&mut SpaceshipPower>, With<SpaceshipRootMarker>>,
+    mut q_beam: Query<
+        (
+            &TurretSectionMuzzleEntity,
+            &ChildOf,
+            &BeamSectionConfigHelper,
+            &TurretSectionInput,
+            &mut BeamSectionState,
+        ),
+        With<BeamSectionMarker>,
+    >,
+    q_muzzle: Query<&GlobalTransform, With<TurretSectionBarrelMuzzleMarker>>,
+    q_collider: Query<&ColliderOf>,
+    q_owner: Query<&ProjectileOwner>,
+    q_health: Query<(), With<Health>>,
+    q_faction: Query<&Faction>,
+    q_resistances: Query<&DamageResistances>,
+    policy: Res<FriendlyFirePolicy>,
+    time: Res<Time>,
+) {
+    let delta = time.delta_secs();
+
+    for (muzzle, ChildOf(spaceship), config, input, mut state) in &mut q_beam {
+        *state = BeamSectionState::default();
+        if !**input {
+            continue;
+        }
+
+        let Ok(power) = q_spaceship.get_mut(*spaceship) else {
+            error!(
+                "update_beam_fire: entity {:?} not found in q_spaceship",
+                spaceship
+            );
+            continue;
+        };
+
+        // The beam weakens when the spaceship runs out of power
+        let strength = power
+            .map(|mut power| power.draw(BEAM_POWER_PER_SECOND * delta))
+            .unwrap_or(1.0);
+        if strength <= 0.0 {
+            continue;
+        }
+
+        let Ok(muzzle_transform) = q_muzzle.get(**muzzle) else {
+            error!(
+                "update_beam_fire: entity {:?} not found in q_muzzle",
+                **muzzle
+            );
+            continue;
+        };
+
+        // The beam goes through the spaceship and the projectiles it fired
+        let hit = spatial_query.cast_ray_predicate(
+            muzzle_transform.translation(),
+            muzzle_transform.forward(),
+            config.range,
+            true,
+            &SpatialQueryFilter::default(),
+            &|collider| {
+                let Ok(&ColliderOf { body }) = q_collider.get(collider) else {
+                    return true;
+                };
+
+                body != *spaceship
+                    && q_owner
+                        .get(body)
+                        .map_or(true, |owner| **owner != *spaceship)
+            },
+        );
+
+        state.firing = true;
+        state.length = config.range;
+
+        let Some(hit) = hit else {
+            continue;
+        };
+
+        let body = q_collider
+            .get(hit.entity)
+            .map(|collider| collider.body)
+            .unwrap_or(hit.entity);
+        state.length = hit.distance;
+        state.target = Some(body);
+
+        let kind = DamageKind::Projectile;
+        let amount = config.damage_per_second * delta * strength;
+        let amount = policy.damage(
+            amount,
+            q_faction.get(body).ok(),
+            q_faction.get(*spaceship).ok(),
+        );
+        let amount = match q_resistances.get(hit.entity) {
+            Ok(resistances) => resistances.apply(kind, amount),
+            Err(_) => amount,
+        };
+        if amount <= 0.0 {
+            continue;
+        }
+
+        // The section that was hit takes the damage as well
+        let sections = if hit.entity != body && q_health.contains(hit.entity) {
+            vec![(hit.entity, amount)]
+        } else {
+            Vec::new()
+        };
+        damage_body_and_sections(
+            &mut commands,
+            HealthApplyDamage {
+                target: body,
+                source: Some(*spaceship),
+                amount,
+                kind,
+            },
+            sections,
+        );
+    }
+}
+
+fn insert_beam_render(
+    add: On<Add, BeamSectionEmitterOf>,
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    q_emitter: Query<&BeamSectionEmitterOf>,
+) {
+    let entity = add.entity;
+    trace!("insert_beam_render: entity {:?}", entity);
+
+    let Ok(beam) = q_emitter.get(entity) else {
+        error!(
+            "insert_beam_render: entity {:?} not found in q_emitter",
+            entity
+        );
+        return;
+    };
+
+    // The cylinder is along the Y axis, it is turned to point forward and stretched to the length
+    // of the beam every frame
+    commands.entity(entity).insert((children![(
+        Name::new("Beam Render"),
+        BeamSectionRenderOf(**beam),
+        Mesh3d(meshes.add(Cylinder::new(0.03, 1.0))),
+        MeshMaterial3d(materials.add(StandardMaterial {
+            base_color: Color::srgb(0.4, 0.8, 1.0),
+            emissive: LinearRgba::rgb(1.0, 4.0, 8.0),
+            unlit: true,
+            ..default()
+        })),
+        Transform::from_rotation(Quat::from_rotation_x(std::f32::consts::FRAC_PI_2)),
+        Visibility::Hidden,
+    ),],));
+}
+
+fn update_beam_render(
+    q_beam: Query<&BeamSectionState, With<BeamSectionMarker>>,
+    mut q_render: Query<(&BeamSectionRenderOf, &mut Transform, &mut Visibility)>,
+) {
+    for (beam, mut transform, mut visibility) in &mut q_render {
+        let Ok(state) = q_beam.get(**beam) else {
+            error!(
+                "update_beam_render: entity {:?} not found in q_beam",
+                **beam
+            );
+            continue;
+        };
+
+        if !state.firing {
+            *visibility = Visibility::Hidden;
+            continue;
+        }
+
+        *visibility = Visibility::Inherited;
+        transform.translation = Vec3::NEG_Z * state.length / 2.0;
+        transform.scale = Vec3::new(1.0, state.length, 1.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugin::{test_app, TEST_TIMESTEP};
+
+    /// Spawn a spaceship with a beam section at the origin, the beam points to -Z.
+    fn spawn_beam(app: &mut App) -> Entity {
+        let mut beam = Entity::PLACEHOLDER;
+        app.world_mut()
+            .spawn((
+                SpaceshipRootMarker,
+                RigidBody::Dynamic,
+                Transform::default(),
+            ))
+            .with_children(|parent| {
+                beam = parent
+                    .spawn((
+                        base_section(BaseSectionConfig::default()),
+                        beam_section(BeamSectionConfig::default()),
+                        Transform::default(),
+                    ))
+                    .id();
+            });
+
+        beam
+    }
+
+    fn spawn_target(app: &mut App, position: Vec3) -> Entity {
+        app.world_mut()
+            .spawn((
+                RigidBody::Static,
+                Collider::sphere(1.0),
+                Health::new(100.0),
+                Transform::from_translation(position),
+            ))
+            .id()
+    }
+
+    /// Let the physics pick up the bodies, then fire the beam for the given number of updates.
+    fn fire(app: &mut App, beam: Entity, updates: usize) {
+        app.update();
+        app.update();
+
+        app.world_mut()
+            .entity_mut(beam)
+            .insert(TurretSectionInput(true));
+        for _ in 0..updates {
+            app.update();
+        }
+    }
+
+    fn health(app: &App, entity: Entity) -> f32 {
+        app.world().get::<Health>(entity).unwrap().current
+    }
+
+    #[test]
+    fn hits_the_body_along_the_beam() {
+        // Arrange
+        let mut app = test_app();
+        let beam = spawn_beam(&mut app);
+        let target = spawn_target(&mut app, Vec3::new(0.0, 0.0, -10.0));
+        let aside = spawn_target(&mut app, Vec3::new(10.0, 0.0, -10.0));
+
+        // Act
+        fire(&mut app, beam, 1);
+
+        // Assert
+        let state = app.world().get::<BeamSectionState>(beam).unwrap();
+        assert!(state.firing);
+        assert_eq!(state.target, Some(target));
+        assert!(state.length > 8.0 && state.length < 9.0);
+        assert!(health(&app, target) < 100.0);
+        assert_eq!(health(&app, aside), 100.0);
+    }
+
+    #[test]
+    fn damages_the_target_every_tick() {
+        // Arrange
+        let mut app = test_app();
+        let beam = spawn_beam(&mut app);
+        let target = spawn_target(&mut app, Vec3::new(0.0, 0.0, -10.0));
+        let updates = 8;
+
+        // Act
+        fire(&mut app, beam, updates);
+
+        // Assert
+        let expected = BeamSectionConfig::default().damage_per_second
+            * TEST_TIMESTEP.as_secs_f32()
+            * updates as f32;
+        assert!((100.0 - health(&app, target) - expected).abs() < 1e-3);
+    }
+
+    #[test]
+    fn stops_at_the_first_collider() {
+        // Arrange
+        let mut app = test_app();
+        let beam = spawn_beam(&mut app);
+        let near = spawn_target(&mut app, Vec3::new(0.0, 0.0, -10.0));
+        let far = spawn_target(&mut app, Vec3::new(0.0, 0.0, -20.0));
+
+        // Act
+        fire(&mut app, beam, 4);
+
+        // Assert
+        let state = app.world().get::<BeamSectionState>(beam).unwrap();
+        assert_eq!(state.target, Some(near));
+        assert!(health(&app, near) < 100.0);
+        assert_eq!(health(&app, far), 100.0);
+    }
+}
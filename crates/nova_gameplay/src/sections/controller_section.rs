@@ -3,7 +3,9 @@
 use avian3d::prelude::*;
 use bevy::prelude::*;
 
-use crate::prelude::{SectionRenderOf, SpaceshipRootMarker};
+use crate::prelude::{
+    SectionRenderOf, SpaceshipPower, SpaceshipRootMarker, CONTROLLER_POWER_PER_TORQUE,
+};
 
 pub mod prelude {
     pub use super::{
@@ -87,7 +89,9 @@ impl Plugin for ControllerSectionPlugin {
 
         app.add_systems(
             FixedUpdate,
-            update_controller_root_torque.in_set(super::SpaceshipSectionSystems),
+            update_controller_root_torque
+                .after(super::power::update_spaceship_power)
+                .in_set(super::SpaceshipSectionSystems),
         );
 
         if self.render {
@@ -97,7 +101,15 @@ impl Plugin for ControllerSectionPlugin {
 }
 
 fn update_controller_root_torque(
-    mut q_root: Query<(&ComputedAngularInertia, &Rotation, Forces), With<SpaceshipRootMarker>>,
+    mut q_root: Query<
+        (
+            &ComputedAngularInertia,
+            &Rotation,
+            Forces,
+            Option<&mut SpaceshipPower>,
+        ),
+        With<SpaceshipRootMarker>,
+    >,
     q_controller: Query<
         (
             &ControllerSectionStableTorquePdController,
@@ -106,9 +118,10 @@ fn update_controller_root_torque(
         ),
         With<ControllerSectionMarker>,
     >,
+    time: Res<Time>,
 ) {
     for (controller, controller_input, &ChildOf(root)) in &q_controller {
-        let Ok((angular_inertia, rotation, mut forces)) = q_root.get_mut(root) else {
+        let Ok((angular_inertia, rotation, mut forces, power)) = q_root.get_mut(root) else {
             error!(
                 "update_controller_root_torque: root entity {:?} not found in q_root",
                 root
@@ -129,7 +142,15 @@ fn update_controller_root_torque(
             local_frame,
         );
 
-        forces.apply_torque(torque);
+        // The torque is weaker when the spaceship runs out of power
+        let efficiency = match power {
+            Some(mut power) => {
+                power.draw(torque.length() * CONTROLLER_POWER_PER_TORQUE * time.delta_secs())
+            }
+            None => 1.0,
+        };
+
+        forces.apply_torque(torque * efficiency);
     }
 }
 
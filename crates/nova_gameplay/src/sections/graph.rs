@@ -0,0 +1,161 @@
+//! The structure of a spaceship: sections are placed on a unit grid, and two sections are
+//! connected when they are next to each other along one of the axes.
+
+use bevy::{
+    platform::collections::{HashMap, HashSet},
+    prelude::*,
+};
+
+pub mod prelude {
+    pub use super::{overlapping_sections, section_grid_position, SectionGraph};
+}
+
+/// The cell of the unit grid that a section at the given local position occupies.
+pub fn section_grid_position(position: Vec3) -> IVec3 {
+    position.round().as_ivec3()
+}
+
+/// The adjacency graph of the sections of a spaceship.
+#[derive(Clone, Debug)]
+pub struct SectionGraph<K> {
+    cells: HashMap<IVec3, K>,
+}
+
+impl<K: Copy + Eq + std::hash::Hash> SectionGraph<K> {
+    /// Build the graph from the sections and their local positions. When two sections occupy the
+    /// same cell, the last one wins; use `overlapping_sections` to detect that.
+    pub fn new(sections: impl IntoIterator<Item = (K, Vec3)>) -> Self {
+        let cells = sections
+            .into_iter()
+            .map(|(key, position)| (section_grid_position(position), key))
+            .collect();
+
+        Self { cells }
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// The sections next to the one in the given cell.
+    pub fn neighbours(&self, cell: IVec3) -> impl Iterator<Item = (IVec3, K)> + '_ {
+        [
+            IVec3::X,
+            IVec3::NEG_X,
+            IVec3::Y,
+            IVec3::NEG_Y,
+            IVec3::Z,
+            IVec3::NEG_Z,
+        ]
+        .into_iter()
+        .filter_map(move |offset| {
+            let neighbour = cell + offset;
+            self.cells.get(&neighbour).map(|key| (neighbour, *key))
+        })
+    }
+
+    /// The groups of sections that are connected to each other. The groups are sorted from the
+    /// largest to the smallest.
+    pub fn components(&self) -> Vec<Vec<K>> {
+        let mut visited = HashSet::new();
+        let mut components = Vec::new();
+
+        for &start in self.cells.keys() {
+            if !visited.insert(start) {
+                continue;
+            }
+
+            let mut component = Vec::new();
+            let mut stack = vec![start];
+            while let Some(cell) = stack.pop() {
+                component.push(self.cells[&cell]);
+                for (neighbour, _) in self.neighbours(cell) {
+                    if visited.insert(neighbour) {
+                        stack.push(neighbour);
+                    }
+                }
+            }
+
+            components.push(component);
+        }
+
+        components.sort_by_key(|component| std::cmp::Reverse(component.len()));
+        components
+    }
+
+    /// Whether all the sections are connected. An empty graph is connected.
+    pub fn is_connected(&self) -> bool {
+        self.components().len() <= 1
+    }
+}
+
+/// The cells that are occupied by more than one of the given positions.
+pub fn overlapping_sections(positions: impl IntoIterator<Item = Vec3>) -> Vec<IVec3> {
+    let mut cells = HashSet::new();
+    let mut overlapping = Vec::new();
+    for position in positions {
+        let cell = section_grid_position(position);
+        if !cells.insert(cell) && !overlapping.contains(&cell) {
+            overlapping.push(cell);
+        }
+    }
+
+    overlapping
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_connected_sections() {
+        // Arrange
+        let graph = SectionGraph::new([
+            (0, Vec3::ZERO),
+            (1, Vec3::Z),
+            (2, Vec3::new(0.0, 0.0, 2.0)),
+            (3, Vec3::new(1.0, 0.0, 2.0)),
+        ]);
+
+        // Act
+        let components = graph.components();
+
+        // Assert
+        assert!(graph.is_connected());
+        assert_eq!(components.len(), 1);
+        assert_eq!(components[0].len(), 4);
+    }
+
+    #[test]
+    fn splits_disconnected_sections() {
+        // Arrange
+        // The diagonal section only touches the others by a corner
+        let graph =
+            SectionGraph::new([(0, Vec3::ZERO), (1, Vec3::X), (2, Vec3::new(2.0, 1.0, 0.0))]);
+
+        // Act
+        let components = graph.components();
+
+        // Assert
+        assert!(!graph.is_connected());
+        assert_eq!(components.len(), 2);
+        assert_eq!(components[0].len(), 2);
+        assert_eq!(components[1], vec![2]);
+    }
+
+    #[test]
+    fn detects_overlapping_sections() {
+        // Arrange
+        let positions = [Vec3::ZERO, Vec3::new(0.1, 0.0, 0.0), Vec3::X];
+
+        // Act
+        let cells = overlapping_sections(positions);
+
+        // Assert
+        assert_eq!(cells, vec![IVec3::ZERO]);
+    }
+}
@@ -0,0 +1,632 @@
+//! A missile launcher section locks on to a target and fires missiles that seek it. The missiles
+//! turn towards their target with a limited turn rate and detonate when they get close enough,
+//! damaging everything around them.
+
+use avian3d::prelude::*;
+use bevy::prelude::*;
+use bevy_common_systems::prelude::*;
+
+use crate::prelude::*;
+
+pub mod prelude {
+    pub use super::{
+        missile_launcher_section, steer_towards, MissileLauncherSectionConfig,
+        MissileLauncherSectionInput, MissileLauncherSectionLock, MissileLauncherSectionMarker,
+        MissileLauncherSectionPlugin, MissileLauncherSectionReload,
+        MissileLauncherSectionTargetInput, MissileProjectileMarker, MissileTarget,
+    };
+}
+
+/// Configuration for a missile launcher section of a spaceship.
+#[derive(Clone, Debug, Reflect)]
+pub struct MissileLauncherSectionConfig {
+    /// The time in seconds that the target must stay in front of the launcher to lock on to it.
+    pub lock_on_time: f32,
+    /// The maximum distance to lock on to a target.
+    pub lock_range: f32,
+    /// The maximum angle in radians between the launcher and the target to lock on to it.
+    pub lock_angle: f32,
+    /// The time in seconds between two missiles.
+    pub reload_time: f32,
+    /// The speed of the missiles in units per second.
+    pub missile_speed: f32,
+    /// The turn rate of the missiles in radians per second.
+    pub missile_turn_rate: f32,
+    /// The time in seconds before a missile that missed its target is removed.
+    pub missile_lifetime: f32,
+    /// The mass of the missiles.
+    pub missile_mass: f32,
+    /// The distance to the target at which a missile detonates.
+    pub proximity_radius: f32,
+    /// The radius of the blast of a missile.
+    pub blast_radius: f32,
+    /// The damage at the center of the blast of a missile.
+    pub blast_damage: f32,
+    /// The render mesh of the launcher, defaults to a prototype mesh if None.
+    pub render_mesh: Option<Handle<Scene>>,
+    /// The render mesh of the missiles, defaults to a prototype mesh if None.
+    pub missile_render_mesh: Option<Handle<Scene>>,
+}
+
+impl Default for MissileLauncherSectionConfig {
+    fn default() -> Self {
+        Self {
+            lock_on_time: 1.5,
+            lock_range: 300.0,
+            lock_angle: std::f32::consts::FRAC_PI_6,
+            reload_time: 3.0,
+            missile_speed: 60.0,
+            missile_turn_rate: 2.0,
+            missile_lifetime: 8.0,
+            missile_mass: 0.5,
+            proximity_radius: 3.0,
+            blast_radius: 8.0,
+            blast_damage: 60.0,
+            render_mesh: None,
+            missile_render_mesh: None,
+        }
+    }
+}
+
+/// Helper function to create a missile launcher section entity bundle.
+pub fn missile_launcher_section(config: MissileLauncherSectionConfig) -> impl Bundle {
+    debug!("missile_launcher_section: config {:?}", config);
+
+    let mut reload = Timer::from_seconds(config.reload_time, TimerMode::Once);
+    reload.finish(); // Ready to fire immediately
+
+    (
+        MissileLauncherSectionMarker,
+        MissileLauncherSectionInput(false),
+        MissileLauncherSectionTargetInput(None),
+        MissileLauncherSectionLock::new(config.lock_on_time, config.lock_range, config.lock_angle),
+        MissileLauncherSectionReload(reload),
+        MissileLauncherSectionRenderMesh(config.render_mesh.clone()),
+        MissileLauncherSectionConfigHelper(config),
+    )
+}
+
+/// Marker component for missile launcher sections.
+#[derive(Component, Clone, Debug, Reflect)]
+pub struct MissileLauncherSectionMarker;
+
+/// Input to request the missile launcher to fire a missile.
+#[derive(Component, Clone, Debug, Deref, DerefMut, Reflect)]
+pub struct MissileLauncherSectionInput(pub bool);
+
+/// The entity that the missile launcher should lock on to.
+#[derive(Component, Clone, Copy, Debug, Deref, DerefMut, Reflect)]
+pub struct MissileLauncherSectionTargetInput(pub Option<Entity>);
+
+/// The lock of a missile launcher on its target. The lock builds up while the target stays in
+/// range and in front of the launcher, and is lost as soon as it leaves.
+#[derive(Component, Clone, Debug, Reflect)]
+pub struct MissileLauncherSectionLock {
+    /// The entity that the launcher is locking on to.
+    pub target: Option<Entity>,
+    /// The time in seconds that the target has been in front of the launcher.
+    pub progress: f32,
+    /// The time in seconds needed to lock on to the target.
+    pub lock_on_time: f32,
+    /// The maximum distance to the target.
+    pub range: f32,
+    /// The maximum angle in radians between the launcher and the target.
+    pub angle: f32,
+}
+
+impl MissileLauncherSectionLock {
+    pub fn new(lock_on_time: f32, range: f32, angle: f32) -> Self {
+        Self {
+            target: None,
+            progress: 0.0,
+            lock_on_time,
+            range,
+            angle,
+        }
+    }
+
+    pub fn is_locked(&self) -> bool {
+        self.target.is_some() && self.progress >= self.lock_on_time
+    }
+
+    /// The progress of the lock in `[0, 1]`.
+    pub fn fraction(&self) -> f32 {
+        if self.lock_on_time <= 0.0 {
+            return if self.target.is_some() { 1.0 } else { 0.0 };
+        }
+
+        (self.progress / self.lock_on_time).clamp(0.0, 1.0)
+    }
+
+    /// Whether a target at the given offset from the launcher can be locked on to.
+    pub fn can_lock(&self, forward: Vec3, offset: Vec3) -> bool {
+        let distance = offset.length();
+        distance <= self.range
+            && distance > f32::EPSILON
+            && forward.angle_between(offset) <= self.angle
+    }
+
+    fn update(&mut self, target: Option<Entity>, in_front: bool, delta: f32) {
+        if target != self.target {
+            self.target = target;
+            self.progress = 0.0;
+        }
+
+        if self.target.is_some() && in_front {
+            self.progress = (self.progress + delta).min(self.lock_on_time);
+        } else {
+            self.progress = 0.0;
+        }
+    }
+}
+
+/// The time until the missile launcher can fire again.
+#[derive(Component, Clone, Debug, Deref, DerefMut, Reflect)]
+pub struct MissileLauncherSectionReload(pub Timer);
+
+/// Marker for missile projectiles.
+#[derive(Component, Clone, Debug, Reflect)]
+pub struct MissileProjectileMarker;
+
+/// The entity that a missile is seeking. A missile without a target flies straight.
+#[derive(Component, Clone, Copy, Debug, Deref, DerefMut, Reflect)]
+pub struct MissileTarget(pub Option<Entity>);
+
+#[derive(Component, Clone, Debug, Reflect)]
+struct MissileGuidance {
+    speed: f32,
+    turn_rate: f32,
+}
+
+#[derive(Component, Clone, Debug, Reflect)]
+struct MissileWarhead {
+    proximity_radius: f32,
+    blast_radius: f32,
+    blast_damage: f32,
+}
+
+#[derive(Component, Clone, Debug, Deref, DerefMut, Reflect)]
+struct MissileLauncherSectionConfigHelper(MissileLauncherSectionConfig);
+
+#[derive(Component, Clone, Debug, Deref, DerefMut, Reflect)]
+struct MissileLauncherSectionRenderMesh(Option<Handle<Scene>>);
+
+#[derive(Component, Clone, Debug, Deref, DerefMut, Reflect)]
+struct MissileProjectileRenderMesh(Option<Handle<Scene>>);
+
+/// Turn a direction towards the target direction by at most `max_angle` radians.
+pub fn steer_towards(direction: Vec3, target_direction: Vec3, max_angle: f32) -> Vec3 {
+    let (Some(direction), Some(target_direction)) =
+        (direction.try_normalize(), target_direction.try_normalize())
+    else {
+        return direction.normalize_or_zero();
+    };
+
+    let angle = direction.angle_between(target_direction);
+    if angle <= max_angle {
+        return target_direction;
+    }
+
+    let rotation = Quat::from_rotation_arc(direction, target_direction);
+    Quat::IDENTITY.slerp(rotation, max_angle / angle) * direction
+}
+
+/// A plugin that enables the MissileLauncherSection component and its related systems.
+#[derive(Default)]
+pub struct MissileLauncherSectionPlugin {
+    pub render: bool,
+}
+
+impl Plugin for MissileLauncherSectionPlugin {
+    fn build(&self, app: &mut App) {
+        debug!("MissileLauncherSectionPlugin: build");
+
+        app.add_observer(on_missile_impact);
+
+        if self.render {
+            app.add_observer(insert_missile_launcher_section_render);
+            app.add_observer(insert_missile_render);
+        }
+
+        app.add_systems(
+            Update,
+            (
+                update_missile_launcher_reload,
+                update_missile_launcher_lock,
+                shoot_spawn_missile,
+                update_missile_guidance,
+                update_missile_fuse,
+            )
+                .chain()
+                .in_set(super::SpaceshipSectionSystems),
+        );
+    }
+}
+
+fn update_missile_launcher_reload(
+    mut q_launcher: Query<&mut MissileLauncherSectionReload, With<MissileLauncherSectionMarker>>,
+    time: Res<Time>,
+) {
+    for mut reload in &mut q_launcher {
+        reload.tick(time.delta());
+    }
+}
+
+fn update_missile_launcher_lock(
+    mut q_launcher: Query<
+        (
+            &GlobalTransform,
+            &MissileLauncherSectionTargetInput,
+            &mut MissileLauncherSectionLock,
+        ),
+        With<MissileLauncherSectionMarker>,
+    >,
+    q_target: Query<&GlobalTransform, Without<DestroyedMarker>>,
+    time: Res<Time>,
+) {
+    for (transform, target_input, mut lock) in &mut q_launcher {
+        let target = (**target_input).filter(|target| q_target.contains(*target));
+        let in_front = target
+            .and_then(|target| q_target.get(target).ok())
+            .is_some_and(|target_transform| {
+                let offset = target_transform.translation() - transform.translation();
+                lock.can_lock(transform.forward().into(), offset)
+            });
+
+        lock.update(target, in_front, time.delta_secs());
+    }
+}
+
+fn shoot_spawn_missile(
+    mut commands: Commands,
+    mut q_spaceship: Query<
+        (&LinearVelocity, Option<&mut SpaceshipPower>),
+        With<SpaceshipRootMarker>,
+    >,
+    mut q_launcher: Query<
+        (
+            &GlobalTransform,
+            &ChildOf,
+            &MissileLauncherSectionConfigHelper,
+            &MissileLauncherSectionInput,
+            &MissileLauncherSectionLock,
+            &mut MissileLauncherSectionReload,
+        ),
+        With<MissileLauncherSectionMarker>,
+    >,
+) {
+    for (transform, ChildOf(spaceship), config, input, lock, mut reload) in &mut q_launcher {
+        if !**input || !reload.is_finished() {
+            continue;
+        }
+
+        let Ok((lin_vel, power)) = q_spaceship.get_mut(*spaceship) else {
+            error!(
+                "shoot_spawn_missile: entity {:?} not found in q_spaceship",
+                spaceship
+            );
+            continue;
+        };
+
+        // Every missile needs power, a spaceship without enough power can't fire
+        if let Some(mut power) = power {
+            if !power.try_draw(MISSILE_LAUNCHER_POWER_PER_SHOT) {
+                continue;
+            }
+        }
+
+        // Without a lock, the missile flies straight like a rocket
+        let target = lock.is_locked().then_some(lock.target).flatten();
+
+        let direction = transform.forward();
+        let missile_transform = Transform {
+            translation: transform.translation() + direction * 1.0,
+            rotation: transform.rotation(),
+            ..default()
+        };
+
+        debug!(
+            "shoot_spawn_missile: spaceship {:?} fired a missile at {:?}",
+            spaceship, target
+        );
+        commands.spawn((
+            Name::new("Missile"),
+            MissileProjectileMarker,
+            ProjectileOwner(*spaceship),
+            MissileTarget(target),
+            MissileGuidance {
+                speed: config.missile_speed,
+                turn_rate: config.missile_turn_rate,
+            },
+            MissileWarhead {
+                proximity_radius: config.proximity_radius,
+                blast_radius: config.blast_radius,
+                blast_damage: config.blast_damage,
+            },
+            missile_transform,
+            RigidBody::Dynamic,
+            LinearVelocity(direction * config.missile_speed + **lin_vel),
+            Collider::capsule(0.1, 0.4),
+            ActiveCollisionHooks::FILTER_PAIRS,
+            Mass(config.missile_mass),
+            MissileProjectileRenderMesh(config.missile_render_mesh.clone()),
+            TempEntity(config.missile_lifetime),
+            Visibility::Visible,
+            TransformInterpolation,
+        ));
+
+        reload.reset();
+    }
+}
+
+/// Turn the missiles towards the point where they will intercept their target.
+fn update_missile_guidance(
+    mut q_missile: Query<
+        (
+            &mut Transform,
+            &mut LinearVelocity,
+            &mut MissileTarget,
+            &MissileGuidance,
+        ),
+        With<MissileProjectileMarker>,
+    >,
+    q_target: Query<(&GlobalTransform, Option<&LinearVelocity>), Without<MissileProjectileMarker>>,
+    time: Res<Time>,
+) {
+    for (mut transform, mut velocity, mut target, guidance) in &mut q_missile {
+        let Some(target_entity) = **target else {
+            continue;
+        };
+
+        let Ok((target_transform, target_velocity)) = q_target.get(target_entity) else {
+            // The target is gone, keep flying straight
+            **target = None;
+            continue;
+        };
+
+        let target_position = target_transform.translation();
+        let target_velocity = target_velocity.map(|v| **v).unwrap_or_default();
+        let aim = intercept_point(
+            transform.translation,
+            Vec3::ZERO,
+            target_position,
+            target_velocity,
+            guidance.speed,
+        )
+        .unwrap_or(target_position);
+
+        let max_angle = guidance.turn_rate * time.delta_secs();
+        let direction = steer_towards(**velocity, aim - transform.translation, max_angle);
+        if direction == Vec3::ZERO {
+            continue;
+        }
+
+        **velocity = direction * guidance.speed;
+        transform.rotation = Quat::from_rotation_arc(Vec3::NEG_Z, direction);
+    }
+}
+
+fn update_missile_fuse(
+    mut commands: Commands,
+    q_missile: Query<
+        (
+            Entity,
+            &Transform,
+            &MissileTarget,
+            &MissileWarhead,
+            &ProjectileOwner,
+        ),
+        With<MissileProjectileMarker>,
+    >,
+    q_target: Query<&GlobalTransform>,
+) {
+    for (missile, transform, target, warhead, owner) in &q_missile {
+        let Some(target_transform) = (**target).and_then(|target| q_target.get(target).ok()) else {
+            continue;
+        };
+
+        let distance = target_transform
+            .translation()
+            .distance(transform.translation);
+        if distance <= warhead.proximity_radius {
+            detonate_missile(
+                &mut commands,
+                missile,
+                transform.translation,
+                warhead,
+                owner,
+            );
+        }
+    }
+}
+
+/// Missiles also detonate when they hit something.
+fn on_missile_impact(
+    hit: On<CollisionImpactEvent>,
+    mut commands: Commands,
+    q_missile: Query<
+        (&Transform, &MissileWarhead, &ProjectileOwner),
+        With<MissileProjectileMarker>,
+    >,
+) {
+    let Ok((transform, warhead, owner)) = q_missile.get(hit.other) else {
+        return;
+    };
+
+    detonate_missile(
+        &mut commands,
+        hit.other,
+        transform.translation,
+        warhead,
+        owner,
+    );
+}
+
+fn detonate_missile(
+    commands: &mut Commands,
+    missile: Entity,
+    position: Vec3,
+    warhead: &MissileWarhead,
+    owner: &ProjectileOwner,
+) {
+    debug!(
+        "detonate_missile: missile {:?} detonated at {:?}",
+        missile, position
+    );
+    commands.trigger(AreaDamage {
+        center: position,
+        radius: warhead.blast_radius,
+        damage: warhead.blast_damage,
+        falloff: DamageFalloff::Linear,
+        impulse: 0.0,
+        source: Some(**owner),
+    });
+    commands.entity(missile).try_despawn();
+}
+
+fn insert_missile_launcher_section_render(
+    add: On<Add, MissileLauncherSectionMarker>,
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    q_launcher: Query<&MissileLauncherSectionRenderMesh, With<MissileLauncherSectionMarker>>,
+) {
+    let entity = add.entity;
+    trace!(
+        "insert_missile_launcher_section_render: entity {:?}",
+        entity
+    );
+
+    let Ok(render_mesh) = q_launcher.get(entity) else {
+        error!(
+            "insert_missile_launcher_section_render: entity {:?} not found in q_launcher",
+            entity
+        );
+        return;
+    };
+
+    match &**render_mesh {
+        Some(scene) => {
+            commands.entity(entity).insert((children![(
+                Name::new("Missile Launcher Section Body"),
+                SectionRenderOf(entity),
+                SceneRoot(scene.clone()),
+            ),],));
+        }
+        None => {
+            let tube_mesh = meshes.add(Cylinder::new(0.15, 0.6));
+            let tube_material = materials.add(Color::srgb(0.2, 0.2, 0.2));
+
+            commands.entity(entity).insert((children![
+                (
+                    Name::new("Missile Launcher Section Body (A)"),
+                    SectionRenderOf(entity),
+                    Mesh3d(meshes.add(Cuboid::new(1.0, 1.0, 1.0))),
+                    MeshMaterial3d(materials.add(Color::srgb(0.4, 0.3, 0.3))),
+                ),
+                (
+                    Name::new("Missile Launcher Section Tube (B)"),
+                    SectionRenderOf(entity),
+                    Mesh3d(tube_mesh.clone()),
+                    MeshMaterial3d(tube_material.clone()),
+                    Transform::from_xyz(0.25, 0.0, -0.5)
+                        .with_rotation(Quat::from_rotation_x(std::f32::consts::FRAC_PI_2)),
+                ),
+                (
+                    Name::new("Missile Launcher Section Tube (C)"),
+                    SectionRenderOf(entity),
+                    Mesh3d(tube_mesh),
+                    MeshMaterial3d(tube_material),
+                    Transform::from_xyz(-0.25, 0.0, -0.5)
+                        .with_rotation(Quat::from_rotation_x(std::f32::consts::FRAC_PI_2)),
+                )
+            ],));
+        }
+    }
+}
+
+fn insert_missile_render(
+    add: On<Add, MissileProjectileMarker>,
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    q_render_mesh: Query<&MissileProjectileRenderMesh>,
+) {
+    let entity = add.entity;
+    trace!("insert_missile_render: entity {:?}", entity);
+
+    let Ok(render_mesh) = q_render_mesh.get(entity) else {
+        error!(
+            "insert_missile_render: entity {:?} not found in q_render_mesh",
+            entity
+        );
+        return;
+    };
+
+    match &**render_mesh {
+        Some(scene_handle) => {
+            commands.entity(entity).insert((children![(
+                Name::new("Missile Render"),
+                SceneRoot(scene_handle.clone()),
+            ),],));
+        }
+        None => {
+            commands.entity(entity).insert((children![(
+                Name::new("Missile Render"),
+                Transform::from_rotation(Quat::from_rotation_x(std::f32::consts::FRAC_PI_2)),
+                Mesh3d(meshes.add(Capsule3d::new(0.1, 0.4))),
+                MeshMaterial3d(materials.add(StandardMaterial {
+                    base_color: Color::srgb(0.8, 0.8, 0.8),
+                    emissive: LinearRgba::rgb(2.0, 0.6, 0.2),
+                    ..default()
+                })),
+            ),],));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn steers_with_a_limited_turn_rate() {
+        // Arrange
+        let direction = Vec3::NEG_Z;
+        let target_direction = Vec3::X;
+
+        // Act
+        let limited = steer_towards(direction, target_direction, std::f32::consts::FRAC_PI_4);
+        let reached = steer_towards(direction, target_direction, std::f32::consts::PI);
+
+        // Assert
+        assert!((limited.angle_between(direction) - std::f32::consts::FRAC_PI_4).abs() < 1e-4);
+        assert!(
+            (limited.angle_between(target_direction) - std::f32::consts::FRAC_PI_4).abs() < 1e-4
+        );
+        assert!(reached.abs_diff_eq(target_direction, 1e-6));
+    }
+
+    #[test]
+    fn locks_on_after_the_target_stays_in_front() {
+        // Arrange
+        let target = Entity::from_raw_u32(1).unwrap();
+        let other = Entity::from_raw_u32(2).unwrap();
+        let mut lock = MissileLauncherSectionLock::new(1.0, 100.0, 0.5);
+
+        // Act
+        lock.update(Some(target), true, 0.6);
+        let locking = lock.is_locked();
+        lock.update(Some(target), true, 0.6);
+        let locked = lock.is_locked();
+        lock.update(Some(other), true, 0.6);
+
+        // Assert
+        assert!(!locking);
+        assert!(locked);
+        assert!(!lock.is_locked());
+        assert!(lock.can_lock(Vec3::NEG_Z, Vec3::new(0.0, 0.0, -50.0)));
+        assert!(!lock.can_lock(Vec3::NEG_Z, Vec3::new(0.0, 0.0, 50.0)));
+        assert!(!lock.can_lock(Vec3::NEG_Z, Vec3::new(0.0, 0.0, -500.0)));
+    }
+}
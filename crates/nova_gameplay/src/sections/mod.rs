@@ -3,16 +3,25 @@
 use bevy::prelude::*;
 
 pub mod base_section;
+pub mod battery_section;
+pub mod beam_section;
 pub mod controller_section;
+pub mod graph;
 pub mod hull_section;
+pub mod missile_launcher_section;
+pub mod power;
+pub mod reactor_section;
+pub mod shield_section;
 pub mod thruster_section;
 pub mod turret_section;
 
 pub mod prelude {
     pub use super::{
-        base_section::prelude::*, controller_section::prelude::*, hull_section::prelude::*,
-        thruster_section::prelude::*, turret_section::prelude::*, SpaceshipRootMarker,
-        SpaceshipSectionPlugin, SpaceshipSectionSystems,
+        base_section::prelude::*, battery_section::prelude::*, beam_section::prelude::*,
+        controller_section::prelude::*, graph::prelude::*, hull_section::prelude::*,
+        missile_launcher_section::prelude::*, power::prelude::*, reactor_section::prelude::*,
+        shield_section::prelude::*, thruster_section::prelude::*, turret_section::prelude::*,
+        SpaceshipRootMarker, SpaceshipSectionPlugin, SpaceshipSectionSystems,
     };
 }
 
@@ -45,6 +54,22 @@ impl Plugin for SpaceshipSectionPlugin {
             controller_section::ControllerSectionPlugin {
                 render: self.render,
             },
+            reactor_section::ReactorSectionPlugin {
+                render: self.render,
+            },
+            battery_section::BatterySectionPlugin {
+                render: self.render,
+            },
+            missile_launcher_section::MissileLauncherSectionPlugin {
+                render: self.render,
+            },
+            beam_section::BeamSectionPlugin {
+                render: self.render,
+            },
+            shield_section::ShieldSectionPlugin {
+                render: self.render,
+            },
+            power::SpaceshipPowerPlugin,
         ));
     }
 }
@@ -0,0 +1,200 @@
+//! The power budget of a spaceship. Reactor sections generate power, battery sections store it,
+//! and thrusters, controllers, weapons and shields draw from it. When the power runs out, their
+//! output degrades.
+//!
+//! Only spaceships with a `SpaceshipPower` component are limited by power, so the prototypes
+//! built from a few sections keep working without a reactor.
+
+use bevy::prelude::*;
+
+use crate::prelude::*;
+
+pub mod prelude {
+    pub use super::{
+        SpaceshipPower, SpaceshipPowerPlugin, BEAM_POWER_PER_SECOND, CONTROLLER_POWER_PER_TORQUE,
+        MISSILE_LAUNCHER_POWER_PER_SHOT, REACTOR_BUFFER_SECONDS, SHIELD_POWER_PER_POINT,
+        THRUSTER_POWER_PER_MAGNITUDE, TURRET_POWER_PER_SHOT,
+    };
+}
+
+/// The power drawn per second by a thruster at full thrust, per unit of magnitude.
+pub const THRUSTER_POWER_PER_MAGNITUDE: f32 = 1.0;
+/// The power drawn per second by a controller, per unit of torque applied.
+pub const CONTROLLER_POWER_PER_TORQUE: f32 = 0.01;
+/// The power drawn by a turret for every shot.
+pub const TURRET_POWER_PER_SHOT: f32 = 0.05;
+/// The power drawn by a missile launcher for every missile.
+pub const MISSILE_LAUNCHER_POWER_PER_SHOT: f32 = 2.0;
+/// The power drawn per second by a beam while it fires.
+pub const BEAM_POWER_PER_SECOND: f32 = 3.0;
+/// The power drawn by a shield for every point of shield it regenerates.
+pub const SHIELD_POWER_PER_POINT: f32 = 0.1;
+/// The seconds of generation that the reactors hold on their own, so a spaceship without
+/// batteries can still pay for a shot that costs more than one step of generation.
+pub const REACTOR_BUFFER_SECONDS: f32 = 1.0;
+
+/// The power budget of a spaceship. The generation and capacity are computed from the reactor and
+/// battery sections of the spaceship.
+#[derive(Component, Clone, Debug, Default, Reflect)]
+pub struct SpaceshipPower {
+    /// The power stored in the batteries and the reactors.
+    pub stored: f32,
+    /// The maximum power that the batteries can store.
+    pub capacity: f32,
+    /// The power generated per second by the reactors.
+    pub generation: f32,
+}
+
+impl SpaceshipPower {
+    /// The maximum power that the spaceship can hold, in the batteries and in the reactors.
+    pub fn max_stored(&self) -> f32 {
+        self.capacity + self.generation * REACTOR_BUFFER_SECONDS
+    }
+
+    /// The stored power as a fraction of the maximum, in `[0, 1]`.
+    pub fn fraction(&self) -> f32 {
+        let max_stored = self.max_stored();
+        if max_stored <= 0.0 {
+            return 0.0;
+        }
+
+        (self.stored / max_stored).clamp(0.0, 1.0)
+    }
+
+    /// Draw as much as possible of the given amount of power. Returns the fraction of the amount
+    /// that was available, used to scale down the output of the consumer.
+    pub fn draw(&mut self, amount: f32) -> f32 {
+        if amount <= 0.0 {
+            return 1.0;
+        }
+
+        let fraction = (self.stored / amount).clamp(0.0, 1.0);
+        self.stored -= amount * fraction;
+        fraction
+    }
+
+    /// Draw the given amount of power only if all of it is available.
+    pub fn try_draw(&mut self, amount: f32) -> bool {
+        if self.stored < amount {
+            return false;
+        }
+
+        self.stored -= amount;
+        true
+    }
+
+    /// Add the power generated during `delta` seconds, up to the maximum.
+    fn generate(&mut self, delta: f32) {
+        self.stored = (self.stored + self.generation * delta).min(self.max_stored());
+    }
+}
+
+/// A plugin that updates the power budget of the spaceships.
+#[derive(Default)]
+pub struct SpaceshipPowerPlugin;
+
+impl Plugin for SpaceshipPowerPlugin {
+    fn build(&self, app: &mut App) {
+        debug!("SpaceshipPowerPlugin: build");
+
+        // The consumers in `FixedUpdate` run after the budget is updated, the ones in `Update`
+        // draw from what the fixed steps of the frame generated.
+        app.add_systems(
+            FixedUpdate,
+            update_spaceship_power.in_set(super::SpaceshipSectionSystems),
+        );
+    }
+}
+
+pub(super) fn update_spaceship_power(
+    mut q_spaceship: Query<(&mut SpaceshipPower, &Children), With<SpaceshipRootMarker>>,
+    q_reactor: Query<&ReactorSectionOutput, With<ReactorSectionMarker>>,
+    q_battery: Query<&BatterySectionCapacity, With<BatterySectionMarker>>,
+    time: Res<Time>,
+) {
+    for (mut power, children) in &mut q_spaceship {
+        // Sections can be lost, so the budget is computed again every step
+        power.generation = children
+            .iter()
+            .filter_map(|child| q_reactor.get(child).ok())
+            .map(|output| **output)
+            .sum();
+        power.capacity = children
+            .iter()
+            .filter_map(|child| q_battery.get(child).ok())
+            .map(|capacity| **capacity)
+            .sum();
+
+        // Spaceships start with full batteries
+        if power.is_added() {
+            power.stored = power.max_stored();
+        }
+
+        power.generate(time.delta_secs());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn draws_what_is_available() {
+        // Arrange
+        let mut power = SpaceshipPower {
+            stored: 1.0,
+            capacity: 10.0,
+            generation: 0.0,
+        };
+
+        // Act
+        let full = power.draw(0.5);
+        let partial = power.draw(1.0);
+        let empty = power.draw(1.0);
+
+        // Assert
+        assert_eq!(full, 1.0);
+        assert_eq!(partial, 0.5);
+        assert_eq!(empty, 0.0);
+        assert_eq!(power.stored, 0.0);
+    }
+
+    #[test]
+    fn generates_up_to_capacity() {
+        // Arrange
+        let mut power = SpaceshipPower {
+            stored: 11.0,
+            capacity: 10.0,
+            generation: 4.0,
+        };
+
+        // Act
+        power.generate(1.0);
+
+        // Assert
+        assert_eq!(power.stored, 14.0);
+        assert_eq!(power.fraction(), 1.0);
+    }
+
+    #[test]
+    fn reactors_pay_for_shots_without_batteries() {
+        // Arrange
+        let mut power = SpaceshipPower {
+            stored: 0.0,
+            capacity: 0.0,
+            generation: 2.0,
+        };
+
+        // Act
+        let early = power.try_draw(MISSILE_LAUNCHER_POWER_PER_SHOT);
+        for _ in 0..64 {
+            power.generate(1.0 / 64.0);
+        }
+        let shot = power.try_draw(MISSILE_LAUNCHER_POWER_PER_SHOT);
+
+        // Assert
+        assert!(!early);
+        assert!(shot);
+        assert_eq!(power.stored, 0.0);
+    }
+}
@@ -0,0 +1,120 @@
+//! A reactor section generates the power used by the other sections of a spaceship.
+
+use bevy::prelude::*;
+
+use crate::prelude::SectionRenderOf;
+
+pub mod prelude {
+    pub use super::{
+        reactor_section, ReactorSectionConfig, ReactorSectionMarker, ReactorSectionOutput,
+        ReactorSectionPlugin,
+    };
+}
+
+const REACTOR_SECTION_DEFAULT_OUTPUT: f32 = 2.0;
+
+/// Configuration for a reactor section.
+#[derive(Clone, Debug, Reflect)]
+pub struct ReactorSectionConfig {
+    /// The power generated per second.
+    pub output: f32,
+    /// The render mesh of the section, defaults to prototype mesh if None.
+    pub render_mesh: Option<Handle<Scene>>,
+}
+
+impl Default for ReactorSectionConfig {
+    fn default() -> Self {
+        Self {
+            output: REACTOR_SECTION_DEFAULT_OUTPUT,
+            render_mesh: None,
+        }
+    }
+}
+
+/// Helper function to create a reactor section entity bundle.
+pub fn reactor_section(config: ReactorSectionConfig) -> impl Bundle {
+    debug!("reactor_section: config {:?}", config);
+
+    (
+        ReactorSectionMarker,
+        ReactorSectionOutput(config.output),
+        ReactorSectionRenderMesh(config.render_mesh),
+    )
+}
+
+/// Marker component for reactor sections.
+#[derive(Component, Clone, Debug, Reflect)]
+pub struct ReactorSectionMarker;
+
+/// The power generated per second by the reactor section.
+#[derive(Component, Clone, Debug, Deref, DerefMut, Reflect)]
+pub struct ReactorSectionOutput(pub f32);
+
+#[derive(Component, Clone, Debug, Deref, DerefMut, Reflect)]
+struct ReactorSectionRenderMesh(Option<Handle<Scene>>);
+
+/// A plugin that enables the ReactorSection component and its related systems.
+#[derive(Default)]
+pub struct ReactorSectionPlugin {
+    pub render: bool,
+}
+
+impl Plugin for ReactorSectionPlugin {
+    fn build(&self, app: &mut App) {
+        debug!("ReactorSectionPlugin: build");
+
+        if self.render {
+            app.add_observer(insert_reactor_section_render);
+        }
+    }
+}
+
+fn insert_reactor_section_render(
+    add: On<Add, ReactorSectionMarker>,
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    q_reactor: Query<&ReactorSectionRenderMesh, With<ReactorSectionMarker>>,
+) {
+    let entity = add.entity;
+    trace!("insert_reactor_section_render: entity {:?}", entity);
+
+    let Ok(render_mesh) = q_reactor.get(entity) else {
+        error!(
+            "insert_reactor_section_render: entity {:?} not found in q_reactor",
+            entity
+        );
+        return;
+    };
+
+    match &**render_mesh {
+        Some(scene) => {
+            commands.entity(entity).insert((children![(
+                Name::new("Reactor Section Body"),
+                SectionRenderOf(entity),
+                SceneRoot(scene.clone()),
+            ),],));
+        }
+        None => {
+            commands.entity(entity).insert((children![
+                (
+                    Name::new("Reactor Section Body (A)"),
+                    SectionRenderOf(entity),
+                    Mesh3d(meshes.add(Cuboid::new(1.0, 1.0, 1.0))),
+                    MeshMaterial3d(materials.add(Color::srgb(0.3, 0.3, 0.3))),
+                ),
+                (
+                    Name::new("Reactor Section Core (B)"),
+                    SectionRenderOf(entity),
+                    Mesh3d(meshes.add(Sphere::new(0.3))),
+                    MeshMaterial3d(materials.add(StandardMaterial {
+                        base_color: Color::srgb(0.2, 0.9, 0.4),
+                        emissive: LinearRgba::rgb(0.4, 2.0, 0.8),
+                        ..default()
+                    })),
+                    Transform::from_xyz(0.0, 0.5, 0.0),
+                )
+            ],));
+        }
+    }
+}
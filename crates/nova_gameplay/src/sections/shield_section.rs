@@ -0,0 +1,521 @@
+//! A shield section generates a shield around its spaceship. The shield absorbs the damage done to
+//! the spaceship before it reaches its health, and regenerates when the spaceship has not been hit
+//! for a while.
+//!
+//! Only spaceships with a `SpaceshipShield` component are protected, the capacity and regeneration
+//! of the shield are computed from the shield sections of the spaceship. A hit on a section goes
+//! through the shield once, and the section takes only the damage that went through.
+
+use bevy::{ecs::world::DeferredWorld, prelude::*};
+use bevy_common_systems::prelude::*;
+
+use crate::prelude::*;
+
+pub mod prelude {
+    pub use super::{
+        shield_section, ShieldSectionConfig, ShieldSectionGenerator, ShieldSectionMarker,
+        ShieldSectionPlugin, SpaceshipShield,
+    };
+}
+
+const SHIELD_SECTION_DEFAULT_CAPACITY: f32 = 100.0;
+const SHIELD_SECTION_DEFAULT_REGEN_RATE: f32 = 10.0;
+const SHIELD_SECTION_DEFAULT_REGEN_DELAY: f32 = 3.0;
+
+/// The margin between the sections of a spaceship and its shield bubble.
+const SHIELD_BUBBLE_MARGIN: f32 = 1.5;
+
+/// Configuration for a shield section.
+#[derive(Clone, Debug, Reflect)]
+pub struct ShieldSectionConfig {
+    /// The damage that the shield can absorb when it is full.
+    pub capacity: f32,
+    /// The shield regenerated per second.
+    pub regen_rate: f32,
+    /// The time in seconds after the last hit before the shield regenerates.
+    pub regen_delay: f32,
+    /// The render mesh of the section, defaults to prototype mesh if None.
+    pub render_mesh: Option<Handle<Scene>>,
+}
+
+impl Default for ShieldSectionConfig {
+    fn default() -> Self {
+        Self {
+            capacity: SHIELD_SECTION_DEFAULT_CAPACITY,
+            regen_rate: SHIELD_SECTION_DEFAULT_REGEN_RATE,
+            regen_delay: SHIELD_SECTION_DEFAULT_REGEN_DELAY,
+            render_mesh: None,
+        }
+    }
+}
+
+/// Helper function to create a shield section entity bundle.
+pub fn shield_section(config: ShieldSectionConfig) -> impl Bundle {
+    debug!("shield_section: config {:?}", config);
+
+    (
+        ShieldSectionMarker,
+        ShieldSectionGenerator {
+            capacity: config.capacity,
+            regen_rate: config.regen_rate,
+            regen_delay: config.regen_delay,
+        },
+        ShieldSectionRenderMesh(config.render_mesh),
+    )
+}
+
+/// Marker component for shield sections.
+#[derive(Component, Clone, Debug, Reflect)]
+pub struct ShieldSectionMarker;
+
+/// What the shield section adds to the shield of its spaceship.
+#[derive(Component, Clone, Debug, Reflect)]
+pub struct ShieldSectionGenerator {
+    /// The damage that the shield can absorb when it is full.
+    pub capacity: f32,
+    /// The shield regenerated per second.
+    pub regen_rate: f32,
+    /// The time in seconds after the last hit before the shield regenerates.
+    pub regen_delay: f32,
+}
+
+#[derive(Component, Clone, Debug, Deref, DerefMut, Reflect)]
+struct ShieldSectionRenderMesh(Option<Handle<Scene>>);
+
+/// The shield of a spaceship. The capacity and regeneration are computed from the shield sections
+/// of the spaceship.
+#[derive(Component, Clone, Debug, Default, Reflect)]
+pub struct SpaceshipShield {
+    /// The damage that the shield can still absorb.
+    pub current: f32,
+    /// The damage that the shield can absorb when it is full.
+    pub capacity: f32,
+    /// The shield regenerated per second.
+    pub regen_rate: f32,
+    /// The time in seconds after the last hit before the shield regenerates.
+    pub regen_delay: f32,
+    /// The time in seconds since the last hit.
+    pub since_hit: f32,
+}
+
+impl SpaceshipShield {
+    /// The current shield as a fraction of the capacity, in `[0, 1]`.
+    pub fn fraction(&self) -> f32 {
+        if self.capacity <= 0.0 {
+            return 0.0;
+        }
+
+        (self.current / self.capacity).clamp(0.0, 1.0)
+    }
+
+    /// Whether the shield can absorb some damage.
+    pub fn is_up(&self) -> bool {
+        self.current > 0.0
+    }
+
+    /// Absorb as much as possible of the given damage. Returns the damage that went through the
+    /// shield. Any hit delays the regeneration, even when the shield is down.
+    pub fn absorb(&mut self, amount: f32) -> f32 {
+        if self.capacity <= 0.0 {
+            return amount;
+        }
+
+        self.since_hit = 0.0;
+        let absorbed = amount.clamp(0.0, self.current);
+        self.current -= absorbed;
+        amount - absorbed
+    }
+
+    /// Absorb a hit of the given kind, scripted damage goes through the shield. Returns the damage
+    /// that went through, which is what the section that was hit takes as well.
+    pub fn absorb_hit(&mut self, kind: DamageKind, amount: f32) -> f32 {
+        if kind == DamageKind::Scripted {
+            return amount;
+        }
+
+        self.absorb(amount)
+    }
+
+    /// Regenerate the shield during `delta` seconds, once the regeneration delay is over. Returns
+    /// the amount that was regenerated.
+    fn regenerate(&mut self, delta: f32) -> f32 {
+        self.since_hit += delta;
+        if self.since_hit < self.regen_delay {
+            return 0.0;
+        }
+
+        let previous = self.current;
+        self.current = (self.current + self.regen_rate * delta).min(self.capacity);
+        self.current - previous
+    }
+}
+
+/// The shield bubble rendered around a spaceship.
+#[derive(Component, Clone, Copy, Debug, Deref, DerefMut, Reflect)]
+struct SpaceshipShieldBubbleOf(Entity);
+
+/// A plugin that enables the ShieldSection component and its related systems.
+#[derive(Default)]
+pub struct ShieldSectionPlugin {
+    pub render: bool,
+}
+
+impl Plugin for ShieldSectionPlugin {
+    fn build(&self, app: &mut App) {
+        debug!("ShieldSectionPlugin: build");
+
+        app.init_resource::<HealthDamageHooks>();
+        app.world_mut()
+            .resource_mut::<HealthDamageHooks>()
+            .add(absorb_shield_damage);
+
+        app.add_systems(
+            FixedUpdate,
+            update_spaceship_shield
+                .after(super::power::update_spaceship_power)
+                .in_set(super::SpaceshipSectionSystems),
+        );
+
+        if self.render {
+            app.add_observer(insert_shield_section_render);
+            app.add_observer(insert_shield_bubble_render);
+
+            app.add_systems(
+                Update,
+                update_shield_bubble_render.in_set(super::SpaceshipSectionSystems),
+            );
+        }
+    }
+}
+
+/// Absorb the damage done to a spaceship with its shield. A hit on a section damages the spaceship
+/// first, and the section only takes what went through the shield.
+fn absorb_shield_damage(world: &mut DeferredWorld, damage: &mut HealthApplyDamage) {
+    if let Some(mut shield) = world.get_mut::<SpaceshipShield>(damage.target) {
+        damage.amount = shield.absorb_hit(damage.kind, damage.amount);
+    }
+}
+
+fn update_spaceship_shield(
+    mut q_spaceship: Query<
+        (&mut SpaceshipShield, Option<&mut SpaceshipPower>, &Children),
+        With<SpaceshipRootMarker>,
+    >,
+    q_generator: Query<&ShieldSectionGenerator, With<ShieldSectionMarker>>,
+    time: Res<Time>,
+) {
+    for (mut shield, power, children) in &mut q_spaceship {
+        // Sections can be lost, so the shield is computed again every step
+        let generators = children
+            .iter()
+            .filter_map(|child| q_generator.get(child).ok())
+            .collect::<Vec<_>>();
+        shield.capacity = generators.iter().map(|generator| generator.capacity).sum();
+        shield.regen_rate = generators
+            .iter()
+            .map(|generator| generator.regen_rate)
+            .sum();
+        shield.regen_delay = generators
+            .iter()
+            .map(|generator| generator.regen_delay)
+            .fold(0.0, f32::max);
+
+        // Spaceships start with full shields
+        if shield.is_added() {
+            shield.current = shield.capacity;
+        }
+        shield.current = shield.current.min(shield.capacity);
+
+        let regenerated = shield.regenerate(time.delta_secs());
+
+        // The regeneration slows down when the spaceship runs out of power
+        if let Some(mut power) = power {
+            let fraction = power.draw(regenerated * SHIELD_POWER_PER_POINT);
+            shield.current -= regenerated * (1.0 - fraction);
+        }
+    }
+}
+
+fn insert_shield_section_render(
+    add: On<Add, ShieldSectionMarker>,
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    q_shield: Query<&ShieldSectionRenderMesh, With<ShieldSectionMarker>>,
+) {
+    let entity = add.entity;
+    trace!("insert_shield_section_render: entity {:?}", entity);
+
+    let Ok(render_mesh) = q_shield.get(entity) else {
+        error!(
+            "insert_shield_section_render: entity {:?} not found in q_shield",
+            entity
+        );
+        return;
+    };
+
+    match &**render_mesh {
+        Some(scene) => {
+            commands.entity(entity).insert((children![(
+                Name::new("Shield Section Body"),
+                SectionRenderOf(entity),
+                SceneRoot(scene.clone()),
+            ),],));
+        }
+        None => {
+            commands.entity(entity).insert((children![
+                (
+                    Name::new("Shield Section Body (A)"),
+                    SectionRenderOf(entity),
+                    Mesh3d(meshes.add(Cuboid::new(1.0, 1.0, 1.0))),
+                    MeshMaterial3d(materials.add(Color::srgb(0.2, 0.3, 0.6))),
+                ),
+                (
+                    Name::new("Shield Section Emitter (B)"),
+                    SectionRenderOf(entity),
+                    Mesh3d(meshes.add(Torus::new(0.2, 0.35))),
+                    MeshMaterial3d(materials.add(StandardMaterial {
+                        base_color: Color::srgb(0.4, 0.7, 1.0),
+                        emissive: LinearRgba::rgb(0.8, 1.6, 4.0),
+                        ..default()
+                    })),
+                    Transform::from_xyz(0.0, 0.55, 0.0),
+                )
+            ],));
+        }
+    }
+}
+
+fn insert_shield_bubble_render(
+    add: On<Add, SpaceshipShield>,
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let entity = add.entity;
+    trace!("insert_shield_bubble_render: entity {:?}", entity);
+
+    // The bubble is a unit sphere, scaled to the size of the spaceship every frame
+    commands.entity(entity).with_child((
+        Name::new("Shield Bubble"),
+        SpaceshipShieldBubbleOf(entity),
+        Mesh3d(meshes.add(Sphere::new(1.0))),
+        MeshMaterial3d(materials.add(StandardMaterial {
+            base_color: Color::srgba(0.4, 0.7, 1.0, 0.2),
+            emissive: LinearRgba::rgb(0.2, 0.4, 1.0),
+            alpha_mode: AlphaMode::Blend,
+            cull_mode: None,
+            unlit: true,
+            ..default()
+        })),
+        Visibility::Hidden,
+    ));
+}
+
+fn update_shield_bubble_render(
+    q_spaceship: Query<(&SpaceshipShield, &Children), With<SpaceshipRootMarker>>,
+    q_section: Query<&Transform, (With<SectionMarker>, Without<SpaceshipShieldBubbleOf>)>,
+    mut q_bubble: Query<(
+        &SpaceshipShieldBubbleOf,
+        &MeshMaterial3d<StandardMaterial>,
+        &mut Transform,
+        &mut Visibility,
+    )>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    for (spaceship, material, mut transform, mut visibility) in &mut q_bubble {
+        let Ok((shield, children)) = q_spaceship.get(**spaceship) else {
+            error!(
+                "update_shield_bubble_render: entity {:?} not found in q_spaceship",
+                **spaceship
+            );
+            continue;
+        };
+
+        if !shield.is_up() {
+            *visibility = Visibility::Hidden;
+            continue;
+        }
+        *visibility = Visibility::Inherited;
+
+        let radius = children
+            .iter()
+            .filter_map(|child| q_section.get(child).ok())
+            .map(|section| section.translation.length())
+            .fold(0.0, f32::max)
+            + SHIELD_BUBBLE_MARGIN;
+        transform.scale = Vec3::splat(radius);
+
+        // The bubble fades as the shield is drained
+        if let Some(material) = materials.get_mut(&material.0) {
+            material
+                .base_color
+                .set_alpha(0.05 + 0.2 * shield.fraction());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use avian3d::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn absorbs_damage_until_the_shield_is_down() {
+        // Arrange
+        let mut shield = SpaceshipShield {
+            current: 10.0,
+            capacity: 20.0,
+            ..default()
+        };
+
+        // Act
+        let absorbed = shield.absorb(4.0);
+        let overflow = shield.absorb(10.0);
+        let down = shield.absorb(5.0);
+
+        // Assert
+        assert_eq!(absorbed, 0.0);
+        assert_eq!(overflow, 4.0);
+        assert_eq!(down, 5.0);
+        assert!(!shield.is_up());
+    }
+
+    #[test]
+    fn regenerates_after_the_delay() {
+        // Arrange
+        let mut shield = SpaceshipShield {
+            current: 0.0,
+            capacity: 20.0,
+            regen_rate: 10.0,
+            regen_delay: 2.0,
+            since_hit: 0.0,
+        };
+
+        // Act
+        let delayed = shield.regenerate(1.0);
+        let regenerated = shield.regenerate(1.0);
+        let full = shield.regenerate(5.0);
+        shield.absorb(5.0);
+        let hit = shield.regenerate(1.0);
+
+        // Assert
+        assert_eq!(delayed, 0.0);
+        assert_eq!(regenerated, 10.0);
+        assert_eq!(full, 10.0);
+        assert_eq!(hit, 0.0);
+        assert_eq!(shield.current, 15.0);
+    }
+
+    #[test]
+    fn sections_take_only_the_damage_through_a_breaking_shield() {
+        // Arrange
+        let mut app = crate::plugin::test_app();
+        let spaceship = app
+            .world_mut()
+            .spawn((
+                SpaceshipRootMarker,
+                RigidBody::Dynamic,
+                Transform::default(),
+                Health::new(100.0),
+                SpaceshipShield::default(),
+            ))
+            .id();
+        app.world_mut().spawn((
+            base_section(BaseSectionConfig::default()),
+            shield_section(ShieldSectionConfig {
+                capacity: 10.0,
+                regen_rate: 0.0,
+                regen_delay: 0.0,
+                render_mesh: None,
+            }),
+            Health::new(100.0),
+            Transform::default(),
+            ChildOf(spaceship),
+        ));
+        let hull = app
+            .world_mut()
+            .spawn((
+                base_section(BaseSectionConfig::default()),
+                Health::new(100.0),
+                Transform::from_translation(Vec3::Z),
+                ChildOf(spaceship),
+            ))
+            .id();
+        app.update();
+        app.update();
+
+        // Act
+        app.world_mut().trigger(AreaDamage {
+            center: Vec3::ZERO,
+            radius: 5.0,
+            damage: 30.0,
+            falloff: DamageFalloff::Constant,
+            impulse: 0.0,
+            source: None,
+        });
+        app.world_mut().flush();
+
+        // Assert
+        let world = app.world();
+        assert_eq!(
+            world.get::<SpaceshipShield>(spaceship).unwrap().current,
+            0.0
+        );
+        assert_eq!(world.get::<Health>(spaceship).unwrap().current, 80.0);
+        assert_eq!(world.get::<Health>(hull).unwrap().current, 80.0);
+    }
+
+    #[test]
+    fn absorbs_damage_sent_to_the_spaceship() {
+        // Arrange
+        let mut app = crate::plugin::test_app();
+        let spaceship = app
+            .world_mut()
+            .spawn((
+                SpaceshipRootMarker,
+                RigidBody::Dynamic,
+                Transform::default(),
+                Health::new(100.0),
+                SpaceshipShield::default(),
+            ))
+            .id();
+        app.world_mut().spawn((
+            base_section(BaseSectionConfig::default()),
+            shield_section(ShieldSectionConfig {
+                capacity: 10.0,
+                regen_rate: 0.0,
+                regen_delay: 0.0,
+                render_mesh: None,
+            }),
+            Health::new(100.0),
+            Transform::default(),
+            ChildOf(spaceship),
+        ));
+        app.update();
+        app.update();
+
+        // Act
+        app.world_mut().trigger(HealthApplyDamage {
+            target: spaceship,
+            source: None,
+            amount: 4.0,
+            kind: DamageKind::Projectile,
+        });
+        app.world_mut().trigger(HealthApplyDamage {
+            target: spaceship,
+            source: None,
+            amount: 3.0,
+            kind: DamageKind::Scripted,
+        });
+
+        // Assert
+        let world = app.world();
+        assert_eq!(
+            world.get::<SpaceshipShield>(spaceship).unwrap().current,
+            6.0
+        );
+        assert_eq!(world.get::<Health>(spaceship).unwrap().current, 97.0);
+    }
+}
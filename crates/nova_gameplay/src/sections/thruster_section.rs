@@ -8,7 +8,9 @@ use bevy::{
     shader::ShaderRef,
 };
 
-use crate::prelude::{SectionRenderOf, SpaceshipRootMarker};
+use crate::prelude::{
+    SectionRenderOf, SpaceshipPower, SpaceshipRootMarker, THRUSTER_POWER_PER_MAGNITUDE,
+};
 
 pub mod prelude {
     pub use super::{
@@ -90,7 +92,9 @@ impl Plugin for ThrusterSectionPlugin {
         );
         app.add_systems(
             FixedUpdate,
-            thruster_impulse_system.in_set(super::SpaceshipSectionSystems),
+            thruster_impulse_system
+                .after(super::power::update_spaceship_power)
+                .in_set(super::SpaceshipSectionSystems),
         );
     }
 }
@@ -106,10 +110,11 @@ fn thruster_impulse_system(
         ),
         With<ThrusterSectionMarker>,
     >,
-    mut q_root: Query<Forces, With<SpaceshipRootMarker>>,
+    mut q_root: Query<(Forces, Option<&mut SpaceshipPower>), With<SpaceshipRootMarker>>,
+    time: Res<Time>,
 ) {
     for (position, rotation, &ChildOf(root), magnitude, input) in &q_thruster {
-        let Ok(mut force) = q_root.get_mut(root) else {
+        let Ok((mut force, power)) = q_root.get_mut(root) else {
             error!(
                 "thruster_impulse_system: entity {:?} not found in q_root",
                 root
@@ -117,8 +122,16 @@ fn thruster_impulse_system(
             continue;
         };
 
+        // The thrust is weaker when the spaceship runs out of power
+        let efficiency = match power {
+            Some(mut power) => {
+                power.draw(**magnitude * **input * THRUSTER_POWER_PER_MAGNITUDE * time.delta_secs())
+            }
+            None => 1.0,
+        };
+
         let thrust_direction = rotation.mul_vec3(Vec3::NEG_Z).normalize();
-        let thrust_force = thrust_direction * **magnitude * **input;
+        let thrust_force = thrust_direction * **magnitude * **input * efficiency;
         let world_point = **position;
 
         force.apply_linear_impulse_at_point(thrust_force, world_point);
@@ -14,8 +14,9 @@ use crate::prelude::*;
 pub mod prelude {
     pub use super::{
         turret_section, TurretBulletProjectileMarker, TurretProjectileHooks,
-        TurretSectionBarrelMuzzleMarker, TurretSectionConfig, TurretSectionInput,
-        TurretSectionMarker, TurretSectionMuzzleEntity, TurretSectionPlugin,
+        TurretSectionBarrelFireState, TurretSectionBarrelMuzzleMarker, TurretSectionConfig,
+        TurretSectionHeat, TurretSectionInput, TurretSectionMagazine, TurretSectionMarker,
+        TurretSectionMuzzleEntity, TurretSectionMuzzleSpeed, TurretSectionPlugin,
         TurretSectionTargetInput,
     };
 }
@@ -51,6 +52,15 @@ pub struct TurretSectionConfig {
     pub muzzle_offset: Vec3,
     /// The fire rate of the turret in rounds per second.
     pub fire_rate: f32,
+    /// The number of rounds in a magazine. If None, the turret has unlimited ammunition.
+    pub magazine_size: Option<u32>,
+    /// The time in seconds to reload an empty magazine.
+    pub reload_time: f32,
+    /// The heat added by every shot, as a fraction of the maximum heat. If zero, the turret never
+    /// overheats.
+    pub heat_per_shot: f32,
+    /// The heat removed per second, as a fraction of the maximum heat.
+    pub cooling_rate: f32,
     /// The muzzle speed of the turret in units per second.
     pub muzzle_speed: f32,
     /// The projectile lifetime
@@ -80,6 +90,10 @@ impl Default for TurretSectionConfig {
             barrel_offset: Vec3::new(0.1, 0.2, 0.0),
             muzzle_offset: Vec3::new(0.0, 0.0, -0.5),
             fire_rate: 100.0,
+            magazine_size: None,
+            reload_time: 0.0,
+            heat_per_shot: 0.0,
+            cooling_rate: 0.0,
             muzzle_speed: 100.0,
             projectile_lifetime: 5.0,
             projectile_mass: 0.1,
@@ -96,6 +110,7 @@ pub fn turret_section(config: TurretSectionConfig) -> impl Bundle {
     (
         TurretSectionMarker,
         TurretSectionTargetInput(None),
+        TurretSectionMuzzleSpeed(config.muzzle_speed),
         TurretSectionConfigHelper(config),
         TurretSectionInput(false),
     )
@@ -122,6 +137,10 @@ pub struct TurretBulletProjectileMarker;
 #[derive(Component, Clone, Copy, Debug, Deref, DerefMut, Reflect)]
 pub struct TurretSectionTargetInput(pub Option<Vec3>);
 
+/// The muzzle speed of the turret section, used to lead moving targets.
+#[derive(Component, Clone, Copy, Debug, Deref, DerefMut, Reflect)]
+pub struct TurretSectionMuzzleSpeed(pub f32);
+
 /// The Turret "parent" entity of the turret component.
 #[derive(Component, Clone, Copy, Debug, Deref, DerefMut, Reflect)]
 struct TurretSectionPartOf(pub Entity);
@@ -144,9 +163,104 @@ struct TurretSectionBarrelRenderMesh(Option<Handle<Scene>>);
 #[derive(Component, Clone, Debug, Deref, DerefMut, Reflect)]
 struct TurretSectionConfigHelper(TurretSectionConfig);
 
+/// The time until the barrel of the turret can fire again.
 #[derive(Component, Clone, Debug, Deref, DerefMut, Reflect)]
 pub struct TurretSectionBarrelFireState(pub Timer);
 
+/// The ammunition of a turret section with a limited magazine. When the magazine is empty, the
+/// turret reloads it and can't shoot until the reload is finished.
+#[derive(Component, Clone, Debug, Reflect)]
+pub struct TurretSectionMagazine {
+    /// The rounds left in the magazine.
+    pub rounds: u32,
+    /// The number of rounds in a full magazine.
+    pub size: u32,
+    /// The reload timer, running only while the magazine is empty.
+    pub reload: Timer,
+}
+
+impl TurretSectionMagazine {
+    pub fn new(size: u32, reload_time: f32) -> Self {
+        Self {
+            rounds: size,
+            size,
+            reload: Timer::from_seconds(reload_time, TimerMode::Once),
+        }
+    }
+
+    pub fn is_reloading(&self) -> bool {
+        self.rounds == 0
+    }
+
+    /// Use one round. Returns false if the magazine is empty.
+    pub fn consume(&mut self) -> bool {
+        if self.rounds == 0 {
+            return false;
+        }
+
+        self.rounds -= 1;
+        if self.rounds == 0 {
+            self.reload.reset();
+        }
+        true
+    }
+
+    fn tick(&mut self, delta: std::time::Duration) {
+        if !self.is_reloading() {
+            return;
+        }
+
+        if self.reload.tick(delta).is_finished() {
+            self.rounds = self.size;
+        }
+    }
+}
+
+/// The heat of a turret section, as a fraction of the maximum heat. When the heat reaches the
+/// maximum, the turret overheats and can't shoot until it has cooled down completely.
+#[derive(Component, Clone, Debug, Reflect)]
+pub struct TurretSectionHeat {
+    /// The current heat in `[0, 1]`.
+    pub heat: f32,
+    /// The heat added by every shot.
+    pub per_shot: f32,
+    /// The heat removed per second.
+    pub cooling_rate: f32,
+    /// Whether the turret is locked out until it has cooled down.
+    pub overheated: bool,
+}
+
+impl TurretSectionHeat {
+    pub fn new(per_shot: f32, cooling_rate: f32) -> Self {
+        Self {
+            heat: 0.0,
+            per_shot,
+            cooling_rate,
+            overheated: false,
+        }
+    }
+
+    /// Add the heat of one shot. Returns false if the turret is overheated.
+    pub fn shoot(&mut self) -> bool {
+        if self.overheated {
+            return false;
+        }
+
+        self.heat = (self.heat + self.per_shot).min(1.0);
+        if self.heat >= 1.0 {
+            self.overheated = true;
+        }
+        true
+    }
+
+    fn cool(&mut self, delta: f32) {
+        self.heat = (self.heat - self.cooling_rate * delta).max(0.0);
+        if self.heat <= 0.0 {
+            self.overheated = false;
+        }
+    }
+}
+
 #[derive(Component, Clone, Debug, Deref, DerefMut, Reflect)]
 struct TurretSectionBarrelMuzzleEffect(Option<Handle<EffectAsset>>);
 
@@ -175,10 +289,6 @@ struct TurretSectionBarrelMuzzleEffectMarker;
 #[derive(Component, Clone, Copy, Debug, Deref, DerefMut, Reflect)]
 pub struct TurretSectionMuzzleEntity(pub Entity);
 
-/// The spaceship entity that owns the projectile.
-#[derive(Component, Clone, Debug, Reflect)]
-struct TurretBulletProjectileOwner(pub Entity);
-
 /// A plugin that enables the TurretSection component and its related systems.
 #[derive(Default)]
 pub struct TurretSectionPlugin {
@@ -211,6 +321,8 @@ impl Plugin for TurretSectionPlugin {
             Update,
             (
                 update_barrel_fire_state,
+                update_turret_magazine,
+                update_turret_heat,
                 sync_turret_rotator_yaw_system,
                 sync_turret_rotator_pitch_system,
                 shoot_spawn_projectile,
@@ -234,7 +346,7 @@ impl Plugin for TurretSectionPlugin {
 // It can have read-only access to queries, resources, and other system parameters.
 #[derive(SystemParam)]
 pub struct TurretProjectileHooks<'w, 's> {
-    projectile_query: Query<'w, 's, (Read<TurretBulletProjectileOwner>,)>,
+    projectile_query: Query<'w, 's, (Read<ProjectileOwner>,)>,
     colider_of_query: Query<'w, 's, (Read<ColliderOf>,)>,
 }
 
@@ -242,7 +354,7 @@ impl CollisionHooks for TurretProjectileHooks<'_, '_> {
     fn filter_pairs(&self, collider1: Entity, collider2: Entity, _commands: &mut Commands) -> bool {
         // Don't allow collision between a projectile and its owner
 
-        if let Ok((&TurretBulletProjectileOwner(owner),)) = self.projectile_query.get(collider1) {
+        if let Ok((&ProjectileOwner(owner),)) = self.projectile_query.get(collider1) {
             if let Ok((&ColliderOf { body },)) = self.colider_of_query.get(collider2) {
                 if owner == body {
                     return false;
@@ -250,7 +362,7 @@ impl CollisionHooks for TurretProjectileHooks<'_, '_> {
             }
         }
 
-        if let Ok((&TurretBulletProjectileOwner(owner),)) = self.projectile_query.get(collider2) {
+        if let Ok((&ProjectileOwner(owner),)) = self.projectile_query.get(collider2) {
             if let Ok((&ColliderOf { body },)) = self.colider_of_query.get(collider1) {
                 if owner == body {
                     return false;
@@ -283,13 +395,82 @@ fn insert_turret_section(
     let mut timer = Timer::from_seconds(interval, TimerMode::Once);
     timer.finish(); // Ready to fire immediately
 
+    spawn_turret_rotators(
+        &mut commands,
+        turret,
+        TurretRotatorConfig::from(&config),
+        (
+            Name::new("Turret Barrel Muzzle"),
+            TurretSectionBarrelFireState(timer),
+            TurretSectionBarrelMuzzleEffect(config.muzzle_effect.clone()),
+        ),
+    );
+
+    if let Some(size) = config.magazine_size {
+        commands
+            .entity(turret)
+            .insert(TurretSectionMagazine::new(size, config.reload_time));
+    }
+
+    if config.heat_per_shot > 0.0 {
+        commands.entity(turret).insert(TurretSectionHeat::new(
+            config.heat_per_shot,
+            config.cooling_rate,
+        ));
+    }
+}
+
+/// The yaw and pitch rotators of a turret, shared by the sections that aim like a turret.
+#[derive(Clone, Debug)]
+pub(crate) struct TurretRotatorConfig {
+    pub yaw_speed: f32,
+    pub pitch_speed: f32,
+    pub min_pitch: Option<f32>,
+    pub max_pitch: Option<f32>,
+    pub render_mesh_base: Option<Handle<Scene>>,
+    pub base_offset: Vec3,
+    pub render_mesh_yaw: Option<Handle<Scene>>,
+    pub yaw_offset: Vec3,
+    pub render_mesh_pitch: Option<Handle<Scene>>,
+    pub pitch_offset: Vec3,
+    pub render_mesh_barrel: Option<Handle<Scene>>,
+    pub barrel_offset: Vec3,
+    pub muzzle_offset: Vec3,
+}
+
+impl From<&TurretSectionConfig> for TurretRotatorConfig {
+    fn from(config: &TurretSectionConfig) -> Self {
+        Self {
+            yaw_speed: config.yaw_speed,
+            pitch_speed: config.pitch_speed,
+            min_pitch: config.min_pitch,
+            max_pitch: config.max_pitch,
+            render_mesh_base: config.render_mesh_base.clone(),
+            base_offset: config.base_offset,
+            render_mesh_yaw: config.render_mesh_yaw.clone(),
+            yaw_offset: config.yaw_offset,
+            render_mesh_pitch: config.render_mesh_pitch.clone(),
+            pitch_offset: config.pitch_offset,
+            render_mesh_barrel: config.render_mesh_barrel.clone(),
+            barrel_offset: config.barrel_offset,
+            muzzle_offset: config.muzzle_offset,
+        }
+    }
+}
+
+/// Spawn the rotator hierarchy of a turret under the given section and return the muzzle entity.
+/// The rotators aim the muzzle at the `TurretSectionTargetInput` of the section.
+pub(crate) fn spawn_turret_rotators(
+    commands: &mut Commands,
+    turret: Entity,
+    config: TurretRotatorConfig,
+    muzzle: impl Bundle,
+) -> Entity {
     let muzzle = commands
         .spawn((
-            Name::new("Turret Barrel Muzzle"),
+            muzzle,
             TurretSectionBarrelMuzzleMarker,
             TurretSectionPartOf(turret),
-            TurretSectionBarrelFireState(timer),
-            TurretSectionBarrelMuzzleEffect(config.muzzle_effect.clone()),
             Transform::from_translation(config.muzzle_offset),
             Visibility::Inherited,
         ))
@@ -388,6 +569,8 @@ fn insert_turret_section(
         .entity(turret)
         .insert((TurretSectionMuzzleEntity(muzzle),))
         .add_child(rotator_base);
+
+    muzzle
 }
 
 fn update_barrel_fire_state(
@@ -399,8 +582,26 @@ fn update_barrel_fire_state(
     }
 }
 
+fn update_turret_magazine(
+    mut q_turret: Query<&mut TurretSectionMagazine, With<TurretSectionMarker>>,
+    time: Res<Time>,
+) {
+    for mut magazine in &mut q_turret {
+        magazine.tick(time.delta());
+    }
+}
+
+fn update_turret_heat(
+    mut q_turret: Query<&mut TurretSectionHeat, With<TurretSectionMarker>>,
+    time: Res<Time>,
+) {
+    for mut heat in &mut q_turret {
+        heat.cool(time.delta_secs());
+    }
+}
+
 fn update_turret_target_yaw_system(
-    q_turret: Query<&TurretSectionTargetInput, With<TurretSectionMarker>>,
+    q_turret: Query<&TurretSectionTargetInput>,
     mut q_rotator_yaw_base: Query<
         (
             &mut SmoothLookRotationTarget,
@@ -461,7 +662,7 @@ fn update_turret_target_yaw_system(
 }
 
 fn update_turret_target_pitch_system(
-    q_turret: Query<&TurretSectionTargetInput, With<TurretSectionMarker>>,
+    q_turret: Query<&TurretSectionTargetInput>,
     mut q_rotator_pitch_base: Query<
         (
             &mut SmoothLookRotationTarget,
@@ -555,17 +756,24 @@ fn sync_turret_rotator_pitch_system(
 
 fn shoot_spawn_projectile(
     mut commands: Commands,
-    q_spaceship: Query<
-        (&LinearVelocity, &AngularVelocity, &ComputedCenterOfMass),
+    mut q_spaceship: Query<
+        (
+            &LinearVelocity,
+            &AngularVelocity,
+            &ComputedCenterOfMass,
+            Option<&mut SpaceshipPower>,
+        ),
         With<SpaceshipRootMarker>,
     >,
-    q_turret: Query<
+    mut q_turret: Query<
         (
             Entity,
             &TurretSectionMuzzleEntity,
             &ChildOf,
             &TurretSectionConfigHelper,
             &TurretSectionInput,
+            Option<&mut TurretSectionMagazine>,
+            Option<&mut TurretSectionHeat>,
         ),
         With<TurretSectionMarker>,
     >,
@@ -574,17 +782,18 @@ fn shoot_spawn_projectile(
     // should be fine, since it will not be called frequently.
     transform_helper: TransformHelper,
 ) {
-    for (turret, muzzle, ChildOf(spaceship), config, input) in &q_turret {
+    for (turret, muzzle, ChildOf(spaceship), config, input, mut magazine, mut heat) in &mut q_turret
+    {
         if !**input {
             continue;
         }
 
-        let Ok((lin_vel, ang_vel, center)) = q_spaceship.get(*spaceship) else {
+        let Ok((lin_vel, ang_vel, center, power)) = q_spaceship.get_mut(*spaceship) else {
             error!(
                 "on_shoot_spawn_projectile: entity {:?} not found in q_spaceship",
                 spaceship
             );
-            return;
+            continue;
         };
 
         let Ok(mut fire_state) = q_muzzle.get_mut(**muzzle) else {
@@ -592,11 +801,18 @@ fn shoot_spawn_projectile(
                 "on_shoot_spawn_projectile: entity {:?} not found in q_muzzle",
                 **muzzle
             );
-            return;
+            continue;
         };
 
         if !fire_state.is_finished() {
-            return;
+            continue;
+        }
+
+        // A turret can't shoot while reloading or overheated
+        if magazine.as_ref().is_some_and(|m| m.is_reloading())
+            || heat.as_ref().is_some_and(|h| h.overheated)
+        {
+            continue;
         }
 
         let Ok(muzzle_transform) = transform_helper.compute_global_transform(**muzzle) else {
@@ -604,9 +820,16 @@ fn shoot_spawn_projectile(
                 "on_shoot_spawn_projectile: entity {:?} global transform not found",
                 **muzzle
             );
-            return;
+            continue;
         };
 
+        // Every shot needs power, a spaceship without enough power can't shoot
+        if let Some(mut power) = power {
+            if !power.try_draw(TURRET_POWER_PER_SHOT) {
+                continue;
+            }
+        }
+
         let muzzle_direction = muzzle_transform.forward();
         let projectile_position = muzzle_transform.translation();
         let projectile_rotation = muzzle_transform.rotation();
@@ -627,7 +850,7 @@ fn shoot_spawn_projectile(
         commands.spawn((
             Name::new("Projectile"),
             TurretBulletProjectileMarker,
-            TurretBulletProjectileOwner(*spaceship),
+            ProjectileOwner(*spaceship),
             projectile_transform,
             RigidBody::Dynamic,
             LinearVelocity(linear_velocity),
@@ -644,6 +867,13 @@ fn shoot_spawn_projectile(
 
         // Reset the fire state timer
         fire_state.reset();
+
+        if let Some(magazine) = magazine.as_mut() {
+            magazine.consume();
+        }
+        if let Some(heat) = heat.as_mut() {
+            heat.shoot();
+        }
     }
 }
 
@@ -1054,11 +1284,8 @@ fn insert_turret_barrel_muzzle_effect(
     let entity = add.entity;
     trace!("insert_turret_barrel_muzzle_effect: entity {:?}", entity);
 
+    // Only the turrets that shoot projectiles have a muzzle effect
     let Ok(effect_handle) = q_effect.get(entity) else {
-        error!(
-            "insert_turret_barrel_muzzle_effect: entity {:?} not found in q_effect",
-            entity
-        );
         return;
     };
 
@@ -1150,3 +1377,50 @@ fn insert_turret_barrel_muzzle_effect(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn reloads_an_empty_magazine() {
+        // Arrange
+        let mut magazine = TurretSectionMagazine::new(2, 1.0);
+
+        // Act
+        let first = magazine.consume();
+        let second = magazine.consume();
+        let empty = magazine.consume();
+        magazine.tick(Duration::from_secs_f32(0.5));
+        let reloading = magazine.is_reloading();
+        magazine.tick(Duration::from_secs_f32(0.5));
+
+        // Assert
+        assert!(first && second);
+        assert!(!empty);
+        assert!(reloading);
+        assert_eq!(magazine.rounds, 2);
+    }
+
+    #[test]
+    fn overheats_until_cooled_down() {
+        // Arrange
+        let mut heat = TurretSectionHeat::new(0.5, 1.0);
+
+        // Act
+        heat.shoot();
+        heat.shoot();
+        let locked = !heat.shoot();
+        heat.cool(0.5);
+        let still_locked = heat.overheated;
+        heat.cool(0.5);
+
+        // Assert
+        assert!(locked);
+        assert!(still_locked);
+        assert!(!heat.overheated);
+        assert!(heat.shoot());
+    }
+}
@@ -8,9 +8,10 @@ use crate::prelude::*;
 
 pub mod prelude {
     pub use super::{
-        BaseScenarioObjectConfig, DebugMessageActionConfig, EventActionConfig,
-        NextScenarioActionConfig, ObjectiveCompleteActionConfig, ScenarioObjectConfig,
-        ScenarioObjectKind, VariableSetActionConfig,
+        BaseScenarioObjectConfig, CancelTimerActionConfig, DebugMessageActionConfig,
+        DelayActionConfig, EventActionConfig, NextScenarioActionConfig,
+        ObjectiveCompleteActionConfig, ScenarioObjectConfig, ScenarioObjectKind, TimerActionConfig,
+        VariableSetActionConfig,
     };
 }
 
@@ -22,6 +23,9 @@ pub enum EventActionConfig {
     ObjectiveComplete(ObjectiveCompleteActionConfig),
     SpawnScenarioObject(ScenarioObjectConfig),
     NextScenario(NextScenarioActionConfig),
+    Delay(DelayActionConfig),
+    Timer(TimerActionConfig),
+    CancelTimer(CancelTimerActionConfig),
 }
 
 impl EventAction<NovaEventWorld> for EventActionConfig {
@@ -45,11 +49,20 @@ impl EventAction<NovaEventWorld> for EventActionConfig {
             EventActionConfig::NextScenario(config) => {
                 config.action(world, info);
             }
+            EventActionConfig::Delay(config) => {
+                config.action(world, info);
+            }
+            EventActionConfig::Timer(config) => {
+                config.action(world, info);
+            }
+            EventActionConfig::CancelTimer(config) => {
+                config.action(world, info);
+            }
         }
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub struct VariableSetActionConfig {
     pub key: String,
     pub expression: VariableExpressionNode,
@@ -71,7 +84,7 @@ impl EventAction<NovaEventWorld> for VariableSetActionConfig {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub struct DebugMessageActionConfig {
     pub message: String,
 }
@@ -82,7 +95,7 @@ impl EventAction<NovaEventWorld> for DebugMessageActionConfig {
     }
 }
 
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, serde::Serialize, serde::Deserialize)]
 pub struct NextScenarioActionConfig {
     pub scenario_id: String,
     pub linger: bool,
@@ -100,7 +113,93 @@ impl EventAction<NovaEventWorld> for ObjectiveActionConfig {
     }
 }
 
+/// Run the actions after a delay, with the info of the event that scheduled them.
 #[derive(Clone, Debug)]
+pub struct DelayActionConfig {
+    pub seconds: f32,
+    pub actions: Vec<EventActionConfig>,
+    /// The index of the event of the delay in its scenario, followed by the index of the action in
+    /// every list of actions down to the delay. It is set when the scenario is spawned, so that a
+    /// pending delay can be saved and found again in the scenario.
+    pub path: Vec<usize>,
+}
+
+impl DelayActionConfig {
+    /// Set the path of the delays in the actions, and in the delays nested in them.
+    pub(crate) fn set_paths(actions: &mut [EventActionConfig], path: &[usize]) {
+        for (index, action) in actions.iter_mut().enumerate() {
+            if let EventActionConfig::Delay(config) = action {
+                config.path = [path, &[index]].concat();
+                Self::set_paths(&mut config.actions, &config.path);
+            }
+        }
+    }
+
+    /// Find the delay at the given path in the events of a scenario.
+    pub(crate) fn find<'a>(events: &'a [ScenarioEventConfig], path: &[usize]) -> Option<&'a Self> {
+        let (event, path) = path.split_first()?;
+        let mut actions = &events.get(*event)?.actions;
+        let mut delay = None;
+        for index in path {
+            let EventActionConfig::Delay(config) = actions.get(*index)? else {
+                return None;
+            };
+            actions = &config.actions;
+            delay = Some(config);
+        }
+
+        delay
+    }
+}
+
+impl EventAction<NovaEventWorld> for DelayActionConfig {
+    fn action(&self, world: &mut NovaEventWorld, info: &GameEventInfo) {
+        world.push_timer(ScenarioTimer {
+            id: None,
+            timer: Timer::from_seconds(self.seconds, TimerMode::Once),
+            kind: ScenarioTimerKind::Delay(self.clone(), info.clone()),
+        });
+    }
+}
+
+/// Start a timer that fires an `OnTimerEvent` with its id once the interval has elapsed. Starting
+/// a timer with the id of a running timer restarts it.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct TimerActionConfig {
+    pub id: String,
+    pub interval: f32,
+    #[serde(default)]
+    pub repeat: bool,
+}
+
+impl EventAction<NovaEventWorld> for TimerActionConfig {
+    fn action(&self, world: &mut NovaEventWorld, _: &GameEventInfo) {
+        let mode = if self.repeat {
+            TimerMode::Repeating
+        } else {
+            TimerMode::Once
+        };
+
+        world.push_timer(ScenarioTimer {
+            id: Some(self.id.clone()),
+            timer: Timer::from_seconds(self.interval, mode),
+            kind: ScenarioTimerKind::Event,
+        });
+    }
+}
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct CancelTimerActionConfig {
+    pub id: String,
+}
+
+impl EventAction<NovaEventWorld> for CancelTimerActionConfig {
+    fn action(&self, world: &mut NovaEventWorld, _: &GameEventInfo) {
+        world.cancel_timer(&self.id);
+    }
+}
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub struct ObjectiveCompleteActionConfig {
     pub id: String,
 }
@@ -117,13 +216,20 @@ pub struct ScenarioObjectConfig {
     pub kind: ScenarioObjectKind,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub struct BaseScenarioObjectConfig {
     pub id: String,
     pub name: String,
     pub position: Vec3,
     pub rotation: Quat,
     pub health: f32,
+    /// The faction of the object, objects without a faction are neutral. Spaceships default to
+    /// the player or enemy faction, depending on their controller.
+    #[serde(default)]
+    pub faction: Option<String>,
+    /// The explosion when the object is destroyed, damaging and pushing away everything around.
+    #[serde(default)]
+    pub explosion: Option<ExplosionConfig>,
 }
 
 pub fn base_scenario_object(config: &BaseScenarioObjectConfig) -> impl Bundle {
@@ -143,6 +249,37 @@ pub fn base_scenario_object(config: &BaseScenarioObjectConfig) -> impl Bundle {
 pub enum ScenarioObjectKind {
     Asteroid(AsteroidConfig),
     Spaceship(SpaceshipConfig),
+    TriggerZone(TriggerZoneConfig),
+}
+
+impl ScenarioObjectConfig {
+    /// Spawn the scenario object in the world.
+    pub fn spawn<'a>(&self, commands: &'a mut Commands) -> EntityCommands<'a> {
+        let mut entity_commands = commands.spawn(base_scenario_object(&self.base));
+        if let Some(faction) = &self.base.faction {
+            entity_commands.insert(Faction::new(faction.clone()));
+        }
+        if let Some(explosion) = &self.base.explosion {
+            entity_commands.insert(explosion.clone());
+        }
+
+        match &self.kind {
+            ScenarioObjectKind::Asteroid(config) => {
+                entity_commands.insert(asteroid_scenario_object(config.clone()));
+            }
+            ScenarioObjectKind::Spaceship(config) => {
+                entity_commands.insert(spaceship_scenario_object(config.clone()));
+            }
+            ScenarioObjectKind::TriggerZone(config) => {
+                // Zones are not physical objects, they cannot be damaged or destroyed.
+                entity_commands
+                    .remove::<(Health, ExplodableEntity)>()
+                    .insert(trigger_zone_scenario_object(config.clone()));
+            }
+        }
+
+        entity_commands
+    }
 }
 
 impl EventAction<NovaEventWorld> for ScenarioObjectConfig {
@@ -150,16 +287,7 @@ impl EventAction<NovaEventWorld> for ScenarioObjectConfig {
         let config = self.clone();
 
         world.push_command(move |commands| {
-            let mut entity_commands = commands.spawn(base_scenario_object(&config.base));
-
-            match &config.kind {
-                ScenarioObjectKind::Asteroid(config) => {
-                    entity_commands.insert(asteroid_scenario_object(config.clone()));
-                }
-                ScenarioObjectKind::Spaceship(config) => {
-                    entity_commands.insert(spaceship_scenario_object(config.clone()));
-                }
-            }
+            config.spawn(commands);
         });
     }
 }
@@ -8,11 +8,15 @@ pub mod prelude {
     pub use super::EventConfig;
 }
 
-#[derive(Debug, Clone, Copy, Reflect)]
+#[derive(Debug, Clone, Copy, Reflect, serde::Serialize, serde::Deserialize)]
 pub enum EventConfig {
     OnStart,
     OnDestroyed,
+    OnDamaged,
     OnUpdate,
+    OnTimer,
+    OnEnterZone,
+    OnExitZone,
 }
 
 impl From<EventConfig> for EventHandler<NovaEventWorld> {
@@ -20,7 +24,11 @@ impl From<EventConfig> for EventHandler<NovaEventWorld> {
         match value {
             EventConfig::OnStart => EventHandler::new::<OnStartEvent>(),
             EventConfig::OnDestroyed => EventHandler::new::<OnDestroyedEvent>(),
+            EventConfig::OnDamaged => EventHandler::new::<OnDamagedEvent>(),
             EventConfig::OnUpdate => EventHandler::new::<OnUpdateEvent>(),
+            EventConfig::OnTimer => EventHandler::new::<OnTimerEvent>(),
+            EventConfig::OnEnterZone => EventHandler::new::<OnEnterZoneEvent>(),
+            EventConfig::OnExitZone => EventHandler::new::<OnExitZoneEvent>(),
         }
     }
 }
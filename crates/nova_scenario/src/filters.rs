@@ -9,7 +9,7 @@ pub mod prelude {
     };
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub enum EventFilterConfig {
     Entity(EntityFilterConfig),
     Conditional(ConditionalFilterConfig),
@@ -26,10 +26,13 @@ impl EventFilter<NovaEventWorld> for EventFilterConfig {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub struct EntityFilterConfig {
     pub id: Option<String>,
     pub type_name: Option<String>,
+    /// Only match events that happened in the trigger zone with this id
+    #[serde(default)]
+    pub zone_id: Option<String>,
 }
 
 impl EventFilter<NovaEventWorld> for EntityFilterConfig {
@@ -57,11 +60,15 @@ impl EventFilter<NovaEventWorld> for EntityFilterConfig {
             None => result &= true,
         }
 
+        if let Some(zone_id) = &self.zone_id {
+            result &= data.get("zone_id").and_then(|v| v.as_str()) == Some(zone_id.as_str());
+        }
+
         result
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub enum ConditionalFilterConfig {
     Not(Box<EventFilterConfig>),
     Or(Box<EventFilterConfig>, Box<EventFilterConfig>),
@@ -96,7 +103,8 @@ impl EventFilter<NovaEventWorld> for ConditionalFilterConfig {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(transparent)]
 pub struct ExpressionFilterConfig(pub VariableConditionNode);
 
 impl EventFilter<NovaEventWorld> for ExpressionFilterConfig {
@@ -0,0 +1,339 @@
+//! Built-in functions that can be called from scenario expressions, e.g `min(kills, 10)` or
+//! `distance("player_spaceship", "boss") < 50` or `health("boss") < 0.5`.
+
+use std::ops::RangeInclusive;
+
+use crate::prelude::*;
+
+pub mod prelude {
+    pub use super::{get_function, VariableFunction, BUILTIN_FUNCTIONS};
+}
+
+/// A function that can be called from an expression.
+#[derive(Clone, Debug)]
+pub struct VariableFunction {
+    /// The name used to call the function
+    pub name: &'static str,
+    /// The number of arguments the function accepts
+    pub arity: RangeInclusive<usize>,
+    /// The implementation, called with the evaluated arguments
+    pub call: fn(&NovaEventWorld, &[VariableLiteral]) -> Result<VariableLiteral, VariableError>,
+}
+
+/// The registry of functions available to expressions.
+pub static BUILTIN_FUNCTIONS: &[VariableFunction] = &[
+    VariableFunction {
+        name: "min",
+        arity: 1..=usize::MAX,
+        call: min,
+    },
+    VariableFunction {
+        name: "max",
+        arity: 1..=usize::MAX,
+        call: max,
+    },
+    VariableFunction {
+        name: "abs",
+        arity: 1..=1,
+        call: abs,
+    },
+    VariableFunction {
+        name: "floor",
+        arity: 1..=1,
+        call: floor,
+    },
+    VariableFunction {
+        name: "clamp",
+        arity: 3..=3,
+        call: clamp,
+    },
+    VariableFunction {
+        name: "random",
+        arity: 2..=2,
+        call: random,
+    },
+    VariableFunction {
+        name: "len",
+        arity: 1..=1,
+        call: len,
+    },
+    VariableFunction {
+        name: "to_string",
+        arity: 1..=1,
+        call: to_string,
+    },
+    VariableFunction {
+        name: "count_alive",
+        arity: 1..=1,
+        call: count_alive,
+    },
+    VariableFunction {
+        name: "distance",
+        arity: 2..=2,
+        call: distance,
+    },
+    VariableFunction {
+        name: "health",
+        arity: 1..=1,
+        call: health,
+    },
+];
+
+/// Find a built-in function by name.
+pub fn get_function(name: &str) -> Option<&'static VariableFunction> {
+    BUILTIN_FUNCTIONS
+        .iter()
+        .find(|function| function.name == name)
+}
+
+fn numbers(function: &str, args: &[VariableLiteral]) -> Result<Vec<f64>, VariableError> {
+    args.iter()
+        .map(|arg| match arg {
+            VariableLiteral::Number(value) => Ok(*value),
+            _ => Err(VariableError::argument_mismatch(function, "numbers", args)),
+        })
+        .collect()
+}
+
+fn strings<'a>(function: &str, args: &'a [VariableLiteral]) -> Result<Vec<&'a str>, VariableError> {
+    args.iter()
+        .map(|arg| match arg {
+            VariableLiteral::String(value) => Ok(value.as_str()),
+            _ => Err(VariableError::argument_mismatch(function, "strings", args)),
+        })
+        .collect()
+}
+
+fn min(_: &NovaEventWorld, args: &[VariableLiteral]) -> Result<VariableLiteral, VariableError> {
+    let values = numbers("min", args)?;
+    Ok(VariableLiteral::Number(
+        values.into_iter().fold(f64::INFINITY, f64::min),
+    ))
+}
+
+fn max(_: &NovaEventWorld, args: &[VariableLiteral]) -> Result<VariableLiteral, VariableError> {
+    let values = numbers("max", args)?;
+    Ok(VariableLiteral::Number(
+        values.into_iter().fold(f64::NEG_INFINITY, f64::max),
+    ))
+}
+
+fn abs(_: &NovaEventWorld, args: &[VariableLiteral]) -> Result<VariableLiteral, VariableError> {
+    let values = numbers("abs", args)?;
+    Ok(VariableLiteral::Number(values[0].abs()))
+}
+
+fn floor(_: &NovaEventWorld, args: &[VariableLiteral]) -> Result<VariableLiteral, VariableError> {
+    let values = numbers("floor", args)?;
+    Ok(VariableLiteral::Number(values[0].floor()))
+}
+
+fn clamp(_: &NovaEventWorld, args: &[VariableLiteral]) -> Result<VariableLiteral, VariableError> {
+    let values = numbers("clamp", args)?;
+    let (value, low, high) = (values[0], values[1], values[2]);
+    if low > high {
+        return Err(VariableError::InvalidArguments(format!(
+            "evaluate: clamp lower bound {} is greater than upper bound {}",
+            low, high
+        )));
+    }
+
+    Ok(VariableLiteral::Number(value.clamp(low, high)))
+}
+
+/// A random number in the range `[a, b)`.
+fn random(
+    world: &NovaEventWorld,
+    args: &[VariableLiteral],
+) -> Result<VariableLiteral, VariableError> {
+    let values = numbers("random", args)?;
+    let (low, high) = (values[0], values[1]);
+
+    Ok(VariableLiteral::Number(low + (high - low) * world.random()))
+}
+
+fn len(_: &NovaEventWorld, args: &[VariableLiteral]) -> Result<VariableLiteral, VariableError> {
+    let values = strings("len", args)?;
+    Ok(VariableLiteral::Number(values[0].chars().count() as f64))
+}
+
+fn to_string(
+    _: &NovaEventWorld,
+    args: &[VariableLiteral],
+) -> Result<VariableLiteral, VariableError> {
+    let value = match &args[0] {
+        VariableLiteral::String(value) => value.clone(),
+        VariableLiteral::Number(value) => value.to_string(),
+        VariableLiteral::Boolean(value) => value.to_string(),
+    };
+
+    Ok(VariableLiteral::String(value))
+}
+
+/// The number of scenario objects of the given type that are not destroyed.
+fn count_alive(
+    world: &NovaEventWorld,
+    args: &[VariableLiteral],
+) -> Result<VariableLiteral, VariableError> {
+    let values = strings("count_alive", args)?;
+    let count = world
+        .entities()
+        .iter()
+        .filter(|entity| entity.alive && entity.type_name == values[0])
+        .count();
+
+    Ok(VariableLiteral::Number(count as f64))
+}
+
+/// The distance between two scenario objects, given by their ids.
+fn distance(
+    world: &NovaEventWorld,
+    args: &[VariableLiteral],
+) -> Result<VariableLiteral, VariableError> {
+    let values = strings("distance", args)?;
+    let position = |id: &str| {
+        world
+            .get_entity(id)
+            .map(|entity| entity.position)
+            .ok_or_else(|| VariableError::UndefinedEntity(id.to_string()))
+    };
+
+    let distance = position(values[0])?.distance(position(values[1])?);
+
+    Ok(VariableLiteral::Number(distance as f64))
+}
+
+/// The remaining health of a scenario object, between 0 and 1.
+fn health(
+    world: &NovaEventWorld,
+    args: &[VariableLiteral],
+) -> Result<VariableLiteral, VariableError> {
+    let values = strings("health", args)?;
+    let entity = world
+        .get_entity(values[0])
+        .ok_or_else(|| VariableError::UndefinedEntity(values[0].to_string()))?;
+
+    Ok(VariableLiteral::Number(entity.health as f64))
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use super::*;
+
+    fn evaluate(source: &str, world: &NovaEventWorld) -> Result<VariableLiteral, VariableError> {
+        parse_expression(source).unwrap().evaluate(world)
+    }
+
+    #[test]
+    fn evaluates_math_functions() {
+        // Arrange
+        let world = NovaEventWorld::default();
+
+        // Act
+        let value = evaluate(
+            "min(4, 2, 8) + max(1, 3) + abs(-2) + floor(1.5) + clamp(12, 0, 10)",
+            &world,
+        );
+
+        // Assert
+        assert_eq!(value.unwrap(), VariableLiteral::Number(18.0));
+    }
+
+    #[test]
+    fn evaluates_string_functions() {
+        // Arrange
+        let world = NovaEventWorld::default();
+
+        // Act
+        let value = evaluate("len('wave ' + to_string(3))", &world);
+
+        // Assert
+        assert_eq!(value.unwrap(), VariableLiteral::Number(6.0));
+    }
+
+    #[test]
+    fn evaluates_random_in_range() {
+        // Arrange
+        let world = NovaEventWorld::default();
+
+        // Act
+        let values: Vec<_> = (0..100)
+            .map(|_| evaluate("random(2, 4)", &world).unwrap())
+            .collect();
+
+        // Assert
+        for value in values {
+            let VariableLiteral::Number(value) = value else {
+                panic!("expected a number, got {:?}", value);
+            };
+            assert!((2.0..4.0).contains(&value));
+        }
+    }
+
+    #[test]
+    fn evaluates_game_functions() {
+        // Arrange
+        let mut world = NovaEventWorld::default();
+        world.set_entities(vec![
+            ScenarioEntityState {
+                id: "player".to_string(),
+                type_name: "spaceship".to_string(),
+                position: Vec3::ZERO,
+                alive: true,
+                health: 1.0,
+            },
+            ScenarioEntityState {
+                id: "boss".to_string(),
+                type_name: "spaceship".to_string(),
+                position: Vec3::new(3.0, 4.0, 0.0),
+                alive: true,
+                health: 1.0,
+            },
+            ScenarioEntityState {
+                id: "wreck".to_string(),
+                type_name: "spaceship".to_string(),
+                position: Vec3::ZERO,
+                alive: false,
+                health: 0.0,
+            },
+        ]);
+
+        // Act
+        let count = evaluate("count_alive('spaceship')", &world);
+        let distance = evaluate("distance('player', 'boss')", &world);
+        let missing = evaluate("distance('player', 'ghost')", &world);
+        let health = evaluate("health('wreck')", &world);
+
+        // Assert
+        assert_eq!(count.unwrap(), VariableLiteral::Number(2.0));
+        assert_eq!(distance.unwrap(), VariableLiteral::Number(5.0));
+        assert_eq!(health.unwrap(), VariableLiteral::Number(0.0));
+        assert!(matches!(missing, Err(VariableError::UndefinedEntity(id)) if id == "ghost"));
+    }
+
+    #[test]
+    fn rejects_unknown_functions_and_wrong_arity() {
+        // Arrange
+        let sources = ["explode(1)", "abs(1, 2)", "clamp(1)"];
+
+        // Act
+        let errors = sources.map(|source| parse_expression(source).unwrap_err());
+
+        // Assert
+        assert_eq!(
+            errors[0].kind,
+            ParseErrorKind::UnknownFunction("explode".to_string())
+        );
+        assert_eq!(errors[0].span, Span::new(0, 7));
+        assert!(matches!(
+            errors[1].kind,
+            ParseErrorKind::WrongArgumentCount { found: 2, .. }
+        ));
+        assert!(matches!(
+            errors[2].kind,
+            ParseErrorKind::WrongArgumentCount { found: 1, .. }
+        ));
+    }
+}
@@ -1,15 +1,29 @@
 pub mod actions;
 pub mod events;
 pub mod filters;
+pub mod functions;
 pub mod loader;
 pub mod objects;
+pub mod parser;
+pub mod save;
+pub mod timers;
 pub mod variables;
 pub mod world;
 
 pub mod prelude {
     pub use super::{
-        actions::prelude::*, events::prelude::*, filters::prelude::*, loader::prelude::*,
-        objects::prelude::*, variables::prelude::*, world::NovaEventWorld, NovaScenarioPlugin,
+        actions::prelude::*,
+        events::prelude::*,
+        filters::prelude::*,
+        functions::prelude::*,
+        loader::prelude::*,
+        objects::prelude::*,
+        parser::prelude::*,
+        save::prelude::*,
+        timers::prelude::*,
+        variables::prelude::*,
+        world::{NovaEventWorld, ScenarioEntityState},
+        NovaScenarioPlugin,
     };
 }
 
@@ -27,6 +41,8 @@ impl Plugin for NovaScenarioPlugin {
 
         app.add_plugins(GameEventsPlugin::<world::NovaEventWorld>::default());
         app.add_plugins(loader::ScenarioLoaderPlugin);
+        app.add_plugins(save::ScenarioSavePlugin);
+        app.add_plugins(timers::ScenarioTimersPlugin);
         app.add_plugins(objects::ScenarioObjectsPlugin {
             render: self.render,
         });
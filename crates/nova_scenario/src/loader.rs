@@ -83,7 +83,9 @@ impl Plugin for ScenarioLoaderPlugin {
         app.add_observer(on_load_scenario);
 
         app.add_observer(on_add_entity_with::<MeshFragmentMarker>);
+        app.add_observer(on_add_entity_with::<SpaceshipWreckMarker>);
         app.add_observer(on_add_entity_with::<TurretBulletProjectileMarker>);
+        app.add_observer(on_add_entity_with::<MissileProjectileMarker>);
 
         app.add_input_context::<ScenarioInputMarker>();
         app.add_observer(on_next_input);
@@ -121,6 +123,18 @@ fn on_load_scenario(
     **current_scenario = Some(scenario.clone());
     debug!("on_load_scenario: scenario {:?}", scenario.name);
 
+    spawn_scenario(&mut commands, &scenario);
+
+    // Trigger ScenarioLoaded event
+    commands.trigger(ScenarioLoaded);
+
+    // Fire onstart event
+    commands.fire::<OnStartEvent>(OnStartEventInfo);
+}
+
+/// Spawn the entities that every scenario needs: the camera, the light, the input context and the
+/// event handlers. This does not fire the `OnStartEvent`.
+pub(crate) fn spawn_scenario(commands: &mut Commands, scenario: &ScenarioConfig) {
     // Setup Scenario Camera
     commands.spawn((
         ScenarioScopedMarker,
@@ -166,13 +180,15 @@ fn on_load_scenario(
     ));
 
     // Setup scenario events
-    for event in scenario.events.iter() {
+    for (index, event) in scenario.events.iter().enumerate() {
         let mut event_handler = EventHandler::<NovaEventWorld>::from(event.name);
         for filter in event.filters.iter() {
             event_handler.add_filter(filter.clone());
         }
-        for action in event.actions.iter() {
-            event_handler.add_action(action.clone());
+        let mut actions = event.actions.clone();
+        DelayActionConfig::set_paths(&mut actions, &[index]);
+        for action in actions {
+            event_handler.add_action(action);
         }
         commands.spawn((
             ScenarioScopedMarker,
@@ -180,12 +196,6 @@ fn on_load_scenario(
             event_handler,
         ));
     }
-
-    // Trigger ScenarioLoaded event
-    commands.trigger(ScenarioLoaded);
-
-    // Fire onstart event
-    commands.fire::<OnStartEvent>(OnStartEventInfo);
 }
 
 fn on_add_entity_with<T: Component>(
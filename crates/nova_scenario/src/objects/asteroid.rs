@@ -4,6 +4,7 @@ use bevy_common_systems::prelude::*;
 use bevy_rand::prelude::*;
 use noise::{Fbm, MultiFractal, NoiseFn, Perlin};
 use nova_events::prelude::*;
+use nova_gameplay::prelude::*;
 use rand::RngCore;
 
 pub mod prelude {
@@ -26,6 +27,7 @@ pub fn asteroid_scenario_object(config: AsteroidConfig) -> impl Bundle {
 
     (
         AsteroidMarker,
+        AIObstacleMarker,
         EntityTypeName::new(ASTEROID_TYPE_NAME),
         AsteroidTexture(config.texture),
         AsteroidRadius(config.radius),
@@ -1,8 +1,12 @@
 pub mod asteroid;
 pub mod spaceship;
+pub mod trigger_zone;
 
 pub mod prelude {
-    pub use super::{asteroid::prelude::*, spaceship::prelude::*, ScenarioObjectsPlugin};
+    pub use super::{
+        asteroid::prelude::*, spaceship::prelude::*, trigger_zone::prelude::*,
+        ScenarioObjectsPlugin,
+    };
 }
 
 use bevy::prelude::*;
@@ -19,5 +23,6 @@ impl Plugin for ScenarioObjectsPlugin {
             render: self.render,
         });
         app.add_plugins(spaceship::SpaceshipPlugin);
+        app.add_plugins(trigger_zone::TriggerZonePlugin);
     }
 }
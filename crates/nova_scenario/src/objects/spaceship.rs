@@ -1,13 +1,14 @@
 use bevy::{platform::collections::HashMap, prelude::*};
+use bevy_common_systems::prelude::*;
 use bevy_enhanced_input::prelude::*;
 use nova_events::prelude::*;
 use nova_gameplay::prelude::*;
 
 pub mod prelude {
     pub use super::{
-        spaceship_scenario_object, AIControllerConfig, PlayerControllerConfig, SpaceshipConfig,
-        SpaceshipController, SpaceshipPlugin, SpaceshipSectionConfig, SpaceshipSectionsConfig,
-        SPACESHIP_TYPE_NAME,
+        spaceship_scenario_object, validate_spaceship_sections, AIControllerConfig,
+        PlayerControllerConfig, SpaceshipConfig, SpaceshipController, SpaceshipDesignError,
+        SpaceshipPlugin, SpaceshipSectionConfig, SpaceshipSectionsConfig, SPACESHIP_TYPE_NAME,
     };
 }
 
@@ -25,8 +26,11 @@ pub struct PlayerControllerConfig {
     pub input_mapping: HashMap<SectionId, Vec<Binding>>,
 }
 
-#[derive(Clone, Debug, Reflect)]
-pub struct AIControllerConfig {}
+#[derive(Clone, Debug, Default, Reflect, serde::Serialize, serde::Deserialize)]
+pub struct AIControllerConfig {
+    #[serde(default)]
+    pub behaviour: AIBehaviour,
+}
 
 pub type SectionId = String;
 
@@ -41,6 +45,82 @@ pub struct SpaceshipSectionConfig {
 #[derive(Component, Clone, Debug, Default, Deref, DerefMut, Reflect)]
 pub struct SpaceshipSectionsConfig(pub Vec<SpaceshipSectionConfig>);
 
+impl SpaceshipSectionsConfig {
+    /// Check that the sections make a spaceship that can fly.
+    pub fn validate(&self) -> Result<(), SpaceshipDesignError> {
+        validate_spaceship_sections(self.iter())
+    }
+}
+
+/// Errors found while validating the design of a spaceship.
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum SpaceshipDesignError {
+    #[error("the spaceship has no sections")]
+    Empty,
+    #[error("the spaceship has no controller section")]
+    NoController,
+    #[error("more than one section is placed at {cells:?}")]
+    Overlapping { cells: Vec<IVec3> },
+    #[error("sections {sections:?} are not connected to the rest of the spaceship")]
+    Disconnected { sections: Vec<SectionId> },
+}
+
+/// Check that the sections are all connected to each other on the unit grid, that none of them
+/// overlap and that there is at least one controller.
+pub fn validate_spaceship_sections<'a>(
+    sections: impl IntoIterator<Item = &'a SpaceshipSectionConfig>,
+) -> Result<(), SpaceshipDesignError> {
+    let sections = sections.into_iter().collect::<Vec<_>>();
+    if sections.is_empty() {
+        return Err(SpaceshipDesignError::Empty);
+    }
+
+    let cells = overlapping_sections(sections.iter().map(|section| section.position));
+    if !cells.is_empty() {
+        return Err(SpaceshipDesignError::Overlapping { cells });
+    }
+
+    let has_controller = sections
+        .iter()
+        .any(|section| matches!(section.config.kind, SectionKind::Controller(_)));
+    if !has_controller {
+        return Err(SpaceshipDesignError::NoController);
+    }
+
+    let graph = SectionGraph::new(
+        sections
+            .iter()
+            .enumerate()
+            .map(|(index, section)| (index, section.position)),
+    );
+    let mut components = graph.components();
+    if components.len() > 1 {
+        // The sections are disconnected from the group of the controller, like at runtime
+        let kept = components
+            .iter()
+            .position(|component| {
+                component
+                    .iter()
+                    .any(|&index| matches!(sections[index].config.kind, SectionKind::Controller(_)))
+            })
+            .unwrap_or(0);
+        components.remove(kept);
+
+        let mut disconnected = components
+            .iter()
+            .flatten()
+            .map(|&index| sections[index].id.clone())
+            .collect::<Vec<_>>();
+        disconnected.sort();
+
+        return Err(SpaceshipDesignError::Disconnected {
+            sections: disconnected,
+        });
+    }
+
+    Ok(())
+}
+
 #[derive(Clone, Debug)]
 pub struct SpaceshipConfig {
     pub controller: SpaceshipController,
@@ -84,12 +164,21 @@ fn insert_spaceship_sections(
         return;
     };
 
+    if let Err(error) = sections_config.validate() {
+        warn!(
+            "insert_spaceship_sections: entity {:?} has an invalid design: {}",
+            entity, error
+        );
+    }
+
     commands.entity(entity).with_children(|parent| {
         for section in sections_config.iter() {
             let mut section_entity = parent.spawn((
                 EntityId::new(section.id.clone()),
                 EntityTypeName::new(section.config.base.id.clone()),
                 base_section(section.config.base.clone()),
+                Health::new(section.config.base.health),
+                ExplodableEntity,
                 Transform::from_translation(section.position).with_rotation(section.rotation),
             ));
 
@@ -128,17 +217,257 @@ fn insert_spaceship_sections(
                         SpaceshipController::AI(_) => {}
                     }
                 }
+                SectionKind::Reactor(reactor_config) => {
+                    section_entity.insert(reactor_section(reactor_config.clone()));
+                }
+                SectionKind::Battery(battery_config) => {
+                    section_entity.insert(battery_section(battery_config.clone()));
+                }
+                SectionKind::Shield(shield_config) => {
+                    section_entity.insert(shield_section(shield_config.clone()));
+                }
+                SectionKind::Beam(beam_config) => {
+                    section_entity.insert(beam_section(beam_config.clone()));
+
+                    match controller_config {
+                        SpaceshipController::None => {}
+                        SpaceshipController::Player(config) => {
+                            if let Some(bindings) = config.input_mapping.get(&section.id) {
+                                section_entity
+                                    .insert(SpaceshipTurretInputBinding(bindings.clone()));
+                            }
+                        }
+                        SpaceshipController::AI(_) => {}
+                    }
+                }
+                SectionKind::MissileLauncher(launcher_config) => {
+                    section_entity.insert(missile_launcher_section(launcher_config.clone()));
+
+                    match controller_config {
+                        SpaceshipController::None => {}
+                        SpaceshipController::Player(config) => {
+                            if let Some(bindings) = config.input_mapping.get(&section.id) {
+                                section_entity
+                                    .insert(SpaceshipMissileInputBinding(bindings.clone()));
+                            }
+                        }
+                        SpaceshipController::AI(_) => {}
+                    }
+                }
             }
         }
     });
 
+    // Spaceships with reactors or batteries are limited by their power, the others are not
+    if sections_config.iter().any(|section| {
+        matches!(
+            section.config.kind,
+            SectionKind::Reactor(_) | SectionKind::Battery(_)
+        )
+    }) {
+        commands.entity(entity).insert(SpaceshipPower::default());
+    }
+    // and only spaceships with shield sections are protected by a shield
+    if sections_config
+        .iter()
+        .any(|section| matches!(section.config.kind, SectionKind::Shield(_)))
+    {
+        commands.entity(entity).insert(SpaceshipShield::default());
+    }
+
     match controller_config {
         SpaceshipController::None => {}
         SpaceshipController::Player(_) => {
             commands.entity(entity).insert(PlayerSpaceshipMarker);
         }
-        SpaceshipController::AI(_) => {
-            commands.entity(entity).insert(AISpaceshipMarker);
+        SpaceshipController::AI(config) => {
+            commands
+                .entity(entity)
+                .insert((AISpaceshipMarker, config.behaviour.clone()));
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn section(id: &str, position: Vec3, kind: SectionKind) -> SpaceshipSectionConfig {
+        SpaceshipSectionConfig {
+            id: id.to_string(),
+            position,
+            rotation: Quat::IDENTITY,
+            config: SectionConfig {
+                base: BaseSectionConfig::default(),
+                kind,
+            },
+        }
+    }
+
+    fn hull(id: &str, position: Vec3) -> SpaceshipSectionConfig {
+        section(
+            id,
+            position,
+            SectionKind::Hull(HullSectionConfig { render_mesh: None }),
+        )
+    }
+
+    fn controller(id: &str, position: Vec3) -> SpaceshipSectionConfig {
+        section(
+            id,
+            position,
+            SectionKind::Controller(ControllerSectionConfig {
+                frequency: 1.0,
+                damping_ratio: 1.0,
+                max_torque: 1.0,
+                render_mesh: None,
+            }),
+        )
+    }
+
+    #[test]
+    fn accepts_connected_design_with_controller() {
+        // Arrange
+        let sections = SpaceshipSectionsConfig(vec![
+            controller("controller", Vec3::ZERO),
+            hull("front", Vec3::NEG_Z),
+            hull("back", Vec3::Z),
+        ]);
+
+        // Act
+        let result = sections.validate();
+
+        // Assert
+        assert_eq!(result, Ok(()));
+    }
+
+    #[test]
+    fn rejects_invalid_designs() {
+        // Arrange
+        let no_controller = SpaceshipSectionsConfig(vec![hull("hull", Vec3::ZERO)]);
+        let disconnected = SpaceshipSectionsConfig(vec![
+            controller("controller", Vec3::ZERO),
+            hull("front", Vec3::NEG_Z),
+            hull("floating", Vec3::new(0.0, 0.0, 3.0)),
+        ]);
+        let overlapping = SpaceshipSectionsConfig(vec![
+            controller("controller", Vec3::ZERO),
+            hull("hull", Vec3::ZERO),
+        ]);
+
+        // Act
+        let no_controller = no_controller.validate();
+        let disconnected = disconnected.validate();
+        let overlapping = overlapping.validate();
+        let empty = SpaceshipSectionsConfig::default().validate();
+
+        // Assert
+        assert_eq!(no_controller, Err(SpaceshipDesignError::NoController));
+        assert_eq!(
+            disconnected,
+            Err(SpaceshipDesignError::Disconnected {
+                sections: vec!["floating".to_string()]
+            })
+        );
+        assert_eq!(
+            overlapping,
+            Err(SpaceshipDesignError::Overlapping {
+                cells: vec![IVec3::ZERO]
+            })
+        );
+        assert_eq!(empty, Err(SpaceshipDesignError::Empty));
+    }
+
+    #[test]
+    fn reports_sections_disconnected_from_the_controller() {
+        // Arrange
+        let sections = SpaceshipSectionsConfig(vec![
+            hull("floating", Vec3::new(0.0, 0.0, 3.0)),
+            controller("controller", Vec3::ZERO),
+            hull("front", Vec3::NEG_Z),
+        ]);
+
+        // Act
+        let result = sections.validate();
+
+        // Assert
+        assert_eq!(
+            result,
+            Err(SpaceshipDesignError::Disconnected {
+                sections: vec!["floating".to_string()]
+            })
+        );
+    }
+
+    #[test]
+    fn limits_power_only_with_power_sections() {
+        // Arrange
+        let mut app = App::new();
+        app.add_observer(insert_spaceship_sections);
+        let spaceship = |sections| {
+            (
+                SpaceshipRootMarker,
+                SpaceshipController::None,
+                SpaceshipSectionsConfig(sections),
+            )
+        };
+        let unpowered = app
+            .world_mut()
+            .spawn(spaceship(vec![controller("controller", Vec3::ZERO)]))
+            .id();
+        let powered = app
+            .world_mut()
+            .spawn(spaceship(vec![
+                controller("controller", Vec3::ZERO),
+                section(
+                    "reactor",
+                    Vec3::Z,
+                    SectionKind::Reactor(ReactorSectionConfig::default()),
+                ),
+            ]))
+            .id();
+
+        // Act
+        app.update();
+
+        // Assert
+        assert!(app.world().get::<SpaceshipPower>(unpowered).is_none());
+        assert!(app.world().get::<SpaceshipPower>(powered).is_some());
+    }
+
+    #[test]
+    fn shields_only_spaceships_with_shield_sections() {
+        // Arrange
+        let mut app = App::new();
+        app.add_observer(insert_spaceship_sections);
+        let spaceship = |sections| {
+            (
+                SpaceshipRootMarker,
+                SpaceshipController::None,
+                SpaceshipSectionsConfig(sections),
+            )
+        };
+        let unshielded = app
+            .world_mut()
+            .spawn(spaceship(vec![controller("controller", Vec3::ZERO)]))
+            .id();
+        let shielded = app
+            .world_mut()
+            .spawn(spaceship(vec![
+                controller("controller", Vec3::ZERO),
+                section(
+                    "shield",
+                    Vec3::Z,
+                    SectionKind::Shield(ShieldSectionConfig::default()),
+                ),
+            ]))
+            .id();
+
+        // Act
+        app.update();
+
+        // Assert
+        assert!(app.world().get::<SpaceshipShield>(unshielded).is_none());
+        assert!(app.world().get::<SpaceshipShield>(shielded).is_some());
+    }
+}
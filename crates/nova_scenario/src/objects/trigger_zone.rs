@@ -0,0 +1,137 @@
+//! A trigger zone is an invisible sensor volume that fires `OnEnterZoneEvent` and
+//! `OnExitZoneEvent` when scenario objects fly in or out of it.
+
+use avian3d::prelude::*;
+use bevy::{platform::collections::HashMap, prelude::*};
+use bevy_common_systems::prelude::*;
+use nova_events::prelude::*;
+
+pub mod prelude {
+    pub use super::{
+        trigger_zone_scenario_object, TriggerZoneConfig, TriggerZoneMarker, TriggerZoneOccupants,
+        TriggerZonePlugin, TriggerZoneShape, TRIGGER_ZONE_TYPE_NAME,
+    };
+}
+
+pub const TRIGGER_ZONE_TYPE_NAME: &str = "trigger_zone";
+
+#[derive(Clone, Debug, Reflect, serde::Serialize, serde::Deserialize)]
+pub enum TriggerZoneShape {
+    Sphere { radius: f32 },
+    Box { half_extents: Vec3 },
+}
+
+#[derive(Clone, Debug, Reflect, serde::Serialize, serde::Deserialize)]
+pub struct TriggerZoneConfig {
+    pub shape: TriggerZoneShape,
+}
+
+pub fn trigger_zone_scenario_object(config: TriggerZoneConfig) -> impl Bundle {
+    debug!("trigger_zone_scenario_object: config {:?}", config);
+
+    let collider = match config.shape {
+        TriggerZoneShape::Sphere { radius } => Collider::sphere(radius),
+        TriggerZoneShape::Box { half_extents } => {
+            let size = half_extents * 2.0;
+            Collider::cuboid(size.x, size.y, size.z)
+        }
+    };
+
+    (
+        TriggerZoneMarker,
+        EntityTypeName::new(TRIGGER_ZONE_TYPE_NAME),
+        TriggerZoneOccupants::default(),
+        RigidBody::Static,
+        collider,
+        Sensor,
+        CollisionEventsEnabled,
+    )
+}
+
+#[derive(Component, Clone, Debug, Reflect)]
+pub struct TriggerZoneMarker;
+
+/// The bodies inside the zone, with the number of their colliders that overlap it. A spaceship has
+/// one collider per section, but it should only enter the zone once.
+#[derive(Component, Clone, Debug, Default, Deref, DerefMut, Reflect)]
+pub struct TriggerZoneOccupants(pub HashMap<Entity, usize>);
+
+pub struct TriggerZonePlugin;
+
+impl Plugin for TriggerZonePlugin {
+    fn build(&self, app: &mut App) {
+        debug!("TriggerZonePlugin: build");
+
+        app.add_observer(on_trigger_zone_enter);
+        app.add_observer(on_trigger_zone_exit);
+    }
+}
+
+fn on_trigger_zone_enter(
+    collision: On<CollisionStart>,
+    mut commands: Commands,
+    mut q_zone: Query<(&EntityId, &mut TriggerZoneOccupants), With<TriggerZoneMarker>>,
+    q_info: Query<(&EntityId, &EntityTypeName)>,
+) {
+    let zone = collision.collider1;
+    let Ok((zone_id, mut occupants)) = q_zone.get_mut(zone) else {
+        return;
+    };
+    let Some(body) = collision.body2 else {
+        return;
+    };
+    trace!("on_trigger_zone_enter: zone {:?}, body {:?}", zone, body);
+
+    let count = occupants.entry(body).or_default();
+    *count += 1;
+    if *count > 1 {
+        return;
+    }
+
+    let Ok((id, type_name)) = q_info.get(body) else {
+        return;
+    };
+
+    debug!("on_trigger_zone_enter: {:?} entered zone {:?}", id, zone_id);
+    commands.fire::<OnEnterZoneEvent>(OnEnterZoneEventInfo {
+        id: id.to_string(),
+        type_name: type_name.to_string(),
+        zone_id: zone_id.to_string(),
+    });
+}
+
+fn on_trigger_zone_exit(
+    collision: On<CollisionEnd>,
+    mut commands: Commands,
+    mut q_zone: Query<(&EntityId, &mut TriggerZoneOccupants), With<TriggerZoneMarker>>,
+    q_info: Query<(&EntityId, &EntityTypeName)>,
+) {
+    let zone = collision.collider1;
+    let Ok((zone_id, mut occupants)) = q_zone.get_mut(zone) else {
+        return;
+    };
+    let Some(body) = collision.body2 else {
+        return;
+    };
+    trace!("on_trigger_zone_exit: zone {:?}, body {:?}", zone, body);
+
+    let Some(count) = occupants.get_mut(&body) else {
+        return;
+    };
+    *count -= 1;
+    if *count > 0 {
+        return;
+    }
+    occupants.remove(&body);
+
+    let Ok((id, type_name)) = q_info.get(body) else {
+        return;
+    };
+
+    debug!("on_trigger_zone_exit: {:?} left zone {:?}", id, zone_id);
+    commands.fire::<OnExitZoneEvent>(OnExitZoneEventInfo {
+        id: id.to_string(),
+        type_name: type_name.to_string(),
+        zone_id: zone_id.to_string(),
+    });
+}
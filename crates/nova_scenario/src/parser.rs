@@ -0,0 +1,792 @@
+//! A text parser for the variable expression language used by scenarios.
+//!
+//! The grammar follows the nodes in `variables`:
+//!
+//! ```text
+//! condition  := and ("or" and)*
+//! and        := not ("and" not)*
+//! not        := "not" not | comparison | "(" condition ")"
+//! comparison := expression (("<" | "<=" | ">" | ">=" | "==" | "!=") expression)?
+//! expression := term (("+" | "-") term)*
+//! term       := factor (("*" | "/") factor)*
+//! factor     := number | string | "true" | "false" | name | call | "(" expression ")"
+//!             | "-" factor
+//! call       := name "(" (expression ("," expression)*)? ")"
+//! ```
+//!
+//! Strings can be quoted with either `"` or `'`, which makes them easier to embed in scenario
+//! files. Binary operators are left associative, so `10 - 2 - 3` is `(10 - 2) - 3`. A comparison
+//! without an operator is a boolean value, e.g `boss_alive and kills > 4`.
+
+use std::{fmt, ops::RangeInclusive, str::FromStr};
+
+use crate::prelude::*;
+
+pub mod prelude {
+    pub use super::{parse_condition, parse_expression, ParseError, ParseErrorKind, Span};
+}
+
+/// A range of bytes in the parsed source.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize) -> Self {
+        Self { start, end }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParseErrorKind {
+    UnexpectedCharacter(char),
+    UnterminatedString,
+    InvalidNumber(String),
+    UnexpectedToken {
+        expected: &'static str,
+        found: String,
+    },
+    UnexpectedEnd {
+        expected: &'static str,
+    },
+    UnknownFunction(String),
+    WrongArgumentCount {
+        function: String,
+        expected: RangeInclusive<usize>,
+        found: usize,
+    },
+}
+
+/// An error found while parsing an expression, with the span of the source that caused it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseError {
+    pub kind: ParseErrorKind,
+    pub span: Span,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            ParseErrorKind::UnexpectedCharacter(c) => write!(f, "unexpected character {:?}", c)?,
+            ParseErrorKind::UnterminatedString => write!(f, "unterminated string")?,
+            ParseErrorKind::InvalidNumber(number) => write!(f, "invalid number '{}'", number)?,
+            ParseErrorKind::UnexpectedToken { expected, found } => {
+                write!(f, "expected {}, but found '{}'", expected, found)?
+            }
+            ParseErrorKind::UnexpectedEnd { expected } => {
+                write!(f, "expected {}, but found the end of the input", expected)?
+            }
+            ParseErrorKind::UnknownFunction(name) => write!(f, "unknown function '{}'", name)?,
+            ParseErrorKind::WrongArgumentCount {
+                function,
+                expected,
+                found,
+            } => {
+                write!(f, "{} expects ", function)?;
+                if expected.start() == expected.end() {
+                    write!(f, "{}", expected.start())?;
+                } else if *expected.end() == usize::MAX {
+                    write!(f, "at least {}", expected.start())?;
+                } else {
+                    write!(f, "{} to {}", expected.start(), expected.end())?;
+                }
+                write!(f, " arguments, but got {}", found)?
+            }
+        }
+
+        write!(f, " at {}..{}", self.span.start, self.span.end)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parse an expression, e.g `kills + 1`.
+pub fn parse_expression(source: &str) -> Result<VariableExpressionNode, ParseError> {
+    let mut parser = Parser::new(source)?;
+    let expression = parser.expression()?;
+    parser.finish()?;
+
+    Ok(expression)
+}
+
+/// Parse a condition, e.g `score > 10 * wave`.
+pub fn parse_condition(source: &str) -> Result<VariableConditionNode, ParseError> {
+    let mut parser = Parser::new(source)?;
+    let condition = parser.condition()?;
+    parser.finish()?;
+
+    Ok(condition)
+}
+
+impl FromStr for VariableExpressionNode {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_expression(s)
+    }
+}
+
+impl FromStr for VariableConditionNode {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_condition(s)
+    }
+}
+
+impl TryFrom<String> for VariableExpressionNode {
+    type Error = ParseError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        parse_expression(&value)
+    }
+}
+
+impl TryFrom<String> for VariableConditionNode {
+    type Error = ParseError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        parse_condition(&value)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Number(f64),
+    String(String),
+    Name(String),
+    True,
+    False,
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    LeftParen,
+    RightParen,
+    Comma,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+    EqualEqual,
+    NotEqual,
+    And,
+    Or,
+    Not,
+}
+
+fn tokenize(source: &str) -> Result<Vec<(Token, Span)>, ParseError> {
+    let mut tokens = Vec::new();
+    let mut chars = source.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        let single = |token: Token| (token, Span::new(start, start + c.len_utf8()));
+
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '+' => single(Token::Plus),
+            '-' => single(Token::Minus),
+            '*' => single(Token::Star),
+            '/' => single(Token::Slash),
+            '(' => single(Token::LeftParen),
+            ')' => single(Token::RightParen),
+            ',' => single(Token::Comma),
+            '<' | '>' | '=' | '!' => match (c, chars.next_if(|(_, c)| *c == '=')) {
+                ('<', None) => single(Token::Less),
+                ('<', Some(_)) => (Token::LessEqual, Span::new(start, start + 2)),
+                ('>', None) => single(Token::Greater),
+                ('>', Some(_)) => (Token::GreaterEqual, Span::new(start, start + 2)),
+                ('=', Some(_)) => (Token::EqualEqual, Span::new(start, start + 2)),
+                ('!', Some(_)) => (Token::NotEqual, Span::new(start, start + 2)),
+                (c, _) => {
+                    return Err(ParseError {
+                        kind: ParseErrorKind::UnexpectedCharacter(c),
+                        span: Span::new(start, start + 1),
+                    })
+                }
+            },
+            '"' | '\'' => {
+                let quote = c;
+                let mut value = String::new();
+                let mut end = None;
+                while let Some((index, c)) = chars.next() {
+                    match c {
+                        c if c == quote => {
+                            end = Some(index + 1);
+                            break;
+                        }
+                        '\\' => match chars.next() {
+                            Some((_, 'n')) => value.push('\n'),
+                            Some((_, 't')) => value.push('\t'),
+                            Some((_, c)) => value.push(c),
+                            None => break,
+                        },
+                        c => value.push(c),
+                    }
+                }
+
+                let Some(end) = end else {
+                    return Err(ParseError {
+                        kind: ParseErrorKind::UnterminatedString,
+                        span: Span::new(start, source.len()),
+                    });
+                };
+
+                (Token::String(value), Span::new(start, end))
+            }
+            c if c.is_ascii_digit() => {
+                let mut end = start + 1;
+                while let Some((index, c)) = chars.next_if(|(_, c)| c.is_ascii_digit() || *c == '.')
+                {
+                    end = index + c.len_utf8();
+                }
+
+                let text = &source[start..end];
+                // Literals too large for a float would read as infinity, which has no text form.
+                let Some(number) = text.parse::<f64>().ok().filter(|number| number.is_finite())
+                else {
+                    return Err(ParseError {
+                        kind: ParseErrorKind::InvalidNumber(text.to_string()),
+                        span: Span::new(start, end),
+                    });
+                };
+
+                (Token::Number(number), Span::new(start, end))
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut end = start + c.len_utf8();
+                while let Some((index, c)) =
+                    chars.next_if(|(_, c)| c.is_alphanumeric() || *c == '_')
+                {
+                    end = index + c.len_utf8();
+                }
+
+                let token = match &source[start..end] {
+                    "true" => Token::True,
+                    "false" => Token::False,
+                    "and" => Token::And,
+                    "or" => Token::Or,
+                    "not" => Token::Not,
+                    name => Token::Name(name.to_string()),
+                };
+
+                (token, Span::new(start, end))
+            }
+            c => {
+                return Err(ParseError {
+                    kind: ParseErrorKind::UnexpectedCharacter(c),
+                    span: Span::new(start, start + c.len_utf8()),
+                })
+            }
+        };
+
+        tokens.push(token);
+    }
+
+    Ok(tokens)
+}
+
+struct Parser<'a> {
+    source: &'a str,
+    tokens: Vec<(Token, Span)>,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(source: &'a str) -> Result<Self, ParseError> {
+        Ok(Self {
+            source,
+            tokens: tokenize(source)?,
+            position: 0,
+        })
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|(token, _)| token)
+    }
+
+    fn advance(&mut self) -> Option<(Token, Span)> {
+        let token = self.tokens.get(self.position).cloned();
+        if token.is_some() {
+            self.position += 1;
+        }
+        token
+    }
+
+    /// Build an error for the current token, or for the end of the input.
+    fn error(&self, expected: &'static str) -> ParseError {
+        match self.tokens.get(self.position) {
+            Some((_, span)) => ParseError {
+                kind: ParseErrorKind::UnexpectedToken {
+                    expected,
+                    found: self.source[span.start..span.end].to_string(),
+                },
+                span: *span,
+            },
+            None => ParseError {
+                kind: ParseErrorKind::UnexpectedEnd { expected },
+                span: Span::new(self.source.len(), self.source.len()),
+            },
+        }
+    }
+
+    fn finish(&self) -> Result<(), ParseError> {
+        match self.peek() {
+            Some(_) => Err(self.error("an operator or the end of the input")),
+            None => Ok(()),
+        }
+    }
+
+    fn condition(&mut self) -> Result<VariableConditionNode, ParseError> {
+        let mut left = self.and()?;
+
+        while let Some(Token::Or) = self.peek() {
+            self.advance();
+            left = VariableConditionNode::new_or(left, self.and()?);
+        }
+
+        Ok(left)
+    }
+
+    fn and(&mut self) -> Result<VariableConditionNode, ParseError> {
+        let mut left = self.not()?;
+
+        while let Some(Token::And) = self.peek() {
+            self.advance();
+            left = VariableConditionNode::new_and(left, self.not()?);
+        }
+
+        Ok(left)
+    }
+
+    fn not(&mut self) -> Result<VariableConditionNode, ParseError> {
+        if let Some(Token::Not) = self.peek() {
+            self.advance();
+            return Ok(VariableConditionNode::new_not(self.not()?));
+        }
+
+        // A parenthesis can start either an expression, `(a + b) > c`, or a condition,
+        // `(a > b or c) and d`. Try the comparison first and fall back to the condition, keeping
+        // the error that got the furthest if both fail.
+        let start = self.position;
+        let comparison_error = match self.comparison() {
+            Ok(comparison) => return Ok(comparison),
+            Err(error) => error,
+        };
+
+        self.position = start;
+        if let Some(Token::LeftParen) = self.peek() {
+            self.advance();
+            let condition = self.condition().and_then(|condition| match self.peek() {
+                Some(Token::RightParen) => {
+                    self.advance();
+                    Ok(condition)
+                }
+                _ => Err(self.error("')'")),
+            });
+
+            match condition {
+                Ok(condition) => return Ok(condition),
+                Err(error) if error.span.start > comparison_error.span.start => return Err(error),
+                Err(_) => {}
+            }
+        }
+
+        Err(comparison_error)
+    }
+
+    fn comparison(&mut self) -> Result<VariableConditionNode, ParseError> {
+        let left = self.expression()?;
+
+        let comparison = match self.peek() {
+            Some(Token::Less) => VariableConditionNode::new_less_than,
+            Some(Token::LessEqual) => VariableConditionNode::new_less_equal,
+            Some(Token::Greater) => VariableConditionNode::new_greater_than,
+            Some(Token::GreaterEqual) => VariableConditionNode::new_greater_equal,
+            Some(Token::EqualEqual) => VariableConditionNode::new_equals,
+            Some(Token::NotEqual) => VariableConditionNode::new_not_equals,
+            _ => return Ok(VariableConditionNode::new_value(left)),
+        };
+        self.advance();
+
+        let right = self.expression()?;
+
+        Ok(comparison(left, right))
+    }
+
+    fn expression(&mut self) -> Result<VariableExpressionNode, ParseError> {
+        let mut left = VariableExpressionNode::new_term(self.term()?);
+
+        loop {
+            let expression = match self.peek() {
+                Some(Token::Plus) => VariableExpressionNode::new_add,
+                Some(Token::Minus) => VariableExpressionNode::new_subtract,
+                _ => return Ok(left),
+            };
+            self.advance();
+
+            let right = VariableExpressionNode::new_term(self.term()?);
+            left = expression(into_term(left), right);
+        }
+    }
+
+    fn term(&mut self) -> Result<VariableTermNode, ParseError> {
+        let mut left = VariableTermNode::new_factor(self.factor()?);
+
+        loop {
+            let term = match self.peek() {
+                Some(Token::Star) => VariableTermNode::new_multiply,
+                Some(Token::Slash) => VariableTermNode::new_divide,
+                _ => return Ok(left),
+            };
+            self.advance();
+
+            let right = VariableTermNode::new_factor(self.factor()?);
+            left = term(into_factor(left), right);
+        }
+    }
+
+    fn factor(&mut self) -> Result<VariableFactorNode, ParseError> {
+        let Some((token, span)) = self.advance() else {
+            return Err(self.error("a value"));
+        };
+
+        let factor = match token {
+            Token::Number(value) => VariableFactorNode::new_literal(VariableLiteral::Number(value)),
+            Token::String(value) => VariableFactorNode::new_literal(VariableLiteral::String(value)),
+            Token::True => VariableFactorNode::new_literal(VariableLiteral::Boolean(true)),
+            Token::False => VariableFactorNode::new_literal(VariableLiteral::Boolean(false)),
+            Token::Name(name) => match self.peek() {
+                Some(Token::LeftParen) => self.call(name, span)?,
+                _ => VariableFactorNode::new_name(name),
+            },
+            Token::LeftParen => {
+                let expression = self.expression()?;
+                match self.advance() {
+                    Some((Token::RightParen, _)) => {}
+                    Some(_) => {
+                        self.position -= 1;
+                        return Err(self.error("')'"));
+                    }
+                    None => return Err(self.error("')'")),
+                }
+
+                VariableFactorNode::new_parens(expression)
+            }
+            Token::Minus => match self.factor()? {
+                VariableFactorNode::Literal(VariableLiteral::Number(value)) => {
+                    VariableFactorNode::new_literal(VariableLiteral::Number(-value))
+                }
+                factor => VariableFactorNode::new_parens(VariableExpressionNode::new_subtract(
+                    VariableTermNode::new_factor(VariableFactorNode::new_literal(
+                        VariableLiteral::Number(0.0),
+                    )),
+                    VariableExpressionNode::new_term(VariableTermNode::new_factor(factor)),
+                )),
+            },
+            _ => {
+                self.position -= 1;
+                return Err(self.error("a value"));
+            }
+        };
+
+        Ok(factor)
+    }
+
+    fn call(&mut self, name: String, span: Span) -> Result<VariableFactorNode, ParseError> {
+        let Some(function) = get_function(&name) else {
+            return Err(ParseError {
+                kind: ParseErrorKind::UnknownFunction(name),
+                span,
+            });
+        };
+
+        // Skip the opening parenthesis
+        self.advance();
+
+        let mut args = Vec::new();
+        if let Some(Token::RightParen) = self.peek() {
+            self.advance();
+        } else {
+            loop {
+                args.push(self.expression()?);
+                match self.peek() {
+                    Some(Token::Comma) => {
+                        self.advance();
+                    }
+                    Some(Token::RightParen) => {
+                        self.advance();
+                        break;
+                    }
+                    _ => return Err(self.error("',' or ')'")),
+                }
+            }
+        }
+
+        if !function.arity.contains(&args.len()) {
+            let end = self.tokens[self.position - 1].1.end;
+            return Err(ParseError {
+                kind: ParseErrorKind::WrongArgumentCount {
+                    function: name,
+                    expected: function.arity.clone(),
+                    found: args.len(),
+                },
+                span: Span::new(span.start, end),
+            });
+        }
+
+        Ok(VariableFactorNode::new_call(name, args))
+    }
+}
+
+/// Use an expression as the left hand side of an addition or subtraction.
+fn into_term(expression: VariableExpressionNode) -> VariableTermNode {
+    match expression {
+        VariableExpressionNode::Term(term) => term,
+        expression => VariableTermNode::new_factor(VariableFactorNode::new_parens(expression)),
+    }
+}
+
+/// Use a term as the left hand side of a multiplication or division.
+fn into_factor(term: VariableTermNode) -> VariableFactorNode {
+    match term {
+        VariableTermNode::Factor(factor) => factor,
+        term => VariableFactorNode::new_parens(VariableExpressionNode::new_term(term)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn evaluate(source: &str, world: &NovaEventWorld) -> VariableLiteral {
+        parse_expression(source).unwrap().evaluate(world).unwrap()
+    }
+
+    #[test]
+    fn parses_arithmetic_with_precedence() {
+        // Arrange
+        let world = NovaEventWorld::default();
+
+        // Act
+        let value = evaluate("1 + 2 * 3", &world);
+
+        // Assert
+        assert_eq!(value, VariableLiteral::Number(7.0));
+    }
+
+    #[test]
+    fn parses_left_associative_operators() {
+        // Arrange
+        let world = NovaEventWorld::default();
+
+        // Act
+        let difference = evaluate("10 - 2 - 3", &world);
+        let quotient = evaluate("12 / 2 / 3", &world);
+
+        // Assert
+        assert_eq!(difference, VariableLiteral::Number(5.0));
+        assert_eq!(quotient, VariableLiteral::Number(2.0));
+    }
+
+    #[test]
+    fn parses_names_strings_and_negation() {
+        // Arrange
+        let mut world = NovaEventWorld::default();
+        world.insert_variable("kills".to_string(), VariableLiteral::Number(2.0));
+
+        // Act
+        let kills = evaluate("kills + 1", &world);
+        let negated = evaluate("-(kills + 1)", &world);
+        let text = evaluate("'wave ' + \"one\"", &world);
+
+        // Assert
+        assert_eq!(kills, VariableLiteral::Number(3.0));
+        assert_eq!(negated, VariableLiteral::Number(-3.0));
+        assert_eq!(text, VariableLiteral::String("wave one".to_string()));
+    }
+
+    #[test]
+    fn parses_conditions() {
+        // Arrange
+        let mut world = NovaEventWorld::default();
+        world.insert_variable("score".to_string(), VariableLiteral::Number(25.0));
+        world.insert_variable("wave".to_string(), VariableLiteral::Number(2.0));
+
+        // Act
+        let condition = parse_condition("score > 10 * wave").unwrap();
+
+        // Assert
+        assert!(condition.evaluate(&world).unwrap());
+    }
+
+    #[test]
+    fn parses_comparison_and_logical_operators() {
+        // Arrange
+        let mut world = NovaEventWorld::default();
+        world.insert_variable("kills".to_string(), VariableLiteral::Number(5.0));
+        world.insert_variable("boss_alive".to_string(), VariableLiteral::Boolean(false));
+        world.insert_variable(
+            "pilot".to_string(),
+            VariableLiteral::String("ace".to_string()),
+        );
+        let sources = [
+            "kills >= 5 and kills <= 5",
+            "kills != 4 and not boss_alive",
+            "boss_alive or (kills > 4 and pilot == 'ace')",
+            "not (boss_alive or kills < 5)",
+            "(kills + 1) * 2 == 12",
+            "pilot < 'bob' and 'b' >= 'a'",
+        ];
+
+        // Act
+        let results = sources.map(|source| parse_condition(source).unwrap().evaluate(&world));
+
+        // Assert
+        for (source, result) in sources.iter().zip(results) {
+            assert!(result.unwrap(), "{} should be true", source);
+        }
+    }
+
+    #[test]
+    fn reports_type_mismatches_consistently() {
+        // Arrange
+        let world = NovaEventWorld::default();
+        let sources = ["1 <= 'a'", "true > false", "1 != 'a'", "1 and true"];
+
+        // Act
+        let errors = sources.map(|source| parse_condition(source).unwrap().evaluate(&world));
+
+        // Assert
+        for error in errors {
+            let Err(VariableError::TypeMismatch(message)) = error else {
+                panic!("expected a type mismatch, got {:?}", error);
+            };
+            assert!(message.starts_with("evaluate: "));
+        }
+    }
+
+    #[test]
+    fn reports_spans_of_errors() {
+        // Arrange
+        let sources = ["1 + ", "1 $ 2", "(1 + 2", "kills 1", "'open"];
+
+        // Act
+        let errors = sources.map(|source| parse_expression(source).unwrap_err());
+
+        // Assert
+        assert_eq!(errors[0].span, Span::new(4, 4));
+        assert_eq!(errors[1].kind, ParseErrorKind::UnexpectedCharacter('$'));
+        assert_eq!(errors[1].span, Span::new(2, 3));
+        assert_eq!(errors[2].span, Span::new(6, 6));
+        assert_eq!(errors[3].span, Span::new(6, 7));
+        assert_eq!(errors[4].kind, ParseErrorKind::UnterminatedString);
+    }
+
+    #[test]
+    fn round_trips_through_strings() {
+        // Arrange
+        let source = "not (a - b) * 2 > 'x' + c and (d or e != 1)";
+
+        // Act
+        let condition = parse_condition(source).unwrap();
+        let reparsed = parse_condition(&condition.to_string()).unwrap();
+
+        // Assert
+        assert_eq!(condition.to_string(), reparsed.to_string());
+    }
+
+    #[test]
+    fn round_trips_nested_right_operands() {
+        // Arrange
+        let world = NovaEventWorld::default();
+        let number = |value| VariableFactorNode::new_literal(VariableLiteral::Number(value));
+        let difference = VariableExpressionNode::new_subtract(
+            VariableTermNode::new_factor(number(10.0)),
+            VariableExpressionNode::new_subtract(
+                VariableTermNode::new_factor(number(2.0)),
+                VariableExpressionNode::new_term(VariableTermNode::new_factor(number(3.0))),
+            ),
+        );
+        let quotient = VariableExpressionNode::new_term(VariableTermNode::new_divide(
+            number(12.0),
+            VariableTermNode::new_divide(number(2.0), VariableTermNode::new_factor(number(3.0))),
+        ));
+
+        // Act
+        let difference_text = difference.to_string();
+        let quotient_text = quotient.to_string();
+
+        // Assert
+        assert_eq!(difference_text, "10 - (2 - 3)");
+        assert_eq!(quotient_text, "12 / (2 / 3)");
+        assert_eq!(
+            evaluate(&difference_text, &world),
+            VariableLiteral::Number(11.0)
+        );
+        assert_eq!(
+            difference.evaluate(&world).unwrap(),
+            VariableLiteral::Number(11.0)
+        );
+        assert_eq!(
+            evaluate(&quotient_text, &world),
+            VariableLiteral::Number(18.0)
+        );
+        assert_eq!(
+            quotient.evaluate(&world).unwrap(),
+            VariableLiteral::Number(18.0)
+        );
+    }
+
+    #[test]
+    fn round_trips_nested_logical_operands() {
+        // Arrange
+        let name = |name| {
+            VariableConditionNode::new_value(VariableExpressionNode::new_term(
+                VariableTermNode::new_factor(VariableFactorNode::new_name(name)),
+            ))
+        };
+        let condition = VariableConditionNode::new_and(
+            name("a"),
+            VariableConditionNode::new_or(
+                name("b"),
+                VariableConditionNode::new_and(name("c"), name("d")),
+            ),
+        );
+
+        // Act
+        let text = condition.to_string();
+        let reparsed = parse_condition(&text).unwrap();
+
+        // Assert
+        assert_eq!(text, "a and (b or c and d)");
+        assert_eq!(reparsed.to_string(), text);
+    }
+
+    #[test]
+    fn rejects_numbers_that_are_not_finite() {
+        // Arrange
+        let huge = format!("1{}", "0".repeat(400));
+        let infinite = VariableExpressionNode::new_term(VariableTermNode::new_factor(
+            VariableFactorNode::new_literal(VariableLiteral::Number(f64::INFINITY)),
+        ));
+        let nan = VariableExpressionNode::new_term(VariableTermNode::new_factor(
+            VariableFactorNode::new_literal(VariableLiteral::Number(f64::NAN)),
+        ));
+
+        // Act
+        let parsed = parse_expression(&huge);
+        let saved_infinite = ron::to_string(&infinite);
+        let saved_nan = ron::to_string(&nan);
+
+        // Assert
+        assert_eq!(
+            parsed.unwrap_err().kind,
+            ParseErrorKind::InvalidNumber(huge)
+        );
+        assert!(saved_infinite.is_err());
+        assert!(saved_nan.is_err());
+    }
+}
@@ -0,0 +1,678 @@
+//! Save and restore a running scenario, so that players can quit in the middle of a mission.
+//!
+//! A save only contains the state of the mission: the variables, objectives and timers of the
+//! `NovaEventWorld` and the physical state of the scenario objects. The objects themselves are
+//! spawned again from the `SpawnScenarioObject` actions of the scenario, matched by their id.
+
+use std::{collections::BTreeMap, path::Path, time::Duration};
+
+use avian3d::prelude::*;
+use bevy::prelude::*;
+use bevy_common_systems::prelude::*;
+use nova_events::prelude::*;
+use nova_gameplay::prelude::*;
+
+use crate::{loader::spawn_scenario, prelude::*};
+
+pub mod prelude {
+    pub use super::{
+        LoadScenarioSave, ScenarioDelaySave, ScenarioObjectSave, ScenarioSave, ScenarioSaveError,
+        ScenarioSavePlugin, ScenarioTimerSave,
+    };
+}
+
+/// The state of a running scenario.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct ScenarioSave {
+    pub scenario_id: ScenarioId,
+    pub variables: BTreeMap<String, VariableLiteral>,
+    pub objectives: Vec<ObjectiveActionConfig>,
+    pub next_scenario: Option<NextScenarioActionConfig>,
+    /// The running `Timer` actions.
+    pub timers: Vec<ScenarioTimerSave>,
+    /// The pending `Delay` actions.
+    #[serde(default)]
+    pub delays: Vec<ScenarioDelaySave>,
+    /// The scenario objects that are not destroyed.
+    pub objects: Vec<ScenarioObjectSave>,
+}
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct ScenarioTimerSave {
+    pub id: String,
+    pub interval: f32,
+    pub elapsed: f32,
+    pub repeat: bool,
+}
+
+/// A pending `Delay` action, found again in the scenario by its path.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct ScenarioDelaySave {
+    /// The path of the delay in the scenario, see `DelayActionConfig::path`.
+    pub path: Vec<usize>,
+    /// The seconds left before the actions of the delay run.
+    pub remaining: f32,
+    /// The info of the event that scheduled the delay.
+    pub info: Option<serde_json::Value>,
+}
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct ScenarioObjectSave {
+    pub id: String,
+    pub position: Vec3,
+    pub rotation: Quat,
+    pub linear_velocity: Vec3,
+    pub angular_velocity: Vec3,
+    /// The current and max health, if the object can be damaged
+    pub health: Option<(f32, f32)>,
+    /// The current and max health of the sections of a spaceship, by section id.
+    #[serde(default)]
+    pub sections: BTreeMap<String, (f32, f32)>,
+    /// The sections of a spaceship that were destroyed or split off, they are not restored.
+    #[serde(default)]
+    pub destroyed_sections: Vec<String>,
+}
+
+/// Errors that can occur while writing or reading a save.
+#[derive(Debug, thiserror::Error)]
+pub enum ScenarioSaveError {
+    #[error("could not access the save file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("could not write the save: {0}")]
+    Serialize(#[from] ron::Error),
+    #[error("could not read the save: {0}")]
+    Parse(#[from] ron::error::SpannedError),
+    #[error("timer '{id}': {field} must be {expected}, got {value}")]
+    InvalidTimer {
+        id: String,
+        field: &'static str,
+        expected: &'static str,
+        value: f32,
+    },
+    #[error("delay {path:?}: remaining must be non-negative, got {value}")]
+    InvalidDelay { path: Vec<usize>, value: f32 },
+}
+
+impl ScenarioSave {
+    /// Capture the state of the current scenario, or `None` if no scenario is loaded.
+    pub fn capture(world: &mut World) -> Option<Self> {
+        let scenario_id = world.resource::<CurrentScenario>().as_ref()?.id.clone();
+
+        // Sections that split off keep their id, but they are saved as part of their spaceship
+        let mut q_objects = world.query_filtered::<(
+            Entity,
+            &EntityId,
+            &Transform,
+            Option<&LinearVelocity>,
+            Option<&AngularVelocity>,
+            Option<&Health>,
+        ), (
+            With<ScenarioScopedMarker>,
+            Without<DestroyedMarker>,
+            Without<SectionMarker>,
+        )>();
+        let mut q_sections = world.query_filtered::<(&EntityId, &Health), (
+            With<SectionMarker>,
+            Without<DestroyedMarker>,
+        )>();
+        let objects = q_objects
+            .iter(world)
+            .map(
+                |(entity, id, transform, linear_velocity, angular_velocity, health)| {
+                    let sections = world
+                        .get::<Children>(entity)
+                        .into_iter()
+                        .flat_map(|children| children.iter())
+                        .filter_map(|child| q_sections.get(world, child).ok())
+                        .map(|(id, health)| (id.0.clone(), (health.current, health.max)))
+                        .collect::<BTreeMap<_, _>>();
+                    let destroyed_sections = world
+                        .get::<SpaceshipSectionsConfig>(entity)
+                        .into_iter()
+                        .flat_map(|config| config.iter())
+                        .filter(|section| !sections.contains_key(&section.id))
+                        .map(|section| section.id.clone())
+                        .collect();
+
+                    ScenarioObjectSave {
+                        id: id.0.clone(),
+                        position: transform.translation,
+                        rotation: transform.rotation,
+                        linear_velocity: linear_velocity.map(|v| v.0).unwrap_or_default(),
+                        angular_velocity: angular_velocity.map(|v| v.0).unwrap_or_default(),
+                        health: health.map(|health| (health.current, health.max)),
+                        sections,
+                        destroyed_sections,
+                    }
+                },
+            )
+            .collect();
+
+        let event_world = world.resource::<NovaEventWorld>();
+        let timers = event_world
+            .timers()
+            .iter()
+            .filter_map(|timer| match (&timer.id, &timer.kind) {
+                (Some(id), ScenarioTimerKind::Event) => Some(ScenarioTimerSave {
+                    id: id.clone(),
+                    interval: timer.timer.duration().as_secs_f32(),
+                    elapsed: timer.timer.elapsed_secs(),
+                    repeat: timer.timer.mode() == TimerMode::Repeating,
+                }),
+                _ => None,
+            })
+            .collect();
+        let delays = event_world
+            .timers()
+            .iter()
+            .filter_map(|timer| match &timer.kind {
+                ScenarioTimerKind::Delay(delay, info) if !delay.path.is_empty() => {
+                    Some(ScenarioDelaySave {
+                        path: delay.path.clone(),
+                        remaining: timer.timer.remaining_secs(),
+                        info: info.data.clone(),
+                    })
+                }
+                _ => None,
+            })
+            .collect();
+
+        Some(Self {
+            scenario_id,
+            variables: event_world
+                .variables()
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect(),
+            objectives: event_world.objectives().to_vec(),
+            next_scenario: event_world.next_scenario.clone(),
+            timers,
+            delays,
+            objects,
+        })
+    }
+
+    pub fn to_ron(&self) -> Result<String, ron::Error> {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+    }
+
+    /// Parse a save and check that it can be restored.
+    pub fn from_ron(source: &str) -> Result<Self, ScenarioSaveError> {
+        let save: Self = ron::from_str(source)?;
+        save.validate()?;
+
+        Ok(save)
+    }
+
+    /// Write the save to a file, creating its directory if needed.
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<(), ScenarioSaveError> {
+        let path = path.as_ref();
+        if let Some(directory) = path.parent() {
+            std::fs::create_dir_all(directory)?;
+        }
+        std::fs::write(path, self.to_ron()?)?;
+
+        Ok(())
+    }
+
+    pub fn read(path: impl AsRef<Path>) -> Result<Self, ScenarioSaveError> {
+        Self::from_ron(&std::fs::read_to_string(path)?)
+    }
+
+    /// Check the values that can't be restored, e.g a timer with a negative interval.
+    pub fn validate(&self) -> Result<(), ScenarioSaveError> {
+        for timer in self.timers.iter() {
+            if !(timer.interval > 0.0 && timer.interval.is_finite()) {
+                return Err(ScenarioSaveError::InvalidTimer {
+                    id: timer.id.clone(),
+                    field: "interval",
+                    expected: "positive",
+                    value: timer.interval,
+                });
+            }
+            if !(timer.elapsed >= 0.0 && timer.elapsed.is_finite()) {
+                return Err(ScenarioSaveError::InvalidTimer {
+                    id: timer.id.clone(),
+                    field: "elapsed",
+                    expected: "non-negative",
+                    value: timer.elapsed,
+                });
+            }
+        }
+        for delay in self.delays.iter() {
+            if !(delay.remaining >= 0.0 && delay.remaining.is_finite()) {
+                return Err(ScenarioSaveError::InvalidDelay {
+                    path: delay.path.clone(),
+                    value: delay.remaining,
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Restore a saved scenario. The scenario is loaded again without firing the `OnStartEvent`, and
+/// its state is replaced by the one in the save. The scenario is looked up in `GameScenarios`, or
+/// is the current scenario when it has the same id.
+#[derive(Event, Clone, Debug, Deref, DerefMut)]
+pub struct LoadScenarioSave(pub ScenarioSave);
+
+pub struct ScenarioSavePlugin;
+
+impl Plugin for ScenarioSavePlugin {
+    fn build(&self, app: &mut App) {
+        debug!("ScenarioSavePlugin: build");
+
+        app.add_observer(on_load_scenario_save);
+    }
+}
+
+fn on_load_scenario_save(
+    load: On<LoadScenarioSave>,
+    mut commands: Commands,
+    scenarios: Res<GameScenarios>,
+    mut current_scenario: ResMut<CurrentScenario>,
+    q_scoped: Query<Entity, With<ScenarioScopedMarker>>,
+    mut world: ResMut<NovaEventWorld>,
+) {
+    let save = &**load;
+    trace!("on_load_scenario_save: scenario {:?}", save.scenario_id);
+
+    if let Err(err) = save.validate() {
+        error!("on_load_scenario_save: invalid save: {}", err);
+        return;
+    }
+
+    let Some(scenario) = scenarios
+        .get(&save.scenario_id)
+        .or(current_scenario
+            .0
+            .as_ref()
+            .filter(|scenario| scenario.id == save.scenario_id))
+        .cloned()
+    else {
+        error!(
+            "on_load_scenario_save: scenario {:?} not found in scenarios",
+            save.scenario_id
+        );
+        return;
+    };
+
+    world.clear();
+    for entity in q_scoped.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    **current_scenario = Some(scenario.clone());
+    debug!("on_load_scenario_save: scenario {:?}", scenario.name);
+
+    spawn_scenario(&mut commands, &scenario);
+
+    for (key, value) in save.variables.iter() {
+        world.insert_variable(key.clone(), value.clone());
+    }
+    for objective in save.objectives.iter() {
+        world.push_objective(objective.clone());
+    }
+    world.next_scenario = save.next_scenario.clone();
+    for timer in save.timers.iter() {
+        let mode = if timer.repeat {
+            TimerMode::Repeating
+        } else {
+            TimerMode::Once
+        };
+        let mut bevy_timer = Timer::from_seconds(timer.interval, mode);
+        bevy_timer.set_elapsed(Duration::from_secs_f32(timer.elapsed));
+
+        world.push_timer(ScenarioTimer {
+            id: Some(timer.id.clone()),
+            timer: bevy_timer,
+            kind: ScenarioTimerKind::Event,
+        });
+    }
+    for delay in save.delays.iter() {
+        let Some(config) = DelayActionConfig::find(&scenario.events, &delay.path) else {
+            warn!(
+                "on_load_scenario_save: delay {:?} not found in scenario {:?}",
+                delay.path, scenario.id
+            );
+            continue;
+        };
+
+        // Only the delays of the event handlers have paths, the restored delay needs its own to be
+        // saved again
+        let mut config = config.clone();
+        config.path = delay.path.clone();
+        DelayActionConfig::set_paths(&mut config.actions, &config.path);
+        let mut timer = Timer::from_seconds(config.seconds, TimerMode::Once);
+        timer.set_elapsed(Duration::from_secs_f32(
+            (config.seconds - delay.remaining).max(0.0),
+        ));
+
+        world.push_timer(ScenarioTimer {
+            id: None,
+            timer,
+            kind: ScenarioTimerKind::Delay(
+                config,
+                GameEventInfo {
+                    data: delay.info.clone(),
+                },
+            ),
+        });
+    }
+
+    let actions = scenario
+        .events
+        .iter()
+        .flat_map(|event| event.actions.iter())
+        .collect::<Vec<_>>();
+    for object in save.objects.iter() {
+        let Some(config) = find_object_config(&actions, &object.id) else {
+            warn!(
+                "on_load_scenario_save: object {:?} is not spawned by scenario {:?}",
+                object.id, scenario.id
+            );
+            continue;
+        };
+
+        let mut config = config.clone();
+        config.base.position = object.position;
+        config.base.rotation = object.rotation;
+        if let ScenarioObjectKind::Spaceship(spaceship) = &mut config.kind {
+            spaceship
+                .sections
+                .retain(|section| !object.destroyed_sections.contains(&section.id));
+        }
+
+        let mut entity_commands = config.spawn(&mut commands);
+        entity_commands.insert((
+            LinearVelocity(object.linear_velocity),
+            AngularVelocity(object.angular_velocity),
+        ));
+        if let Some((current, max)) = object.health {
+            entity_commands.insert(Health { current, max });
+        }
+
+        // The sections are spawned when the spaceship is added, their health is set after that
+        let entity = entity_commands.id();
+        if !object.sections.is_empty() {
+            let sections = object.sections.clone();
+            commands.queue(move |world: &mut World| {
+                restore_section_health(world, entity, &sections);
+            });
+        }
+    }
+
+    commands.trigger(ScenarioLoaded);
+}
+
+/// Set the health of the sections of a restored spaceship.
+fn restore_section_health(
+    world: &mut World,
+    spaceship: Entity,
+    sections: &BTreeMap<String, (f32, f32)>,
+) {
+    let children = world
+        .get::<Children>(spaceship)
+        .map(|children| children.to_vec())
+        .unwrap_or_default();
+
+    for child in children {
+        let Some(id) = world.get::<EntityId>(child).map(|id| id.0.clone()) else {
+            continue;
+        };
+        let Some(&(current, max)) = sections.get(&id) else {
+            continue;
+        };
+
+        if let Some(mut health) = world.get_mut::<Health>(child) {
+            *health = Health { current, max };
+        }
+    }
+}
+
+/// Find the `SpawnScenarioObject` action that spawns the object with the given id.
+fn find_object_config<'a>(
+    actions: &[&'a EventActionConfig],
+    id: &str,
+) -> Option<&'a ScenarioObjectConfig> {
+    actions.iter().copied().find_map(|action| match action {
+        EventActionConfig::SpawnScenarioObject(config) if config.base.id == id => Some(config),
+        EventActionConfig::Delay(config) => {
+            find_object_config(&config.actions.iter().collect::<Vec<_>>(), id)
+        }
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn captures_and_serializes_the_scenario_state() {
+        // Arrange
+        let mut world = World::new();
+        world.insert_resource(CurrentScenario(Some(ScenarioConfig {
+            id: "asteroid_field".to_string(),
+            name: "Asteroid Field".to_string(),
+            description: String::new(),
+            cubemap: Handle::default(),
+            events: vec![],
+        })));
+        let mut event_world = NovaEventWorld::default();
+        event_world.insert_variable("kills".to_string(), VariableLiteral::Number(3.0));
+        event_world.push_objective(ObjectiveActionConfig::new("kill", "Destroy the asteroids"));
+        world.insert_resource(event_world);
+
+        world.spawn((
+            ScenarioScopedMarker,
+            EntityId::new("asteroid_1"),
+            Transform::from_xyz(1.0, 2.0, 3.0),
+            LinearVelocity(Vec3::X),
+            Health {
+                current: 40.0,
+                max: 100.0,
+            },
+        ));
+        world.spawn((
+            ScenarioScopedMarker,
+            EntityId::new("asteroid_2"),
+            Transform::default(),
+            DestroyedMarker,
+        ));
+
+        // Act
+        let save = ScenarioSave::capture(&mut world).unwrap();
+        let save = ScenarioSave::from_ron(&save.to_ron().unwrap()).unwrap();
+
+        // Assert
+        assert_eq!(save.scenario_id, "asteroid_field");
+        assert_eq!(
+            save.variables.get("kills"),
+            Some(&VariableLiteral::Number(3.0))
+        );
+        assert_eq!(save.objectives.len(), 1);
+        assert_eq!(save.objects.len(), 1);
+        assert_eq!(save.objects[0].id, "asteroid_1");
+        assert_eq!(save.objects[0].position, Vec3::new(1.0, 2.0, 3.0));
+        assert_eq!(save.objects[0].linear_velocity, Vec3::X);
+        assert_eq!(save.objects[0].health, Some((40.0, 100.0)));
+    }
+
+    #[test]
+    fn captures_the_sections_of_spaceships() {
+        // Arrange
+        let mut world = World::new();
+        world.insert_resource(CurrentScenario(Some(ScenarioConfig {
+            id: "asteroid_field".to_string(),
+            name: "Asteroid Field".to_string(),
+            description: String::new(),
+            cubemap: Handle::default(),
+            events: vec![],
+        })));
+        world.init_resource::<NovaEventWorld>();
+
+        let section = |id: &str| SpaceshipSectionConfig {
+            id: id.to_string(),
+            position: Vec3::ZERO,
+            rotation: Quat::IDENTITY,
+            config: SectionConfig {
+                base: BaseSectionConfig::default(),
+                kind: SectionKind::Hull(HullSectionConfig { render_mesh: None }),
+            },
+        };
+        let spaceship = world
+            .spawn((
+                ScenarioScopedMarker,
+                EntityId::new("spaceship"),
+                Transform::default(),
+                SpaceshipSectionsConfig(vec![section("hull"), section("wing"), section("tail")]),
+            ))
+            .id();
+        world.spawn((
+            SectionMarker,
+            EntityId::new("hull"),
+            Health {
+                current: 30.0,
+                max: 100.0,
+            },
+            ChildOf(spaceship),
+        ));
+        world.spawn((
+            SectionMarker,
+            EntityId::new("wing"),
+            Health {
+                current: 0.0,
+                max: 100.0,
+            },
+            DestroyedMarker,
+            ChildOf(spaceship),
+        ));
+        // The tail split off as a wreck, it is still in the scenario
+        world.spawn((
+            ScenarioScopedMarker,
+            SectionMarker,
+            EntityId::new("tail"),
+            Transform::default(),
+            Health::new(100.0),
+        ));
+
+        // Act
+        let save = ScenarioSave::capture(&mut world).unwrap();
+        let save = ScenarioSave::from_ron(&save.to_ron().unwrap()).unwrap();
+
+        // Assert
+        assert_eq!(save.objects.len(), 1);
+        let object = &save.objects[0];
+        assert_eq!(object.id, "spaceship");
+        assert_eq!(object.sections.len(), 1);
+        assert_eq!(object.sections.get("hull"), Some(&(30.0, 100.0)));
+        assert_eq!(
+            object.destroyed_sections,
+            vec!["wing".to_string(), "tail".to_string()]
+        );
+    }
+
+    #[test]
+    fn captures_pending_delays_by_their_path() {
+        // Arrange
+        let delay = |actions: Vec<EventActionConfig>| {
+            EventActionConfig::Delay(DelayActionConfig {
+                seconds: 10.0,
+                actions,
+                path: Vec::new(),
+            })
+        };
+        let mut events = vec![ScenarioEventConfig {
+            name: EventConfig::OnStart,
+            filters: Vec::new(),
+            actions: vec![
+                EventActionConfig::DebugMessage(DebugMessageActionConfig {
+                    message: "start".to_string(),
+                }),
+                delay(vec![delay(Vec::new())]),
+            ],
+        }];
+        DelayActionConfig::set_paths(&mut events[0].actions, &[0]);
+        let EventActionConfig::Delay(outer) = &events[0].actions[1] else {
+            unreachable!();
+        };
+        let EventActionConfig::Delay(inner) = &outer.actions[0] else {
+            unreachable!();
+        };
+
+        let mut world = World::new();
+        world.insert_resource(CurrentScenario(Some(ScenarioConfig {
+            id: "asteroid_field".to_string(),
+            name: "Asteroid Field".to_string(),
+            description: String::new(),
+            cubemap: Handle::default(),
+            events: events.clone(),
+        })));
+        let mut event_world = NovaEventWorld::default();
+        inner.action(&mut event_world, &GameEventInfo::default());
+        event_world.tick_timers(Duration::from_secs_f32(4.0));
+        world.insert_resource(event_world);
+
+        // Act
+        let save = ScenarioSave::capture(&mut world).unwrap();
+        let save = ScenarioSave::from_ron(&save.to_ron().unwrap()).unwrap();
+
+        // Assert
+        assert_eq!(save.delays.len(), 1);
+        assert_eq!(save.delays[0].path, vec![0, 1, 0]);
+        assert_eq!(save.delays[0].remaining, 6.0);
+        assert!(DelayActionConfig::find(&events, &save.delays[0].path).is_some());
+        assert!(DelayActionConfig::find(&events, &[0, 0]).is_none());
+    }
+
+    #[test]
+    fn rejects_saves_with_invalid_timers() {
+        // Arrange
+        let save = |interval: f32| {
+            format!(
+                "(scenario_id: \"test\", variables: {{}}, objectives: [], next_scenario: None, \
+                 timers: [(id: \"tick\", interval: {:?}, elapsed: 0.0, repeat: true)], objects: [])",
+                interval
+            )
+        };
+
+        // Act
+        let valid = ScenarioSave::from_ron(&save(1.0));
+        let negative = ScenarioSave::from_ron(&save(-1.0));
+        let zero = ScenarioSave::from_ron(&save(0.0));
+
+        // Assert
+        assert!(valid.is_ok());
+        assert!(matches!(
+            negative,
+            Err(ScenarioSaveError::InvalidTimer {
+                field: "interval",
+                ..
+            })
+        ));
+        assert!(matches!(
+            zero,
+            Err(ScenarioSaveError::InvalidTimer {
+                field: "interval",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn captures_nothing_without_a_scenario() {
+        // Arrange
+        let mut world = World::new();
+        world.init_resource::<CurrentScenario>();
+        world.init_resource::<NovaEventWorld>();
+
+        // Act
+        let save = ScenarioSave::capture(&mut world);
+
+        // Assert
+        assert!(save.is_none());
+    }
+}
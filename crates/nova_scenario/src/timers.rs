@@ -0,0 +1,118 @@
+//! Timers scheduled by the `Delay` and `Timer` scenario actions.
+
+use bevy::prelude::*;
+use bevy_common_systems::prelude::*;
+use nova_events::prelude::*;
+
+use crate::prelude::*;
+
+pub mod prelude {
+    pub use super::{ScenarioTimer, ScenarioTimerKind, ScenarioTimersPlugin, TIMER_TYPE_NAME};
+}
+
+/// The type name in the info of an `OnTimerEvent`, so that `EntityFilterConfig` can match timers.
+pub const TIMER_TYPE_NAME: &str = "timer";
+
+/// A timer stored in the `NovaEventWorld`. It is cleared together with the scenario.
+#[derive(Clone, Debug)]
+pub struct ScenarioTimer {
+    pub id: Option<String>,
+    pub timer: Timer,
+    pub kind: ScenarioTimerKind,
+}
+
+#[derive(Clone, Debug)]
+pub enum ScenarioTimerKind {
+    /// Run the actions of the delay with the info of the event that scheduled it.
+    Delay(DelayActionConfig, GameEventInfo),
+    /// Fire an `OnTimerEvent` with the id of the timer.
+    Event,
+}
+
+pub struct ScenarioTimersPlugin;
+
+impl Plugin for ScenarioTimersPlugin {
+    fn build(&self, app: &mut App) {
+        debug!("ScenarioTimersPlugin: build");
+
+        app.add_systems(Update, tick_scenario_timers);
+    }
+}
+
+fn tick_scenario_timers(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut world: ResMut<NovaEventWorld>,
+) {
+    // Ticking the timers should not trigger the event processing, only the timers that fired
+    let fired = world.bypass_change_detection().tick_timers(time.delta());
+
+    for timer in fired {
+        match timer.kind {
+            ScenarioTimerKind::Delay(delay, info) => {
+                trace!("tick_scenario_timers: delay finished, info {:?}", info);
+
+                for action in delay.actions.iter() {
+                    action.action(&mut world, &info);
+                }
+            }
+            ScenarioTimerKind::Event => {
+                let id = timer.id.unwrap_or_default();
+                trace!("tick_scenario_timers: timer {:?} fired", id);
+
+                commands.fire::<OnTimerEvent>(OnTimerEventInfo {
+                    id,
+                    type_name: TIMER_TYPE_NAME.to_string(),
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn timer(id: &str, seconds: f32, mode: TimerMode) -> ScenarioTimer {
+        ScenarioTimer {
+            id: Some(id.to_string()),
+            timer: Timer::from_seconds(seconds, mode),
+            kind: ScenarioTimerKind::Event,
+        }
+    }
+
+    #[test]
+    fn fires_once_timers_a_single_time() {
+        // Arrange
+        let mut world = NovaEventWorld::default();
+        world.push_timer(timer("countdown", 1.0, TimerMode::Once));
+
+        // Act
+        let early = world.tick_timers(Duration::from_secs_f32(0.5));
+        let fired = world.tick_timers(Duration::from_secs_f32(0.5));
+        let late = world.tick_timers(Duration::from_secs_f32(5.0));
+
+        // Assert
+        assert!(early.is_empty());
+        assert_eq!(fired.len(), 1);
+        assert!(late.is_empty());
+    }
+
+    #[test]
+    fn fires_repeating_timers_until_cancelled() {
+        // Arrange
+        let mut world = NovaEventWorld::default();
+        world.push_timer(timer("waves", 1.0, TimerMode::Repeating));
+
+        // Act
+        let fired = world.tick_timers(Duration::from_secs_f32(2.5));
+        world.cancel_timer("waves");
+        let cancelled = world.tick_timers(Duration::from_secs_f32(2.5));
+
+        // Assert
+        assert_eq!(fired.len(), 2);
+        assert!(cancelled.is_empty());
+    }
+}
@@ -1,3 +1,5 @@
+use std::{cmp::Ordering, fmt};
+
 use bevy::prelude::*;
 
 use crate::prelude::*;
@@ -14,20 +16,48 @@ pub enum VariableError {
     UndefinedVariable(String),
     TypeMismatch(String),
     DivisionByZero,
+    UndefinedFunction(String),
+    InvalidArguments(String),
+    UndefinedEntity(String),
 }
 
-#[derive(Clone, Debug, PartialEq)]
+impl VariableError {
+    /// The error for an operation that does not support the types of its operands.
+    pub fn type_mismatch(
+        operation: &str,
+        expected: &str,
+        left: &VariableLiteral,
+        right: &VariableLiteral,
+    ) -> Self {
+        VariableError::TypeMismatch(format!(
+            "evaluate: lhs and rhs must be {} for {}, but got {:?} and {:?}",
+            expected, operation, left, right
+        ))
+    }
+
+    /// The error for a function that does not support the types of its arguments.
+    pub fn argument_mismatch(function: &str, expected: &str, args: &[VariableLiteral]) -> Self {
+        VariableError::TypeMismatch(format!(
+            "evaluate: arguments must be {} for {}, but got {:?}",
+            expected, function, args
+        ))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
 pub enum VariableLiteral {
     String(String),
     Number(f64),
     Boolean(bool),
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub enum VariableFactorNode {
     Parens(Box<VariableExpressionNode>),
     Literal(VariableLiteral),
     Name(String),
+    /// A call to one of the `BUILTIN_FUNCTIONS`
+    Call(String, Vec<VariableExpressionNode>),
 }
 
 impl VariableFactorNode {
@@ -43,6 +73,10 @@ impl VariableFactorNode {
         VariableFactorNode::Parens(Box::new(expr))
     }
 
+    pub fn new_call<S: Into<String>>(name: S, args: Vec<VariableExpressionNode>) -> Self {
+        VariableFactorNode::Call(name.into(), args)
+    }
+
     pub fn evaluate(&self, world: &NovaEventWorld) -> Result<VariableLiteral, VariableError> {
         match self {
             VariableFactorNode::Parens(expr) => expr.evaluate(world),
@@ -51,11 +85,28 @@ impl VariableFactorNode {
                 .get_variable(name)
                 .cloned()
                 .ok_or_else(|| VariableError::UndefinedVariable(name.clone())),
+            VariableFactorNode::Call(name, args) => {
+                let function = get_function(name)
+                    .ok_or_else(|| VariableError::UndefinedFunction(name.clone()))?;
+                if !function.arity.contains(&args.len()) {
+                    return Err(VariableError::InvalidArguments(format!(
+                        "evaluate: {} does not accept {} arguments",
+                        name,
+                        args.len()
+                    )));
+                }
+
+                let args = args
+                    .iter()
+                    .map(|arg| arg.evaluate(world))
+                    .collect::<Result<Vec<_>, _>>()?;
+                (function.call)(world, &args)
+            }
         }
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub enum VariableTermNode {
     Multiply(Box<VariableFactorNode>, Box<VariableTermNode>),
     Divide(Box<VariableFactorNode>, Box<VariableTermNode>),
@@ -87,8 +138,11 @@ impl VariableTermNode {
                     (VariableLiteral::Boolean(l), VariableLiteral::Boolean(r)) => {
                         Ok(VariableLiteral::Boolean(l && r))
                     }
-                    (left_val, right_val) => Err(VariableError::TypeMismatch(
-                        format!("evaluate: lhs and rhs must be numbers or booleans for multiplication, but got {:?} and {:?}", left_val, right_val)
+                    (left_val, right_val) => Err(VariableError::type_mismatch(
+                        "multiplication",
+                        "numbers or booleans",
+                        &left_val,
+                        &right_val,
                     )),
                 }
             }
@@ -103,10 +157,9 @@ impl VariableTermNode {
                             Ok(VariableLiteral::Number(l / r))
                         }
                     }
-                    (left_val, right_val) => Err(VariableError::TypeMismatch(format!(
-                        "evaluate: lhs and rhs must be numbers for division, but got {:?} and {:?}",
-                        left_val, right_val
-                    ))),
+                    (left_val, right_val) => Err(VariableError::type_mismatch(
+                        "division", "numbers", &left_val, &right_val,
+                    )),
                 }
             }
             VariableTermNode::Factor(factor) => factor.evaluate(world),
@@ -114,7 +167,9 @@ impl VariableTermNode {
     }
 }
 
-#[derive(Clone, Debug)]
+/// Expressions are written as text in scenario files, e.g `kills + 1`.
+#[derive(Clone, Debug, serde::Deserialize)]
+#[serde(try_from = "String")]
 pub enum VariableExpressionNode {
     Add(Box<VariableTermNode>, Box<VariableExpressionNode>),
     Subtract(Box<VariableTermNode>, Box<VariableExpressionNode>),
@@ -149,8 +204,11 @@ impl VariableExpressionNode {
                     (VariableLiteral::String(l), VariableLiteral::String(r)) => {
                         Ok(VariableLiteral::String(l + &r))
                     }
-                    (left_val, right_val) => Err(VariableError::TypeMismatch(
-                        format!("evaluate: lhs and rhs must be numbers, booleans, or strings for addition, but got {:?} and {:?}", left_val, right_val)
+                    (left_val, right_val) => Err(VariableError::type_mismatch(
+                        "addition",
+                        "numbers, booleans, or strings",
+                        &left_val,
+                        &right_val,
                     )),
                 }
             }
@@ -161,8 +219,11 @@ impl VariableExpressionNode {
                     (VariableLiteral::Number(l), VariableLiteral::Number(r)) => {
                         Ok(VariableLiteral::Number(l - r))
                     }
-                    (left_val, right_val) => Err(VariableError::TypeMismatch(
-                        format!("evaluate: lhs and rhs must be numbers for subtraction, but got {:?} and {:?}", left_val, right_val)
+                    (left_val, right_val) => Err(VariableError::type_mismatch(
+                        "subtraction",
+                        "numbers",
+                        &left_val,
+                        &right_val,
                     )),
                 }
             }
@@ -171,11 +232,21 @@ impl VariableExpressionNode {
     }
 }
 
-#[derive(Clone, Debug)]
+/// Conditions are written as text in scenario files, e.g `score > 10 * wave and not boss_alive`.
+#[derive(Clone, Debug, serde::Deserialize)]
+#[serde(try_from = "String")]
 pub enum VariableConditionNode {
     LessThan(Box<VariableExpressionNode>, Box<VariableExpressionNode>),
+    LessEqual(Box<VariableExpressionNode>, Box<VariableExpressionNode>),
     GreaterThan(Box<VariableExpressionNode>, Box<VariableExpressionNode>),
+    GreaterEqual(Box<VariableExpressionNode>, Box<VariableExpressionNode>),
     Equal(Box<VariableExpressionNode>, Box<VariableExpressionNode>),
+    NotEqual(Box<VariableExpressionNode>, Box<VariableExpressionNode>),
+    And(Box<VariableConditionNode>, Box<VariableConditionNode>),
+    Or(Box<VariableConditionNode>, Box<VariableConditionNode>),
+    Not(Box<VariableConditionNode>),
+    /// An expression that must evaluate to a boolean, e.g a flag variable.
+    Value(Box<VariableExpressionNode>),
 }
 
 impl VariableConditionNode {
@@ -183,48 +254,338 @@ impl VariableConditionNode {
         VariableConditionNode::LessThan(Box::new(left), Box::new(right))
     }
 
+    pub fn new_less_equal(left: VariableExpressionNode, right: VariableExpressionNode) -> Self {
+        VariableConditionNode::LessEqual(Box::new(left), Box::new(right))
+    }
+
     pub fn new_greater_than(left: VariableExpressionNode, right: VariableExpressionNode) -> Self {
         VariableConditionNode::GreaterThan(Box::new(left), Box::new(right))
     }
 
+    pub fn new_greater_equal(left: VariableExpressionNode, right: VariableExpressionNode) -> Self {
+        VariableConditionNode::GreaterEqual(Box::new(left), Box::new(right))
+    }
+
     pub fn new_equals(left: VariableExpressionNode, right: VariableExpressionNode) -> Self {
         VariableConditionNode::Equal(Box::new(left), Box::new(right))
     }
 
+    pub fn new_not_equals(left: VariableExpressionNode, right: VariableExpressionNode) -> Self {
+        VariableConditionNode::NotEqual(Box::new(left), Box::new(right))
+    }
+
+    pub fn new_and(left: VariableConditionNode, right: VariableConditionNode) -> Self {
+        VariableConditionNode::And(Box::new(left), Box::new(right))
+    }
+
+    pub fn new_or(left: VariableConditionNode, right: VariableConditionNode) -> Self {
+        VariableConditionNode::Or(Box::new(left), Box::new(right))
+    }
+
+    pub fn new_not(inner: VariableConditionNode) -> Self {
+        VariableConditionNode::Not(Box::new(inner))
+    }
+
+    pub fn new_value(expr: VariableExpressionNode) -> Self {
+        VariableConditionNode::Value(Box::new(expr))
+    }
+
     pub fn evaluate(&self, world: &NovaEventWorld) -> Result<bool, VariableError> {
         match self {
             VariableConditionNode::LessThan(left, right) => {
-                let left_val = left.evaluate(world)?;
-                let right_val = right.evaluate(world)?;
-                match (left_val, right_val) {
-                    (VariableLiteral::Number(l), VariableLiteral::Number(r)) => Ok(l < r),
-                    (left_val, right_val) => Err(VariableError::TypeMismatch(
-                        format!("evaluate: lhs and rhs must be numbers for less than comparison, but got {:?} and {:?}", left_val, right_val)
-                    )),
-                }
+                compare(left, right, world, "less than comparison").map(Ordering::is_lt)
+            }
+            VariableConditionNode::LessEqual(left, right) => {
+                compare(left, right, world, "less or equal comparison").map(Ordering::is_le)
             }
             VariableConditionNode::GreaterThan(left, right) => {
-                let left_val = left.evaluate(world)?;
-                let right_val = right.evaluate(world)?;
-                match (left_val, right_val) {
-                    (VariableLiteral::Number(l), VariableLiteral::Number(r)) => Ok(l > r),
-                    (left_val, right_val) => Err(VariableError::TypeMismatch(
-                        format!("evaluate: lhs and rhs must be numbers for greater than comparison, but got {:?} and {:?}", left_val, right_val)
-                    )),
-                }
+                compare(left, right, world, "greater than comparison").map(Ordering::is_gt)
+            }
+            VariableConditionNode::GreaterEqual(left, right) => {
+                compare(left, right, world, "greater or equal comparison").map(Ordering::is_ge)
             }
             VariableConditionNode::Equal(left, right) => {
-                let left_val = left.evaluate(world)?;
-                let right_val = right.evaluate(world)?;
-                match (left_val, right_val) {
-                    (VariableLiteral::Number(l), VariableLiteral::Number(r)) => Ok(l == r),
-                    (VariableLiteral::Boolean(l), VariableLiteral::Boolean(r)) => Ok(l == r),
-                    (VariableLiteral::String(l), VariableLiteral::String(r)) => Ok(l == r),
-                    (left_val, right_val) => Err(VariableError::TypeMismatch(
-                        format!("evaluate: lhs and rhs must be of the same type for equality comparison, but got {:?} and {:?}", left_val, right_val)
-                    )),
+                equals(left, right, world, "equality comparison")
+            }
+            VariableConditionNode::NotEqual(left, right) => {
+                equals(left, right, world, "inequality comparison").map(|equal| !equal)
+            }
+            VariableConditionNode::And(left, right) => {
+                Ok(left.evaluate(world)? && right.evaluate(world)?)
+            }
+            VariableConditionNode::Or(left, right) => {
+                Ok(left.evaluate(world)? || right.evaluate(world)?)
+            }
+            VariableConditionNode::Not(inner) => Ok(!inner.evaluate(world)?),
+            VariableConditionNode::Value(expr) => match expr.evaluate(world)? {
+                VariableLiteral::Boolean(value) => Ok(value),
+                value => Err(VariableError::TypeMismatch(format!(
+                    "evaluate: value must be a boolean for a condition, but got {:?}",
+                    value
+                ))),
+            },
+        }
+    }
+}
+
+/// Order two values. Numbers are compared by value and strings lexicographically.
+fn compare(
+    left: &VariableExpressionNode,
+    right: &VariableExpressionNode,
+    world: &NovaEventWorld,
+    operation: &str,
+) -> Result<Ordering, VariableError> {
+    let left_val = left.evaluate(world)?;
+    let right_val = right.evaluate(world)?;
+    let ordering = match (&left_val, &right_val) {
+        (VariableLiteral::Number(l), VariableLiteral::Number(r)) => l.partial_cmp(r),
+        (VariableLiteral::String(l), VariableLiteral::String(r)) => Some(l.cmp(r)),
+        _ => None,
+    };
+
+    ordering.ok_or_else(|| {
+        VariableError::type_mismatch(operation, "numbers or strings", &left_val, &right_val)
+    })
+}
+
+/// Check two values of the same type for equality.
+fn equals(
+    left: &VariableExpressionNode,
+    right: &VariableExpressionNode,
+    world: &NovaEventWorld,
+    operation: &str,
+) -> Result<bool, VariableError> {
+    let left_val = left.evaluate(world)?;
+    let right_val = right.evaluate(world)?;
+    match (&left_val, &right_val) {
+        (VariableLiteral::Number(l), VariableLiteral::Number(r)) => Ok(l == r),
+        (VariableLiteral::Boolean(l), VariableLiteral::Boolean(r)) => Ok(l == r),
+        (VariableLiteral::String(l), VariableLiteral::String(r)) => Ok(l == r),
+        _ => Err(VariableError::type_mismatch(
+            operation,
+            "of the same type",
+            &left_val,
+            &right_val,
+        )),
+    }
+}
+
+impl fmt::Display for VariableLiteral {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VariableLiteral::String(value) => {
+                write!(f, "\"")?;
+                for c in value.chars() {
+                    match c {
+                        '"' => write!(f, "\\\"")?,
+                        '\\' => write!(f, "\\\\")?,
+                        '\n' => write!(f, "\\n")?,
+                        '\t' => write!(f, "\\t")?,
+                        c => write!(f, "{}", c)?,
+                    }
                 }
+                write!(f, "\"")
             }
+            VariableLiteral::Number(value) => write!(f, "{}", value),
+            VariableLiteral::Boolean(value) => write!(f, "{}", value),
         }
     }
 }
+
+impl fmt::Display for VariableFactorNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VariableFactorNode::Parens(expr) => write!(f, "({})", expr),
+            VariableFactorNode::Literal(lit) => write!(f, "{}", lit),
+            VariableFactorNode::Name(name) => write!(f, "{}", name),
+            VariableFactorNode::Call(name, args) => {
+                write!(f, "{}(", name)?;
+                for (index, arg) in args.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", arg)?;
+                }
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+// The operators are left associative, so a compound right hand side is written in parentheses to
+// keep its grouping, e.g `Subtract(a, Subtract(b, c))` is `a - (b - c)`.
+
+impl fmt::Display for VariableTermNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (operator, left, right) = match self {
+            VariableTermNode::Multiply(left, right) => ("*", left, right),
+            VariableTermNode::Divide(left, right) => ("/", left, right),
+            VariableTermNode::Factor(factor) => return write!(f, "{}", factor),
+        };
+
+        write!(f, "{} {} ", left, operator)?;
+        write_operand(f, &**right, !matches!(**right, VariableTermNode::Factor(_)))
+    }
+}
+
+impl fmt::Display for VariableExpressionNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (operator, left, right) = match self {
+            VariableExpressionNode::Add(left, right) => ("+", left, right),
+            VariableExpressionNode::Subtract(left, right) => ("-", left, right),
+            VariableExpressionNode::Term(term) => return write!(f, "{}", term),
+        };
+
+        write!(f, "{} {} ", left, operator)?;
+        write_operand(
+            f,
+            &**right,
+            !matches!(**right, VariableExpressionNode::Term(_)),
+        )
+    }
+}
+
+impl fmt::Display for VariableConditionNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VariableConditionNode::LessThan(left, right) => write!(f, "{} < {}", left, right),
+            VariableConditionNode::LessEqual(left, right) => write!(f, "{} <= {}", left, right),
+            VariableConditionNode::GreaterThan(left, right) => write!(f, "{} > {}", left, right),
+            VariableConditionNode::GreaterEqual(left, right) => write!(f, "{} >= {}", left, right),
+            VariableConditionNode::Equal(left, right) => write!(f, "{} == {}", left, right),
+            VariableConditionNode::NotEqual(left, right) => write!(f, "{} != {}", left, right),
+            VariableConditionNode::And(left, right) => {
+                write_operand(f, &**left, matches!(**left, VariableConditionNode::Or(..)))?;
+                write!(f, " and ")?;
+                write_operand(
+                    f,
+                    &**right,
+                    matches!(
+                        **right,
+                        VariableConditionNode::And(..) | VariableConditionNode::Or(..)
+                    ),
+                )
+            }
+            VariableConditionNode::Or(left, right) => {
+                write!(f, "{} or ", left)?;
+                write_operand(
+                    f,
+                    &**right,
+                    matches!(**right, VariableConditionNode::Or(..)),
+                )
+            }
+            VariableConditionNode::Not(inner) => {
+                write!(f, "not ")?;
+                write_operand(
+                    f,
+                    &**inner,
+                    matches!(
+                        **inner,
+                        VariableConditionNode::And(..) | VariableConditionNode::Or(..)
+                    ),
+                )
+            }
+            VariableConditionNode::Value(expr) => write!(f, "{}", expr),
+        }
+    }
+}
+
+/// Write an operand of an operator, in parentheses when it would not be grouped the same way
+/// when parsed back.
+fn write_operand(
+    f: &mut fmt::Formatter<'_>,
+    operand: &impl fmt::Display,
+    needs_parens: bool,
+) -> fmt::Result {
+    if needs_parens {
+        write!(f, "({})", operand)
+    } else {
+        write!(f, "{}", operand)
+    }
+}
+
+impl VariableFactorNode {
+    /// Whether all the number literals are finite. NaN and infinity have no text form, they would
+    /// be read back as variable names.
+    pub fn is_finite(&self) -> bool {
+        match self {
+            VariableFactorNode::Parens(expr) => expr.is_finite(),
+            VariableFactorNode::Literal(VariableLiteral::Number(value)) => value.is_finite(),
+            VariableFactorNode::Literal(_) | VariableFactorNode::Name(_) => true,
+            VariableFactorNode::Call(_, args) => args.iter().all(|arg| arg.is_finite()),
+        }
+    }
+}
+
+impl VariableTermNode {
+    /// Whether all the number literals are finite.
+    pub fn is_finite(&self) -> bool {
+        match self {
+            VariableTermNode::Multiply(left, right) | VariableTermNode::Divide(left, right) => {
+                left.is_finite() && right.is_finite()
+            }
+            VariableTermNode::Factor(factor) => factor.is_finite(),
+        }
+    }
+}
+
+impl VariableExpressionNode {
+    /// Whether all the number literals are finite.
+    pub fn is_finite(&self) -> bool {
+        match self {
+            VariableExpressionNode::Add(left, right)
+            | VariableExpressionNode::Subtract(left, right) => {
+                left.is_finite() && right.is_finite()
+            }
+            VariableExpressionNode::Term(term) => term.is_finite(),
+        }
+    }
+}
+
+impl VariableConditionNode {
+    /// Whether all the number literals are finite.
+    pub fn is_finite(&self) -> bool {
+        match self {
+            VariableConditionNode::LessThan(left, right)
+            | VariableConditionNode::LessEqual(left, right)
+            | VariableConditionNode::GreaterThan(left, right)
+            | VariableConditionNode::GreaterEqual(left, right)
+            | VariableConditionNode::Equal(left, right)
+            | VariableConditionNode::NotEqual(left, right) => left.is_finite() && right.is_finite(),
+            VariableConditionNode::And(left, right) | VariableConditionNode::Or(left, right) => {
+                left.is_finite() && right.is_finite()
+            }
+            VariableConditionNode::Not(inner) => inner.is_finite(),
+            VariableConditionNode::Value(expr) => expr.is_finite(),
+        }
+    }
+}
+
+// Expressions and conditions are saved as text, which fails for the values that can't be parsed
+// back.
+
+impl serde::Serialize for VariableExpressionNode {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if !self.is_finite() {
+            return Err(serde::ser::Error::custom(format!(
+                "expression '{}' has a number that is not finite",
+                self
+            )));
+        }
+
+        serializer.collect_str(self)
+    }
+}
+
+impl serde::Serialize for VariableConditionNode {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if !self.is_finite() {
+            return Err(serde::ser::Error::custom(format!(
+                "condition '{}' has a number that is not finite",
+                self
+            )));
+        }
+
+        serializer.collect_str(self)
+    }
+}
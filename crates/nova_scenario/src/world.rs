@@ -1,8 +1,15 @@
-use std::collections::VecDeque;
+use std::{
+    collections::VecDeque,
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
 
 use bevy::{ecs::world::CommandQueue, platform::collections::HashMap, prelude::*};
-use bevy_common_systems::prelude::EventWorld;
+use bevy_common_systems::prelude::{DestroyedMarker, EventWorld, Health};
+use bevy_rand::prelude::*;
+use nova_events::prelude::*;
 use nova_gameplay::prelude::*;
+use rand::RngCore;
 
 use crate::prelude::*;
 
@@ -12,10 +19,62 @@ pub struct NovaEventWorld {
     objectives: Vec<ObjectiveActionConfig>,
     variables: HashMap<String, VariableLiteral>,
     pub next_scenario: Option<NextScenarioActionConfig>,
+    /// Snapshot of the scenario objects, taken before the events are processed.
+    entities: Vec<ScenarioEntityState>,
+    /// State of the random number generator used by expressions.
+    random_state: AtomicU64,
+    timers: Vec<ScenarioTimer>,
+}
+
+/// The state of a scenario object that can be queried from expressions.
+#[derive(Clone, Debug)]
+pub struct ScenarioEntityState {
+    pub id: String,
+    pub type_name: String,
+    pub position: Vec3,
+    pub alive: bool,
+    /// The remaining health, between 0 and 1
+    pub health: f32,
 }
 
 impl EventWorld for NovaEventWorld {
-    fn world_to_state_system(_world: &mut World) {}
+    fn world_to_state_system(world: &mut World) {
+        // Take a snapshot of the scenario objects for the expressions
+        let mut q_entities = world.query_filtered::<(
+            &EntityId,
+            &EntityTypeName,
+            &Transform,
+            Option<&Health>,
+            Has<DestroyedMarker>,
+        ), With<ScenarioScopedMarker>>();
+        let entities = q_entities
+            .iter(world)
+            .map(
+                |(id, type_name, transform, health, destroyed)| ScenarioEntityState {
+                    id: id.0.clone(),
+                    type_name: type_name.0.clone(),
+                    position: transform.translation,
+                    alive: !destroyed,
+                    health: health.map(Health::fraction).unwrap_or(1.0),
+                },
+            )
+            .collect();
+
+        // Seed the expressions from the global rng, so that they are deterministic with it
+        let seed = world
+            .query_filtered::<&mut WyRand, With<GlobalRng>>()
+            .single_mut(world)
+            .ok()
+            .map(|mut rng| rng.next_u64());
+
+        // Don't trigger change detection, otherwise the events would be processed every frame
+        let mut event_world = world.resource_mut::<Self>();
+        let event_world = event_world.bypass_change_detection();
+        event_world.entities = entities;
+        if let Some(seed) = seed {
+            event_world.random_state = AtomicU64::new(seed);
+        }
+    }
 
     fn state_to_world_system(world: &mut World) {
         // Copy the objectives to the bevy world
@@ -73,6 +132,8 @@ impl NovaEventWorld {
         self.objectives.clear();
         self.variables.clear();
         self.next_scenario = None;
+        self.entities.clear();
+        self.timers.clear();
     }
 
     pub fn push_command<F>(&mut self, f: F)
@@ -97,4 +158,72 @@ impl NovaEventWorld {
     pub fn get_variable(&self, key: &str) -> Option<&VariableLiteral> {
         self.variables.get(key)
     }
+
+    pub fn variables(&self) -> &HashMap<String, VariableLiteral> {
+        &self.variables
+    }
+
+    pub fn objectives(&self) -> &[ObjectiveActionConfig] {
+        &self.objectives
+    }
+
+    pub fn timers(&self) -> &[ScenarioTimer] {
+        &self.timers
+    }
+
+    pub fn entities(&self) -> &[ScenarioEntityState] {
+        &self.entities
+    }
+
+    pub fn get_entity(&self, id: &str) -> Option<&ScenarioEntityState> {
+        self.entities.iter().find(|entity| entity.id == id)
+    }
+
+    pub fn set_entities(&mut self, entities: Vec<ScenarioEntityState>) {
+        self.entities = entities;
+    }
+
+    /// Schedule a timer. A timer with the same id replaces the existing one.
+    pub fn push_timer(&mut self, timer: ScenarioTimer) {
+        if let Some(id) = &timer.id {
+            self.cancel_timer(id);
+        }
+        self.timers.push(timer);
+    }
+
+    pub fn cancel_timer(&mut self, id: &str) {
+        self.timers.retain(|timer| timer.id.as_deref() != Some(id));
+    }
+
+    /// Advance the timers, returning the ones that fired (once per time they fired). Timers that
+    /// will not fire again are removed.
+    pub fn tick_timers(&mut self, delta: Duration) -> Vec<ScenarioTimer> {
+        let mut fired = Vec::new();
+        for timer in self.timers.iter_mut() {
+            timer.timer.tick(delta);
+            for _ in 0..timer.timer.times_finished_this_tick() {
+                fired.push(timer.clone());
+            }
+        }
+
+        self.timers.retain(|timer| {
+            timer.timer.mode() == TimerMode::Repeating || !timer.timer.is_finished()
+        });
+
+        fired
+    }
+
+    /// A random number in the range `[0, 1)`.
+    pub fn random(&self) -> f64 {
+        // splitmix64, which only needs a counter as state
+        let mut z = self
+            .random_state
+            .fetch_add(0x9E37_79B9_7F4A_7C15, Ordering::Relaxed)
+            .wrapping_add(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+
+        (z >> 11) as f64 / (1u64 << 53) as f64
+    }
 }
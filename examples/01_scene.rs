@@ -44,6 +44,8 @@ pub fn test_scenario(game_assets: &GameAssets) -> ScenarioConfig {
                 position: pos,
                 rotation: Quat::IDENTITY,
                 health: 100.0,
+                faction: None,
+                explosion: None,
             },
             kind: ScenarioObjectKind::Asteroid(AsteroidConfig { radius, texture }),
         });
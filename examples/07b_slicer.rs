@@ -43,6 +43,7 @@ fn on_click_damage_health(
             target: click.entity,
             source: None,
             amount: 100.0,
+            kind: DamageKind::Scripted,
         });
     }
 }
@@ -79,6 +80,8 @@ pub fn test_scenario(game_assets: &GameAssets) -> ScenarioConfig {
                 position: pos,
                 rotation: Quat::IDENTITY,
                 health: 100.0,
+                faction: None,
+                explosion: None,
             },
             kind: ScenarioObjectKind::Asteroid(AsteroidConfig { radius, texture }),
         });
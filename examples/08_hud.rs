@@ -48,6 +48,8 @@ pub fn test_scenario(game_assets: &GameAssets, sections: Res<GameSections>) -> S
                 position: pos,
                 rotation: Quat::IDENTITY,
                 health: 100.0,
+                faction: None,
+                explosion: None,
             },
             kind: ScenarioObjectKind::Asteroid(AsteroidConfig { radius, texture }),
         });
@@ -74,6 +76,8 @@ pub fn test_scenario(game_assets: &GameAssets, sections: Res<GameSections>) -> S
             position: Vec3::ZERO,
             rotation: Quat::IDENTITY,
             health: 500.0,
+            faction: None,
+            explosion: None,
         },
         kind: ScenarioObjectKind::Spaceship(spaceship),
     });